@@ -0,0 +1,232 @@
+//! OHLCV candle aggregation built from the `events:price` tick stream.
+//!
+//! Maintains rolling 1m/5m/15m/1h buckets per token; on bucket rollover the
+//! completed candle is persisted to `Database` and published both on
+//! `events:candles` (raw `Candle`, for dashboards/history) and back onto
+//! `events:price` as `MarketEvent::Candle` (so strategies can subscribe to
+//! it the same way they subscribe to any other market event). A startup
+//! backfill pass reconstructs recently-completed candles from the existing
+//! `events:price` stream history so a restart doesn't leave a gap before
+//! live aggregation catches up; backfill only ever replays ticks and never
+//! touches the live loop's in-memory buckets, so a gap in one can't corrupt
+//! the other. A bucket is only finalized once a tick for the *next* bucket
+//! arrives, so a partial/in-progress candle never leaks to strategies.
+use crate::database::Database;
+use anyhow::{Context, Result};
+use redis::AsyncCommands;
+use shared_models::{Candle, MarketEvent, PriceTick};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+/// (label, seconds) for every interval we aggregate.
+const INTERVALS: &[(&str, i64)] = &[("1m", 60), ("5m", 300), ("15m", 900), ("1h", 3600)];
+
+const CANDLES_STREAM: &str = "events:candles";
+const PRICE_STREAM: &str = "events:price";
+const BACKFILL_COUNT: usize = 2000;
+
+pub struct CandleAggregator {
+    db: Arc<Database>,
+    redis_client: redis::Client,
+}
+
+/// In-progress bucket for one (token, interval) pair.
+struct Bucket {
+    bucket_start_ts: i64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume_usd: f64,
+}
+
+impl Bucket {
+    fn new(bucket_start_ts: i64, price: f64, volume_usd: f64) -> Self {
+        Self {
+            bucket_start_ts,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume_usd,
+        }
+    }
+
+    fn update(&mut self, price: f64, volume_usd: f64) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.volume_usd += volume_usd;
+    }
+
+    fn into_candle(self, token_address: String, interval: &str) -> Candle {
+        Candle {
+            token_address,
+            interval: interval.to_string(),
+            bucket_start_ts: self.bucket_start_ts,
+            open: self.open,
+            high: self.high,
+            low: self.low,
+            close: self.close,
+            volume_usd: self.volume_usd,
+        }
+    }
+}
+
+fn bucket_start(timestamp_ms: i64, interval_secs: i64) -> i64 {
+    let timestamp_secs = timestamp_ms / 1000;
+    (timestamp_secs / interval_secs) * interval_secs
+}
+
+impl CandleAggregator {
+    pub fn new(db: Arc<Database>, redis_client: redis::Client) -> Self {
+        Self { db, redis_client }
+    }
+
+    pub async fn run(self) -> Result<()> {
+        if let Err(e) = self.backfill().await {
+            error!("Candle backfill failed, continuing with live aggregation only: {}", e);
+        }
+        self.live_loop().await
+    }
+
+    /// Reconstruct recently-completed candles from `events:price` history.
+    /// The still-open final bucket is left for the live loop to pick up fresh,
+    /// so backfill and live aggregation never fight over the same in-memory state.
+    async fn backfill(&self) -> Result<()> {
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        let reply: redis::streams::StreamRangeReply = conn
+            .xrevrange_count(PRICE_STREAM, "+", "-", BACKFILL_COUNT)
+            .await
+            .context("Failed to read events:price history for candle backfill")?;
+
+        info!("🕯️ Backfilling candles from {} historical price ticks", reply.ids.len());
+
+        let mut buckets: HashMap<(String, &'static str), Bucket> = HashMap::new();
+
+        // xrevrange returns newest-first; replay oldest-first so OHLC order is correct.
+        for message in reply.ids.into_iter().rev() {
+            let Some(data) = message.map.get("data") else { continue };
+            let Ok(data_str) = redis::from_redis_value::<String>(data) else { continue };
+            let Ok(tick) = serde_json::from_str::<PriceTick>(&data_str) else { continue };
+
+            for (label, interval_secs) in INTERVALS {
+                let start = bucket_start(tick.timestamp_ms, *interval_secs);
+                let key = (tick.token_address.clone(), *label);
+
+                match buckets.get_mut(&key) {
+                    Some(bucket) if bucket.bucket_start_ts == start => {
+                        bucket.update(tick.price_usd, tick.volume_usd_1m);
+                    }
+                    Some(bucket) => {
+                        let completed = std::mem::replace(bucket, Bucket::new(start, tick.price_usd, tick.volume_usd_1m));
+                        if let Err(e) = self.db.upsert_candle(&completed.into_candle(tick.token_address.clone(), label)) {
+                            warn!("Failed to persist backfilled candle: {}", e);
+                        }
+                    }
+                    None => {
+                        buckets.insert(key, Bucket::new(start, tick.price_usd, tick.volume_usd_1m));
+                    }
+                }
+            }
+        }
+
+        // Deliberately drop the still-open buckets left in `buckets` — they're
+        // incomplete and the live loop will rebuild them from the next tick onward.
+        Ok(())
+    }
+
+    async fn live_loop(&self) -> Result<()> {
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        let mut last_id = "$".to_string();
+        let mut buckets: HashMap<(String, &'static str), Bucket> = HashMap::new();
+
+        info!("🕯️ Candle aggregator live loop started, watching {}", PRICE_STREAM);
+
+        loop {
+            let reply: redis::streams::StreamReadReply = match conn
+                .xread_options(
+                    &[PRICE_STREAM],
+                    &[last_id.as_str()],
+                    &redis::streams::StreamReadOptions::default().block(5000).count(200),
+                )
+                .await
+            {
+                Ok(r) => r,
+                Err(e) => {
+                    warn!("Candle aggregator redis read error: {}, retrying", e);
+                    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                    continue;
+                }
+            };
+
+            for stream_key in reply.keys {
+                for message in stream_key.ids {
+                    last_id = message.id.clone();
+                    let Some(data) = message.map.get("data") else { continue };
+                    let Ok(data_str) = redis::from_redis_value::<String>(data) else { continue };
+                    let Ok(tick) = serde_json::from_str::<PriceTick>(&data_str) else { continue };
+
+                    for (label, interval_secs) in INTERVALS {
+                        let start = bucket_start(tick.timestamp_ms, *interval_secs);
+                        let key = (tick.token_address.clone(), *label);
+
+                        match buckets.get_mut(&key) {
+                            Some(bucket) if bucket.bucket_start_ts == start => {
+                                bucket.update(tick.price_usd, tick.volume_usd_1m);
+                            }
+                            Some(bucket) => {
+                                let completed = std::mem::replace(
+                                    bucket,
+                                    Bucket::new(start, tick.price_usd, tick.volume_usd_1m),
+                                );
+                                self.finalize(completed.into_candle(tick.token_address.clone(), label)).await;
+                            }
+                            None => {
+                                buckets.insert(key, Bucket::new(start, tick.price_usd, tick.volume_usd_1m));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn finalize(&self, candle: Candle) {
+        if let Err(e) = self.db.upsert_candle(&candle) {
+            error!("Failed to persist candle: {}", e);
+        }
+
+        let mut conn = match self.redis_client.get_multiplexed_async_connection().await {
+            Ok(c) => c,
+            Err(e) => {
+                error!("Failed to get redis connection to publish candle: {}", e);
+                return;
+            }
+        };
+
+        // Raw candle for dashboards/historical consumers.
+        match serde_json::to_string(&candle) {
+            Ok(payload) => {
+                let result: redis::RedisResult<()> = conn.xadd(CANDLES_STREAM, "*", &[("data", payload)]).await;
+                if let Err(e) = result {
+                    error!("Failed to publish candle to {}: {}", CANDLES_STREAM, e);
+                }
+            }
+            Err(e) => error!("Failed to serialize candle: {}", e),
+        }
+
+        // Same candle wrapped as a MarketEvent on events:price so strategies
+        // can subscribe to it via EventType::Candle like any other event.
+        match serde_json::to_string(&MarketEvent::Candle(candle)) {
+            Ok(payload) => {
+                let result: redis::RedisResult<()> = conn.xadd(PRICE_STREAM, "*", &[("data", payload)]).await;
+                if let Err(e) = result {
+                    error!("Failed to publish candle MarketEvent to {}: {}", PRICE_STREAM, e);
+                }
+            }
+            Err(e) => error!("Failed to serialize candle MarketEvent: {}", e),
+        }
+    }
+}
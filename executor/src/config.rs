@@ -1,5 +1,6 @@
 use std::env;
 use anyhow::{Context, Result};
+use lazy_static::lazy_static;
 
 pub struct Config {
     pub paper_trading_mode: bool,
@@ -23,6 +24,79 @@ pub struct Config {
     pub twitter_bearer_token: String,
     pub drift_api_url: String,
     pub farcaster_api_url: String,
+    pub ws_fanout_bind_addr: String,
+    /// Bind address for the shadow-ledger/live-fills WS fan-out server, kept
+    /// separate from `ws_fanout_bind_addr` since it's subscribed to by
+    /// strategy_id rather than channel name.
+    pub fills_ws_bind_addr: String,
+    pub metrics_bind_addr: String,
+    /// Bind address for the control-plane HTTP API (list/stop/start
+    /// strategies, inject a synthetic event, read the current SOL/USD price).
+    /// Defaults to loopback-only since this can force a strategy live or
+    /// inject a fabricated market event; widen it deliberately, and set
+    /// `control_api_token` when doing so.
+    pub control_api_bind_addr: String,
+    /// Bearer token `control_api` requires on every request when set. `None`
+    /// (the default) means the API is unauthenticated, which is only safe
+    /// alongside the default loopback-only `control_api_bind_addr`.
+    pub control_api_token: Option<String>,
+    /// Floor for `SetComputeUnitPrice`, in micro-lamports per CU: the minimum
+    /// for every estimate, and the value used when
+    /// `getRecentPrioritizationFees` returns no samples.
+    pub priority_fee_floor_micro_lamports: u64,
+    /// Caps the estimated total priority fee for a single swap so a fee spike
+    /// can't dwarf a small order.
+    pub priority_fee_max_usd: f64,
+    /// When true, `JupiterClient` serves deterministic quotes/prices from an
+    /// in-memory map instead of calling `quote-api.jup.ag`, so the simulated
+    /// trading pipeline is reproducible in CI.
+    pub mock_jupiter: bool,
+    /// Optional JSON fixture of `{ "<token_mint>": <price_usd> }` to seed the
+    /// mock Jupiter backend's price map.
+    pub mock_jupiter_fixture_path: Option<String>,
+    /// Pyth/Hermes REST base URL, used by `SolPriceOracle` as a fallback SOL/USD
+    /// source when Jupiter's price endpoint is unavailable.
+    pub pyth_hermes_url: String,
+    /// How often `SolPriceOracle` refreshes its cached SOL/USD price.
+    pub sol_price_refresh_interval_secs: u64,
+    /// How old the oracle's last good SOL/USD price may be before callers
+    /// treat it as unusable rather than sizing orders off a stale quote.
+    pub sol_price_staleness_ttl_secs: u64,
+    /// WS endpoint `SolPriceWsFeed` subscribes to for live SOL/USD ticks; the
+    /// freshest source in `RateAggregator`'s priority list when reachable.
+    pub sol_price_ws_url: String,
+    /// Text frame sent immediately after connecting to `sol_price_ws_url`.
+    pub sol_price_ws_subscribe_frame: String,
+    /// Max age (across all `LatestRate` sources combined) before a token's
+    /// price is considered too stale to size a trade off of.
+    pub price_max_staleness_secs: u64,
+    /// How many concurrent worker tasks pull `OrderDetails` candidates off
+    /// the execution queue and turn them into quotes/transactions, so a
+    /// strategy's signal loop never blocks waiting on Jupiter.
+    pub executor_worker_pool_size: usize,
+    /// Hard ceiling on a single `get_quote`/`get_swap_transaction` call; a
+    /// candidate whose quote doesn't return within this window is dropped
+    /// rather than allowed to stall its worker indefinitely.
+    pub jupiter_quote_timeout_secs: u64,
+    /// How many live-trade signatures `ConfirmationTracker` polls concurrently;
+    /// bounds the number of in-flight confirmation tasks a burst of trades
+    /// can spawn.
+    pub confirmation_worker_pool_size: usize,
+    /// How long a signature may sit unconfirmed before its blockhash is
+    /// assumed expired and `ConfirmationTracker` rebroadcasts (or, once
+    /// `tx_rebroadcast_max_retries` is exhausted, marks the trade failed).
+    pub tx_confirmation_timeout_secs: u64,
+    /// How many times `ConfirmationTracker` will re-sign and rebroadcast a
+    /// trade whose blockhash expired before landing, before giving up and
+    /// marking it terminally failed.
+    pub tx_rebroadcast_max_retries: u32,
+    /// Path to the JSON file mapping strategy id -> `{mode, ...init params}`,
+    /// read by `StrategyConfigStore` at startup and re-read on a timer so
+    /// operators can retune a running strategy without a restart.
+    pub strategy_config_path: String,
+    /// How often `StrategyConfigStore` checks `strategy_config_path`'s mtime
+    /// for changes.
+    pub strategy_config_reload_interval_secs: u64,
 }
 
 impl Config {
@@ -69,13 +143,75 @@ impl Config {
             twitter_bearer_token: env::var("TWITTER_BEARER_TOKEN").unwrap_or_else(|_| "demo_token".to_string()),
             drift_api_url: env::var("DRIFT_API_URL").unwrap_or_else(|_| "https://api.drift.trade".to_string()),
             farcaster_api_url: env::var("FARCASTER_API_URL").unwrap_or_else(|_| "https://api.neynar.com/v2".to_string()),
+            ws_fanout_bind_addr: env::var("WS_FANOUT_BIND_ADDR").unwrap_or_else(|_| "0.0.0.0:9187".to_string()),
+            fills_ws_bind_addr: env::var("FILLS_WS_BIND_ADDR").unwrap_or_else(|_| "0.0.0.0:9189".to_string()),
+            metrics_bind_addr: env::var("METRICS_BIND_ADDR").unwrap_or_else(|_| "0.0.0.0:9188".to_string()),
+            control_api_bind_addr: env::var("CONTROL_API_BIND_ADDR").unwrap_or_else(|_| "127.0.0.1:9190".to_string()),
+            control_api_token: env::var("CONTROL_API_TOKEN").ok(),
+            priority_fee_floor_micro_lamports: env::var("PRIORITY_FEE_FLOOR_MICRO_LAMPORTS")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .context("PRIORITY_FEE_FLOOR_MICRO_LAMPORTS must be a valid number")?,
+            priority_fee_max_usd: env::var("PRIORITY_FEE_MAX_USD")
+                .unwrap_or_else(|_| "0.50".to_string())
+                .parse()
+                .context("PRIORITY_FEE_MAX_USD must be a valid number")?,
+            mock_jupiter: env::var("MOCK_JUPITER").unwrap_or_else(|_| "false".to_string()) == "true",
+            mock_jupiter_fixture_path: env::var("MOCK_JUPITER_FIXTURE_PATH").ok(),
+            pyth_hermes_url: env::var("PYTH_HERMES_URL").unwrap_or_else(|_| "https://hermes.pyth.network".to_string()),
+            sol_price_refresh_interval_secs: env::var("SOL_PRICE_REFRESH_INTERVAL_SECS")
+                .unwrap_or_else(|_| "15".to_string())
+                .parse()
+                .context("SOL_PRICE_REFRESH_INTERVAL_SECS must be a valid number")?,
+            sol_price_staleness_ttl_secs: env::var("SOL_PRICE_STALENESS_TTL_SECS")
+                .unwrap_or_else(|_| "120".to_string())
+                .parse()
+                .context("SOL_PRICE_STALENESS_TTL_SECS must be a valid number")?,
+            sol_price_ws_url: env::var("SOL_PRICE_WS_URL")
+                .unwrap_or_else(|_| "wss://hermes.pyth.network/ws".to_string()),
+            sol_price_ws_subscribe_frame: env::var("SOL_PRICE_WS_SUBSCRIBE_FRAME").unwrap_or_else(|_| {
+                r#"{"type":"subscribe","channel":"ticker","symbol":"SOL/USD"}"#.to_string()
+            }),
+            price_max_staleness_secs: env::var("PRICE_MAX_STALENESS_SECS")
+                .unwrap_or_else(|_| "120".to_string())
+                .parse()
+                .context("PRICE_MAX_STALENESS_SECS must be a valid number")?,
+            executor_worker_pool_size: env::var("EXECUTOR_WORKER_POOL_SIZE")
+                .unwrap_or_else(|_| "4".to_string())
+                .parse()
+                .context("EXECUTOR_WORKER_POOL_SIZE must be a valid number")?,
+            jupiter_quote_timeout_secs: env::var("JUPITER_QUOTE_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .context("JUPITER_QUOTE_TIMEOUT_SECS must be a valid number")?,
+            confirmation_worker_pool_size: env::var("CONFIRMATION_WORKER_POOL_SIZE")
+                .unwrap_or_else(|_| "4".to_string())
+                .parse()
+                .context("CONFIRMATION_WORKER_POOL_SIZE must be a valid number")?,
+            tx_confirmation_timeout_secs: env::var("TX_CONFIRMATION_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "90".to_string())
+                .parse()
+                .context("TX_CONFIRMATION_TIMEOUT_SECS must be a valid number")?,
+            tx_rebroadcast_max_retries: env::var("TX_REBROADCAST_MAX_RETRIES")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .context("TX_REBROADCAST_MAX_RETRIES must be a valid number")?,
+            strategy_config_path: env::var("STRATEGY_CONFIG_PATH").unwrap_or_else(|_| "strategies.json".to_string()),
+            strategy_config_reload_interval_secs: env::var("STRATEGY_CONFIG_RELOAD_INTERVAL_SECS")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .context("STRATEGY_CONFIG_RELOAD_INTERVAL_SECS must be a valid number")?,
         };
-        
+
         println!("Configuration loaded successfully");
         println!("Paper trading mode: {}", config.paper_trading_mode);
         println!("Redis URL: {}", config.redis_url);
         println!("Signer URL: {}", config.signer_url);
-        
+
         Ok(config)
     }
 }
+
+lazy_static! {
+    pub static ref CONFIG: Config = Config::load().expect("Failed to load configuration - check environment variables");
+}
@@ -0,0 +1,291 @@
+//! Tracks live-trade signatures through to an on-chain outcome. Mirrors the
+//! candidate-queue/worker-pool split in `executor.rs`: `execute_trade` only
+//! ever enqueues a signature here, a bounded pool of workers polls the RPC
+//! for its status, and the eventual confirmed/reverted/expired outcome is
+//! written back to the trade record so PnL/allocation logic never counts a
+//! phantom fill. A signature whose blockhash expires before it lands is
+//! re-signed against a fresh blockhash and rebroadcast (up to
+//! `CONFIG.tx_rebroadcast_max_retries` times) rather than given up on
+//! outright, since an expired blockhash says nothing about whether the trade
+//! itself was a bad idea.
+use crate::config::CONFIG;
+use crate::database::Database;
+use crate::executor::emit_fill_event;
+use crate::jito_client::JitoClient;
+use crate::jupiter::decompile;
+use crate::signer_client::SignerClient;
+use anyhow::Result;
+use base64::{Engine as _, engine::general_purpose};
+use shared_models::{CircuitBreaker, FillStatus, OrderDetails, TokenAmount, TradeMode};
+use solana_sdk::{
+    message::{Message, VersionedMessage},
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{
+    mpsc::{self, Receiver, Sender},
+    Mutex,
+};
+use tracing::{error, info, warn};
+
+/// Capacity of the queue sitting between live sends and the confirmation
+/// worker pool; bounded for the same reason `CANDIDATE_QUEUE_CAPACITY` is.
+const CONFIRMATION_QUEUE_CAPACITY: usize = 256;
+
+/// How often a confirmation worker re-polls the RPC for a pending signature.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+struct PendingTx {
+    trade_id: i64,
+    strategy_id: String,
+    signature: Signature,
+    /// The signed transaction as sent, kept around so a rebroadcast can
+    /// decompile it, swap in a fresh blockhash, and have the signer re-sign
+    /// the same instructions rather than re-deriving the trade from scratch.
+    tx: VersionedTransaction,
+    last_valid_block_height: u64,
+    /// How many times this trade has been (re)signed and sent; 0 on the
+    /// first send, incremented on every rebroadcast.
+    attempt: u32,
+    /// Everything `emit_fill_event` needs beyond `trade_id`/`strategy_id`, so
+    /// a reverted/expired outcome discovered here can publish the same
+    /// `FillStatus::Revoked` record the synchronous send-failure path in
+    /// `executor.rs` does, instead of only updating the local trade record.
+    details: OrderDetails,
+    mode: TradeMode,
+    price_usd: f64,
+    price_source: &'static str,
+    filled_size_token: TokenAmount,
+    slippage_pct: f64,
+}
+
+pub struct ConfirmationTracker {
+    tx: Sender<PendingTx>,
+    redis_client: redis::Client,
+}
+
+impl ConfirmationTracker {
+    /// Spawns `CONFIG.confirmation_worker_pool_size` workers draining a
+    /// bounded queue of pending confirmations, so a burst of live trades
+    /// can't spawn one polling task per signature.
+    pub fn new(
+        db: Arc<Database>,
+        jito_client: Arc<JitoClient>,
+        signer_client: Arc<SignerClient>,
+        circuit_breaker: Arc<CircuitBreaker>,
+        redis_client: redis::Client,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel(CONFIRMATION_QUEUE_CAPACITY);
+        let rx = Arc::new(Mutex::new(rx));
+        for worker_id in 0..CONFIG.confirmation_worker_pool_size {
+            tokio::spawn(confirmation_worker(
+                worker_id,
+                rx.clone(),
+                db.clone(),
+                jito_client.clone(),
+                signer_client.clone(),
+                circuit_breaker.clone(),
+                redis_client.clone(),
+            ));
+        }
+        Self { tx, redis_client }
+    }
+
+    /// Queues a just-sent transaction for confirmation tracking. Drops it
+    /// with a warning rather than blocking the caller if the queue is full —
+    /// a live send whose confirmation is lost is still safer than stalling
+    /// the execution worker that's sending it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn track(
+        &self,
+        trade_id: i64,
+        strategy_id: String,
+        signature: Signature,
+        tx: VersionedTransaction,
+        last_valid_block_height: u64,
+        details: OrderDetails,
+        mode: TradeMode,
+        price_usd: f64,
+        price_source: &'static str,
+        filled_size_token: TokenAmount,
+        slippage_pct: f64,
+    ) {
+        let pending = PendingTx {
+            trade_id,
+            strategy_id,
+            signature,
+            tx,
+            last_valid_block_height,
+            attempt: 0,
+            details,
+            mode,
+            price_usd,
+            price_source,
+            filled_size_token,
+            slippage_pct,
+        };
+        if self.tx.try_send(pending).is_err() {
+            warn!(trade_id, %signature, "Confirmation queue full, dropping signature untracked");
+        }
+    }
+}
+
+async fn confirmation_worker(
+    worker_id: usize,
+    rx: Arc<Mutex<Receiver<PendingTx>>>,
+    db: Arc<Database>,
+    jito_client: Arc<JitoClient>,
+    signer_client: Arc<SignerClient>,
+    circuit_breaker: Arc<CircuitBreaker>,
+    redis_client: redis::Client,
+) {
+    info!(worker_id, "Confirmation worker started.");
+    loop {
+        let pending = {
+            let mut rx = rx.lock().await;
+            rx.recv().await
+        };
+        let Some(pending) = pending else {
+            info!(worker_id, "Confirmation queue closed, worker exiting.");
+            return;
+        };
+
+        let trade_id = pending.trade_id;
+        if let Err(e) =
+            track_to_outcome(&db, &jito_client, &signer_client, &circuit_breaker, &redis_client, pending).await
+        {
+            error!(worker_id, trade_id, error = %e, "Confirmation tracking failed");
+        }
+    }
+}
+
+/// Polls a pending transaction through to a terminal outcome, rebroadcasting
+/// in between as its blockhash expires.
+async fn track_to_outcome(
+    db: &Database,
+    jito_client: &JitoClient,
+    signer_client: &SignerClient,
+    circuit_breaker: &CircuitBreaker,
+    redis_client: &redis::Client,
+    mut pending: PendingTx,
+) -> Result<()> {
+    loop {
+        let deadline = Instant::now() + Duration::from_secs(CONFIG.tx_confirmation_timeout_secs);
+        loop {
+            match jito_client.get_signature_status(&pending.signature).await? {
+                Some(Ok(())) => {
+                    db.confirm_trade(pending.trade_id)?;
+                    circuit_breaker.record_execution_success();
+                    info!(
+                        trade_id = pending.trade_id,
+                        signature = %pending.signature,
+                        attempt = pending.attempt,
+                        "Live trade confirmed on-chain"
+                    );
+                    return Ok(());
+                }
+                Some(Err(tx_err)) => {
+                    db.fail_trade(pending.trade_id, "REVERTED")?;
+                    warn!(
+                        trade_id = pending.trade_id,
+                        signature = %pending.signature,
+                        error = %tx_err,
+                        "Live trade reverted on-chain"
+                    );
+                    emit_revoked_fill(redis_client, &pending).await;
+                    return Ok(());
+                }
+                None => {
+                    let blockhash_expired = jito_client.get_block_height().await? > pending.last_valid_block_height;
+                    if blockhash_expired || Instant::now() >= deadline {
+                        break;
+                    }
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                }
+            }
+        }
+
+        if pending.attempt >= CONFIG.tx_rebroadcast_max_retries {
+            db.fail_trade(pending.trade_id, "EXPIRED")?;
+            circuit_breaker.record_execution_failure();
+            warn!(
+                trade_id = pending.trade_id,
+                signature = %pending.signature,
+                attempt = pending.attempt,
+                "Live trade signature expired without confirmation; rebroadcast retries exhausted"
+            );
+            emit_revoked_fill(redis_client, &pending).await;
+            return Ok(());
+        }
+
+        pending = rebroadcast(db, jito_client, signer_client, pending).await?;
+    }
+}
+
+/// Publishes the `FillStatus::Revoked` record a reverted/expired outcome
+/// needs, same as the synchronous immediate-send-failure path in
+/// `executor.rs` does — otherwise a consumer of `events:fills` (e.g. the
+/// fills fan-out server) keeps counting this trade as a successful `New`
+/// fill even after `db.fail_trade` has already corrected the local record.
+/// Logged rather than propagated on failure: losing this publish shouldn't
+/// stop the tracker from moving on to its next pending signature.
+async fn emit_revoked_fill(redis_client: &redis::Client, pending: &PendingTx) {
+    if let Err(e) = emit_fill_event(
+        redis_client,
+        pending.trade_id,
+        &pending.strategy_id,
+        &pending.details,
+        pending.mode,
+        pending.price_usd,
+        pending.price_source,
+        pending.filled_size_token,
+        pending.slippage_pct,
+        FillStatus::Revoked,
+    )
+    .await
+    {
+        error!(trade_id = pending.trade_id, error = %e, "Failed to publish Revoked fill event");
+    }
+}
+
+/// Re-signs `pending`'s instructions against a fresh blockhash and resends,
+/// returning the updated `PendingTx` for the next polling pass.
+async fn rebroadcast(
+    db: &Database,
+    jito_client: &JitoClient,
+    signer_client: &SignerClient,
+    mut pending: PendingTx,
+) -> Result<PendingTx> {
+    pending.attempt += 1;
+    let (instructions, _account_keys, payer) = decompile(&pending.tx.message)?;
+    let (recent_blockhash, last_valid_block_height) = jito_client.get_recent_blockhash().await?;
+
+    let mut message = Message::new(&instructions, Some(&payer));
+    message.recent_blockhash = recent_blockhash;
+    let unsigned_tx = VersionedTransaction {
+        signatures: vec![Default::default(); message.header.num_required_signatures as usize],
+        message: VersionedMessage::Legacy(message),
+    };
+    let unsigned_tx_b64 = general_purpose::STANDARD.encode(bincode::serialize(&unsigned_tx)?);
+
+    let signed_tx_b64 = signer_client.sign_transaction(&unsigned_tx_b64).await?;
+    let tx: VersionedTransaction = bincode::deserialize(&general_purpose::STANDARD.decode(signed_tx_b64)?)?;
+
+    let signature = jito_client.send_transaction(&tx).await?;
+    db.open_trade(pending.trade_id, &signature.to_string())?;
+    info!(
+        trade_id = pending.trade_id,
+        strategy_id = %pending.strategy_id,
+        attempt = pending.attempt,
+        old_signature = %pending.signature,
+        new_signature = %signature,
+        "Rebroadcast live trade with a fresh blockhash"
+    );
+
+    pending.tx = tx;
+    pending.signature = signature;
+    pending.last_valid_block_height = last_valid_block_height;
+    Ok(pending)
+}
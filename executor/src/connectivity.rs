@@ -0,0 +1,85 @@
+//! Connectivity watchdog for the executor's always-on upstreams: the Redis
+//! connection `run()`'s event pump reads from, and the SOL/USD price WS feed.
+//! Both already reconnect themselves on a hard error (`run()`'s xread arms
+//! replace their connection on failure; `SolPriceWsFeed` has its own backoff
+//! loop), but neither is checked on a fixed cadence independent of whether
+//! events are actually flowing — an idle-but-dead socket would otherwise sit
+//! unnoticed until the next publish. This watchdog pings Redis directly on a
+//! timer and reads `SolPriceWsFeed`'s own staleness check, so `is_healthy()`
+//! reflects reality even during a quiet period.
+use crate::sol_price_ws_feed::SolPriceWsFeed;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Point-in-time view of both upstreams, as reported by the control API.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ConnectivityStatus {
+    pub redis_healthy: bool,
+    pub price_ws_healthy: bool,
+}
+
+pub struct ConnectivityMonitor {
+    redis_client: redis::Client,
+    price_ws_feed: Arc<SolPriceWsFeed>,
+    redis_healthy: AtomicBool,
+}
+
+impl ConnectivityMonitor {
+    pub fn new(redis_client: redis::Client, price_ws_feed: Arc<SolPriceWsFeed>) -> Self {
+        Self { redis_client, price_ws_feed, redis_healthy: AtomicBool::new(true) }
+    }
+
+    /// `self` must be wrapped in an `Arc` so the spawned task can outlive the
+    /// caller, same as `SolPriceOracle::spawn_refresh`.
+    pub fn spawn(self: &Arc<Self>) {
+        let monitor = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                monitor.check_redis().await;
+            }
+        });
+    }
+
+    /// Opens a fresh connection and PINGs it rather than reusing `run()`'s
+    /// long-lived connection, so this check still detects a dead socket that
+    /// hasn't been touched by an xread recently.
+    async fn check_redis(&self) {
+        let result: redis::RedisResult<String> = async {
+            let mut conn = self.redis_client.get_async_connection().await?;
+            redis::cmd("PING").query_async(&mut conn).await
+        }
+        .await;
+
+        let was_healthy = self.redis_healthy.load(Ordering::Relaxed);
+        match result {
+            Ok(_) => {
+                if !was_healthy {
+                    info!("Redis connectivity restored");
+                }
+                self.redis_healthy.store(true, Ordering::Relaxed);
+            }
+            Err(e) => {
+                if was_healthy {
+                    warn!(error = %e, "Redis connectivity check failed");
+                } else {
+                    error!(error = %e, "Redis still unreachable");
+                }
+                self.redis_healthy.store(false, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub async fn status(&self) -> ConnectivityStatus {
+        ConnectivityStatus {
+            redis_healthy: self.redis_healthy.load(Ordering::Relaxed),
+            price_ws_healthy: self.price_ws_feed.is_connected().await,
+        }
+    }
+}
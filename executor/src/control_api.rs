@@ -0,0 +1,209 @@
+//! HTTP control plane for `MasterExecutor`, so an operator can list/stop/start
+//! strategies, push a synthetic `MarketEvent` for testing, or read the current
+//! SOL/USD price without restarting the process. Every request is forwarded
+//! as a `ControlCommand` over a channel rather than reaching into
+//! `MasterExecutor`'s state directly, so mutations still only ever happen on
+//! the executor's own task instead of racing its event loop from this one.
+use crate::connectivity::ConnectivityStatus;
+use crate::strategies::MarketEvent;
+use axum::{
+    extract::{Path, Request, State},
+    http::StatusCode,
+    middleware::{self, Next},
+    response::Response,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use shared_models::TradeMode;
+use std::sync::Arc;
+use tokio::sync::{mpsc::Sender, oneshot};
+use tracing::{error, info, warn};
+
+/// One running strategy's control-plane summary, as returned by `GET /strategies`.
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyInfo {
+    pub id: String,
+    pub mode: TradeMode,
+    pub uptime_secs: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StartStrategyRequest {
+    pub family: String,
+    #[serde(default)]
+    pub params: serde_json::Value,
+    pub mode: TradeMode,
+}
+
+pub enum ControlCommand {
+    ListStrategies {
+        reply: oneshot::Sender<Vec<StrategyInfo>>,
+    },
+    StopStrategy {
+        id: String,
+        reply: oneshot::Sender<bool>,
+    },
+    StartStrategy {
+        id: String,
+        family: String,
+        params: serde_json::Value,
+        mode: TradeMode,
+        reply: oneshot::Sender<Result<(), String>>,
+    },
+    InjectEvent {
+        event: MarketEvent,
+        reply: oneshot::Sender<()>,
+    },
+    GetSolPrice {
+        reply: oneshot::Sender<Option<f64>>,
+    },
+    GetConnectivity {
+        reply: oneshot::Sender<ConnectivityStatus>,
+    },
+    /// Sent by `StrategyConfigStore`'s file-watch task after it detects
+    /// `strategies.json` changed and re-read it; applies the new params to
+    /// every active strategy the file still describes. Replies with how
+    /// many strategies were updated.
+    ReloadStrategyConfig {
+        reply: oneshot::Sender<usize>,
+    },
+}
+
+#[derive(Clone)]
+struct ApiState {
+    tx: Sender<ControlCommand>,
+    /// Bearer token required on every request, from `CONFIG.control_api_token`.
+    /// `None` disables the check entirely (only safe alongside the default
+    /// loopback-only bind address).
+    token: Arc<Option<String>>,
+}
+
+/// Rejects any request whose `Authorization: Bearer <token>` header doesn't
+/// match `ApiState::token`, a no-op when no token is configured. Applied as a
+/// router-wide layer so new routes can't accidentally be added unauthenticated.
+async fn require_bearer_token(
+    State(state): State<ApiState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let Some(expected) = state.token.as_ref() else {
+        return Ok(next.run(request).await);
+    };
+
+    let provided = request
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+
+    if provided != Some(expected.as_str()) {
+        warn!("Rejected control API request with missing or invalid bearer token");
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    Ok(next.run(request).await)
+}
+
+/// Sends `build(reply)` to `MasterExecutor` and awaits its reply, mapping
+/// either half of the round trip being gone (executor shutting down) to 503
+/// instead of panicking the handler.
+async fn send<T>(
+    state: &ApiState,
+    build: impl FnOnce(oneshot::Sender<T>) -> ControlCommand,
+) -> Result<T, StatusCode> {
+    let (reply_tx, reply_rx) = oneshot::channel();
+    state
+        .tx
+        .send(build(reply_tx))
+        .await
+        .map_err(|_| StatusCode::SERVICE_UNAVAILABLE)?;
+    reply_rx.await.map_err(|_| StatusCode::SERVICE_UNAVAILABLE)
+}
+
+async fn list_strategies(State(state): State<ApiState>) -> Result<Json<Vec<StrategyInfo>>, StatusCode> {
+    let strategies = send(&state, |reply| ControlCommand::ListStrategies { reply }).await?;
+    Ok(Json(strategies))
+}
+
+async fn stop_strategy(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let stopped = send(&state, |reply| ControlCommand::StopStrategy { id, reply }).await?;
+    Ok(Json(serde_json::json!({ "stopped": stopped })))
+}
+
+async fn start_strategy(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+    Json(req): Json<StartStrategyRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let result = send(&state, |reply| ControlCommand::StartStrategy {
+        id,
+        family: req.family,
+        params: req.params,
+        mode: req.mode,
+        reply,
+    })
+    .await
+    .map_err(|code| (code, "executor control channel unavailable".to_string()))?;
+
+    match result {
+        Ok(()) => Ok(Json(serde_json::json!({ "started": true }))),
+        Err(reason) => Err((StatusCode::BAD_REQUEST, reason)),
+    }
+}
+
+async fn inject_event(
+    State(state): State<ApiState>,
+    Json(event): Json<MarketEvent>,
+) -> Result<StatusCode, StatusCode> {
+    send(&state, |reply| ControlCommand::InjectEvent { event, reply }).await?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+async fn get_sol_price(State(state): State<ApiState>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let price = send(&state, |reply| ControlCommand::GetSolPrice { reply }).await?;
+    Ok(Json(serde_json::json!({ "sol_usd_price": price })))
+}
+
+async fn get_connectivity(State(state): State<ApiState>) -> Result<Json<ConnectivityStatus>, StatusCode> {
+    let status = send(&state, |reply| ControlCommand::GetConnectivity { reply }).await?;
+    Ok(Json(status))
+}
+
+/// Spawns the control-plane HTTP server, forwarding every request onto `tx`
+/// (a clone of `MasterExecutor::control_handle()`). Requires a bearer token
+/// matching `CONFIG.control_api_token` on every request when one is set.
+pub fn spawn_server(bind_addr: &str, tx: Sender<ControlCommand>) {
+    let bind_addr = bind_addr.to_string();
+    tokio::spawn(async move {
+        let state = ApiState {
+            tx,
+            token: Arc::new(crate::config::CONFIG.control_api_token.clone()),
+        };
+        if state.token.is_none() {
+            warn!("CONTROL_API_TOKEN is not set; the control API is unauthenticated, relying solely on its bind address for protection.");
+        }
+        let app = Router::new()
+            .route("/strategies", get(list_strategies))
+            .route("/strategies/:id/stop", post(stop_strategy))
+            .route("/strategies/:id/start", post(start_strategy))
+            .route("/events/inject", post(inject_event))
+            .route("/sol-price", get(get_sol_price))
+            .route("/connectivity", get(get_connectivity))
+            .layer(middleware::from_fn_with_state(state.clone(), require_bearer_token))
+            .with_state(state);
+
+        match tokio::net::TcpListener::bind(&bind_addr).await {
+            Ok(listener) => {
+                info!("🎛️  Executor control API listening on {}", bind_addr);
+                if let Err(e) = axum::serve(listener, app).await {
+                    error!("Executor control API server failed: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to bind executor control API to {}: {}", bind_addr, e),
+        }
+    });
+}
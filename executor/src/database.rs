@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection};
-use shared_models::OrderDetails;
+use shared_models::{Candle, NavCandle, OrderDetails, TokenAmount};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 use tracing::info;
@@ -23,6 +23,10 @@ pub struct TradeRecord {
     pub confidence: f64,
     pub side: String,
     pub highest_price_usd: Option<f64>,
+    /// Exact on-chain base-unit size of the quote backing this trade, when
+    /// one was recorded at entry. `None` for trades logged before this
+    /// column existed.
+    pub quote_out_amount: Option<TokenAmount>,
 }
 
 pub struct Database {
@@ -37,51 +41,89 @@ impl Database {
         }
         let conn = Connection::open(path).with_context(|| format!("Failed to open database at {}", db_path))?;
         info!("Database opened at {}", db_path);
-        Self::init_db(&conn)?;
+        shared_models::migrations::run(&conn).context("Failed to run schema migrations")?;
         Ok(Self { conn: Arc::new(Mutex::new(conn)) })
     }
 
-    fn init_db(conn: &Connection) -> Result<()> {
+    /// Persist a completed candle, overwriting any existing row for the same
+    /// bucket (the backfill path and live aggregator can race on the same bucket).
+    pub fn upsert_candle(&self, candle: &Candle) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Failed to acquire database lock: {}", e))?;
         conn.execute(
-            "CREATE TABLE IF NOT EXISTS trades (
-                id INTEGER PRIMARY KEY,
-                strategy_id TEXT NOT NULL,
-                token_address TEXT NOT NULL,
-                symbol TEXT NOT NULL,
-                amount_usd REAL NOT NULL,
-                status TEXT NOT NULL,
-                signature TEXT,
-                entry_time INTEGER NOT NULL,
-                entry_price_usd REAL NOT NULL,
-                close_time INTEGER,
-                close_price_usd REAL,
-                pnl_usd REAL,
-                confidence REAL NOT NULL,
-                side TEXT NOT NULL,
-                highest_price_usd REAL
-            )",
-            [],
-        )?;
+            "INSERT INTO candles (token_address, interval, bucket_start_ts, open, high, low, close, volume_usd)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(token_address, interval, bucket_start_ts)
+             DO UPDATE SET open = ?4, high = ?5, low = ?6, close = ?7, volume_usd = ?8",
+            params![
+                candle.token_address,
+                candle.interval,
+                candle.bucket_start_ts,
+                candle.open,
+                candle.high,
+                candle.low,
+                candle.close,
+                candle.volume_usd,
+            ],
+        ).context("Failed to upsert candle")?;
         Ok(())
     }
 
-    pub fn log_trade_attempt(&self, details: &OrderDetails, strategy_id: &str, entry_price_usd: f64) -> Result<i64> {
+    pub fn get_recent_candles(&self, token_address: &str, interval: &str, limit: i64) -> Result<Vec<Candle>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Failed to acquire database lock: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT token_address, interval, bucket_start_ts, open, high, low, close, volume_usd
+             FROM candles WHERE token_address = ?1 AND interval = ?2
+             ORDER BY bucket_start_ts DESC LIMIT ?3",
+        )?;
+        let candles = stmt.query_map(params![token_address, interval, limit], |row| {
+            Ok(Candle {
+                token_address: row.get(0)?,
+                interval: row.get(1)?,
+                bucket_start_ts: row.get(2)?,
+                open: row.get(3)?,
+                high: row.get(4)?,
+                low: row.get(5)?,
+                close: row.get(6)?,
+                volume_usd: row.get(7)?,
+            })
+        })?;
+        candles
+            .collect::<Result<Vec<Candle>, rusqlite::Error>>()
+            .map_err(anyhow::Error::from)
+    }
+
+    pub fn log_trade_attempt(
+        &self,
+        details: &OrderDetails,
+        strategy_id: &str,
+        entry_price_usd: f64,
+        quote_out_amount: Option<TokenAmount>,
+    ) -> Result<i64> {
         let now: DateTime<Utc> = Utc::now();
+        let trail_percent_override = match details.order_type {
+            shared_models::OrderType::TrailingStop { trail_percent } => Some(trail_percent),
+            _ => None,
+        };
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Failed to acquire database lock: {}", e))?;
         conn.execute(
-            "INSERT INTO trades (strategy_id, token_address, symbol, amount_usd, status, entry_time, entry_price_usd, confidence, side, highest_price_usd)
-             VALUES (?1, ?2, ?3, ?4, 'PENDING', ?5, ?6, ?7, ?8, ?9)",
+            "INSERT INTO trades (strategy_id, token_address, symbol, amount_usd, status, entry_time, entry_price_usd, confidence, side, highest_price_usd, quote_out_amount_raw, quote_out_amount_decimals, trail_percent_override)
+             VALUES (?1, ?2, ?3, ?4, 'PENDING', ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
             params![
                 strategy_id,
                 details.token_address,
                 details.token_address,
-                details.suggested_size_usd,
+                details.suggested_size_usd.to_f64(),
                 now.timestamp(),
                 entry_price_usd,
                 details.confidence,
                 details.side.to_string(),
                 entry_price_usd,
+                quote_out_amount.map(|q| q.raw.to_string()),
+                quote_out_amount.map(|q| q.decimals),
+                trail_percent_override,
             ],
         ).context("Failed to insert trade attempt into database")?;
         Ok(conn.last_insert_rowid())
@@ -95,6 +137,28 @@ impl Database {
         Ok(())
     }
 
+    /// Marks a live trade whose signature landed on-chain successfully. Kept
+    /// separate from `open_trade` since that one fires optimistically right
+    /// after the send, before the confirmation tracker knows the outcome.
+    pub fn confirm_trade(&self, trade_id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Failed to acquire database lock: {}", e))?;
+        conn.execute("UPDATE trades SET status = 'FILLED' WHERE id = ?1", params![trade_id])
+            .context("Failed to mark trade confirmed")?;
+        Ok(())
+    }
+
+    /// Marks a live trade whose signature reverted on-chain or never landed
+    /// before its blockhash expired, so downstream PnL/allocation logic stops
+    /// treating it as an open position.
+    pub fn fail_trade(&self, trade_id: i64, reason: &str) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Failed to acquire database lock: {}", e))?;
+        conn.execute("UPDATE trades SET status = ?1 WHERE id = ?2", params![reason, trade_id])
+            .context("Failed to mark trade failed")?;
+        Ok(())
+    }
+
     pub fn update_trade_pnl(&self, trade_id: i64, status: &str, close_price_usd: f64, pnl_usd: f64) -> Result<()> {
         let now: DateTime<Utc> = Utc::now();
         let conn = self.conn.lock()
@@ -109,9 +173,19 @@ impl Database {
     pub fn get_all_trades(&self) -> Result<Vec<TradeRecord>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Failed to acquire database lock: {}", e))?;
-        let mut stmt = conn.prepare("SELECT * FROM trades ORDER BY entry_time DESC")
-            .context("Failed to prepare trade query")?;
+        let mut stmt = conn.prepare(
+            "SELECT id, strategy_id, token_address, symbol, amount_usd, status, signature,
+                    entry_time, entry_price_usd, close_time, close_price_usd, pnl_usd,
+                    confidence, side, highest_price_usd, quote_out_amount_raw, quote_out_amount_decimals
+             FROM trades ORDER BY entry_time DESC",
+        ).context("Failed to prepare trade query")?;
         let trades_iter = stmt.query_map([], |row| {
+            let quote_out_amount_raw: Option<String> = row.get(15)?;
+            let quote_out_amount_decimals: Option<u8> = row.get(16)?;
+            let quote_out_amount = quote_out_amount_raw.zip(quote_out_amount_decimals).map(|(raw, decimals)| {
+                TokenAmount::new(raw.parse().unwrap_or_default(), decimals)
+            });
+
             Ok(TradeRecord {
                 id: row.get(0)?,
                 strategy_id: row.get(1)?,
@@ -128,6 +202,7 @@ impl Database {
                 confidence: row.get(12)?,
                 side: row.get(13)?,
                 highest_price_usd: row.get(14)?,
+                quote_out_amount,
             })
         }).context("Failed to execute trade query")?;
 
@@ -159,23 +234,93 @@ impl Database {
         Ok(total)
     }
 
-    /// Get maximum NAV (for drawdown calculation)
+    /// Record a point-in-time NAV sample. Called periodically (currently by
+    /// the risk manager's health check loop) so `get_max_nav`/`get_max_drawdown`
+    /// read the true running NAV series instead of inferring it from closed trades.
+    pub fn record_nav_snapshot(&self, nav_usd: f64, realized_pnl: f64, unrealized_pnl: f64) -> Result<()> {
+        let now: DateTime<Utc> = Utc::now();
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Failed to acquire database lock: {}", e))?;
+        conn.execute(
+            "INSERT INTO nav_snapshots (timestamp, nav_usd, realized_pnl, unrealized_pnl) VALUES (?1, ?2, ?3, ?4)",
+            params![now.timestamp(), nav_usd, realized_pnl, unrealized_pnl],
+        ).context("Failed to record NAV snapshot")?;
+        Ok(())
+    }
+
+    /// Get maximum NAV ever observed, from the recorded NAV snapshot series.
     pub fn get_max_nav(&self, initial_capital_usd: f64) -> Result<f64> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Failed to acquire database lock: {}", e))?;
-        // For now, calculate max NAV as initial capital + max cumulative PnL
-        // In production, this should track actual NAV over time
-        let max_pnl: f64 = conn.query_row(
-            "SELECT COALESCE(MAX(running_pnl), 0.0) FROM (
-                SELECT SUM(pnl_usd) OVER (ORDER BY close_time) as running_pnl 
-                FROM trades 
-                WHERE status LIKE 'CLOSED_%' AND pnl_usd IS NOT NULL AND close_time IS NOT NULL
-                ORDER BY close_time
-            )",
-            [],
+        let max_nav: f64 = conn.query_row(
+            "SELECT COALESCE(MAX(nav_usd), ?1) FROM nav_snapshots",
+            params![initial_capital_usd],
             |row| row.get(0),
         ).context("Failed to calculate maximum NAV")?;
-        
-        Ok(initial_capital_usd + max_pnl.max(0.0))
+
+        Ok(max_nav)
+    }
+
+    /// Get the largest peak-to-trough NAV drawdown ever observed, as a
+    /// percentage, from the recorded NAV snapshot series.
+    pub fn get_max_drawdown(&self) -> Result<f64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Failed to acquire database lock: {}", e))?;
+        let max_drawdown_pct: f64 = conn.query_row(
+            "SELECT COALESCE(MAX((running_max - nav_usd) / running_max) * 100.0, 0.0) FROM (
+                SELECT nav_usd, MAX(nav_usd) OVER (ORDER BY timestamp) as running_max
+                FROM nav_snapshots
+            ) WHERE running_max > 0",
+            [],
+            |row| row.get(0),
+        ).context("Failed to calculate maximum drawdown")?;
+
+        Ok(max_drawdown_pct)
+    }
+
+    /// Roll the NAV snapshot series into OHLC buckets for `interval`
+    /// ("1m"/"5m"/"1h") between `from` and `to` (inclusive, unix seconds), so
+    /// a dashboard can chart the equity curve the same way it charts price candles.
+    pub fn get_nav_candles(&self, interval: &str, from: i64, to: i64) -> Result<Vec<NavCandle>> {
+        let interval_secs = match interval {
+            "1m" => 60,
+            "5m" => 300,
+            "1h" => 3600,
+            other => return Err(anyhow::anyhow!("Unsupported NAV candle interval: {}", other)),
+        };
+
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Failed to acquire database lock: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT timestamp, nav_usd FROM nav_snapshots
+             WHERE timestamp >= ?1 AND timestamp <= ?2
+             ORDER BY timestamp ASC",
+        )?;
+        let snapshots = stmt
+            .query_map(params![from, to], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, f64>(1)?)))?
+            .collect::<Result<Vec<(i64, f64)>, rusqlite::Error>>()
+            .map_err(anyhow::Error::from)?;
+
+        let mut candles: Vec<NavCandle> = Vec::new();
+        for (timestamp, nav_usd) in snapshots {
+            let bucket_start_ts = (timestamp / interval_secs) * interval_secs;
+            match candles.last_mut() {
+                Some(candle) if candle.bucket_start_ts == bucket_start_ts => {
+                    candle.high = candle.high.max(nav_usd);
+                    candle.low = candle.low.min(nav_usd);
+                    candle.close = nav_usd;
+                }
+                _ => candles.push(NavCandle {
+                    interval: interval.to_string(),
+                    bucket_start_ts,
+                    open: nav_usd,
+                    high: nav_usd,
+                    low: nav_usd,
+                    close: nav_usd,
+                }),
+            }
+        }
+
+        Ok(candles)
     }
 }
@@ -0,0 +1,109 @@
+//! Per-stage latency tracking for the trade execution pipeline (quote, risk
+//! check, signing round-trip, Jito send), tagged by `TradeMode` so paper and
+//! live latencies aren't blended together. Complements `ExecutorMetrics`'s
+//! Prometheus gauges, which track whole-pipeline event-handling latency and
+//! Jupiter quote latency without a `TradeMode` split: this publishes per-stage percentiles to the
+//! `metrics:execution_latency` Redis stream, since per-`TradeMode` breakdown
+//! would mean a gauge per mode per stage rather than one scrapeable series.
+use redis::AsyncCommands;
+use serde::Serialize;
+use shared_models::{LatencyHistogram, TradeMode};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::error;
+
+const EXECUTION_LATENCY_STREAM: &str = "metrics:execution_latency";
+const PUBLISH_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExecutionStage {
+    Quote,
+    RiskCheck,
+    Signing,
+    JitoSend,
+}
+
+impl ExecutionStage {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionStage::Quote => "quote",
+            ExecutionStage::RiskCheck => "risk_check",
+            ExecutionStage::Signing => "signing",
+            ExecutionStage::JitoSend => "jito_send",
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct StagePercentiles {
+    stage: &'static str,
+    mode: TradeMode,
+    p50_ms: f64,
+    p90_ms: f64,
+    p99_ms: f64,
+    max_ms: f64,
+}
+
+/// One HDR histogram per `(stage, mode)` pair encountered so far, guarded
+/// behind `MasterExecutor` and shared with every execution worker.
+pub struct ExecutionLatencyMetrics {
+    redis_client: redis::Client,
+    histograms: Mutex<HashMap<(ExecutionStage, TradeMode), Arc<LatencyHistogram>>>,
+}
+
+impl ExecutionLatencyMetrics {
+    pub fn new(redis_client: redis::Client) -> Self {
+        Self { redis_client, histograms: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn record(&self, stage: ExecutionStage, mode: TradeMode, millis: u64) {
+        let histogram = self
+            .histograms
+            .lock()
+            .unwrap()
+            .entry((stage, mode))
+            .or_insert_with(|| Arc::new(LatencyHistogram::new()))
+            .clone();
+        histogram.record_ms(millis);
+    }
+
+    /// Every `PUBLISH_INTERVAL`, drains the tracked histograms and XADDs one
+    /// entry per `(stage, mode)` pair to `metrics:execution_latency`, then
+    /// starts each fresh so percentiles reflect only the most recent window
+    /// rather than the process's entire lifetime.
+    pub fn spawn_publisher(self: &Arc<Self>) {
+        let metrics = self.clone();
+        tokio::spawn(async move {
+            let mut conn = match metrics.redis_client.get_multiplexed_async_connection().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    error!("Execution latency publisher failed to connect to Redis: {}", e);
+                    return;
+                }
+            };
+
+            let mut interval = tokio::time::interval(PUBLISH_INTERVAL);
+            loop {
+                interval.tick().await;
+
+                let snapshot: Vec<((ExecutionStage, TradeMode), Arc<LatencyHistogram>)> = {
+                    let mut histograms = metrics.histograms.lock().unwrap();
+                    std::mem::take(&mut *histograms).into_iter().collect()
+                };
+
+                for ((stage, mode), histogram) in snapshot {
+                    let (p50_ms, p90_ms, p99_ms) = histogram.percentiles();
+                    let sample = StagePercentiles { stage: stage.as_str(), mode, p50_ms, p90_ms, p99_ms, max_ms: histogram.max_ms() };
+
+                    let Ok(payload) = serde_json::to_string(&sample) else { continue };
+                    let result: redis::RedisResult<()> =
+                        conn.xadd(EXECUTION_LATENCY_STREAM, "*", &[("data", payload)]).await;
+                    if let Err(e) = result {
+                        error!("Failed to publish execution latency sample: {}", e);
+                    }
+                }
+            }
+        });
+    }
+}
@@ -1,42 +1,220 @@
 use crate::{
     config::CONFIG,
+    confirmation_tracker::ConfirmationTracker,
+    connectivity::ConnectivityMonitor,
+    control_api::{ControlCommand, StrategyInfo},
     database::Database,
+    execution_latency::{ExecutionLatencyMetrics, ExecutionStage},
     jito_client::JitoClient,
     jupiter::JupiterClient,
+    price_oracle::{ExternalReferenceRate, JupiterRate, RateAggregator, SolOracleRate, SOL_MINT},
+    priority_fee::{FeeStrategy, PriorityFeeEstimator},
     risk_manager::RiskManager,
-    signer_client,
-    strategies::{self, Strategy, EventType, MarketEvent, StrategyAction, OrderDetails},
+    signer_client::SignerClient,
+    sol_price_oracle::SolPriceOracle,
+    sol_price_ws_feed::SolPriceWsFeed,
+    strategies::{self, Strategy, EventType, MarketEvent, StrategyAction, OrderDetails, OrderType},
+    strategy_config::StrategyConfigStore,
+    ws_server::FanoutMessage,
 };
-use anyhow::{anyhow, Context, Result};
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose, Engine as _};
 use redis::AsyncCommands;
-use shared_models::{Side, StrategyAllocation, TradeMode};
-use solana_sdk::pubkey::Pubkey;
+use serde_json::Value;
+use shared_models::{CircuitBreaker, FillEvent, FillStatus, Price, Side, StrategyAllocation, TokenAmount, TradeMode, Usd};
+use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
 use std::{collections::HashMap, str::FromStr, sync::Arc};
+use std::time::Instant;
 use tokio::sync::{
+    broadcast,
     mpsc::{self, Receiver, Sender},
-    Mutex,
+    watch, Mutex, RwLock,
 };
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, instrument, warn};
 
+/// Settlement records land here, separate from `events:price`, since fills
+/// are execution output rather than a market event any strategy subscribes
+/// to as input.
+const FILLS_STREAM: &str = "events:fills";
+
+/// Capacity of the candidate queue sitting between strategy signal loops and
+/// the execution worker pool. Bounded so a worker stall applies backpressure
+/// (a strategy's `try_send` starts failing) instead of growing unboundedly.
+const CANDIDATE_QUEUE_CAPACITY: usize = 256;
+
+/// Capacity of the control-plane command queue. Control requests are rare
+/// and operator-driven, so this only needs enough slack to absorb a burst of
+/// clicks, not anything approaching `CANDIDATE_QUEUE_CAPACITY`.
+const CONTROL_QUEUE_CAPACITY: usize = 32;
+
+/// An `OrderDetails` a strategy wants executed, queued for one of the
+/// execution workers to quote/risk-check/send. Kept separate from
+/// `OrderDetails` itself since it also carries the routing info (which
+/// strategy, which `TradeMode`) the strategy's own event loop knows but the
+/// execution worker doesn't.
+struct TradeCandidate {
+    details: OrderDetails,
+    strategy_id: String,
+    mode: TradeMode,
+}
+
+/// One strategy currently running under `MasterExecutor`. `shutdown_tx` lets
+/// `StopStrategy`/`shutdown()` ask the running `strategy_supervisor` to stop
+/// cooperatively instead of aborting its task mid-`on_event`; `supervisor`
+/// is the task that owns the rebuild-on-panic loop, not the event loop
+/// itself, so awaiting it on drain covers any in-progress restart too.
+struct ActiveStrategy {
+    tx: Sender<MarketEvent>,
+    allocation: Arc<Mutex<StrategyAllocation>>,
+    shutdown_tx: watch::Sender<bool>,
+    /// Bumped by `ReloadStrategyConfig` after this strategy's `allocation`
+    /// has been updated from `strategies.json`, telling the supervisor to
+    /// rebuild and re-`init` with the new params.
+    reload_tx: watch::Sender<u64>,
+    supervisor: JoinHandle<()>,
+    started_at: Instant,
+}
+
 pub struct MasterExecutor {
     db: Arc<Database>,
-    active_strategies: HashMap<String, (Sender<MarketEvent>, JoinHandle<()>, Arc<Mutex<StrategyAllocation>>)>,
+    active_strategies: HashMap<String, ActiveStrategy>,
     event_router_senders: HashMap<EventType, Vec<Sender<MarketEvent>>>,
     redis_client: redis::Client,
     jupiter_client: Arc<JupiterClient>,
     jito_client: Arc<JitoClient>,
-    sol_usd_price: Arc<Mutex<f64>>,
+    signer_client: Arc<SignerClient>,
+    execution_latency: Arc<ExecutionLatencyMetrics>,
+    confirmation_tracker: Arc<ConfirmationTracker>,
+    /// Shared with `ConfirmationTracker`, which trips it on repeated
+    /// terminal send failures regardless of what realized drawdown looks like.
+    circuit_breaker: Arc<CircuitBreaker>,
+    priority_fee: Arc<PriorityFeeEstimator>,
+    sol_price_oracle: Arc<SolPriceOracle>,
+    rate_aggregator: Arc<RateAggregator>,
     portfolio_paused: Arc<Mutex<bool>>,
+    /// Strategy signal loops only ever produce candidates onto this; they
+    /// never await a quote themselves.
+    candidate_tx: Sender<TradeCandidate>,
+    /// The execution worker pool draining `candidate_tx`, joined (with a
+    /// bounded timeout) on shutdown alongside the strategy tasks.
+    execution_workers: Vec<JoinHandle<()>>,
+    /// Clonable handle handed out via `control_handle()` to whoever spawns
+    /// the control-plane HTTP server; kept alongside `control_rx` so the
+    /// channel never closes just because no server has been spawned yet.
+    control_tx: Sender<ControlCommand>,
+    control_rx: Receiver<ControlCommand>,
+    connectivity: Arc<ConnectivityMonitor>,
+    strategy_config: Arc<StrategyConfigStore>,
+    /// Fans every dispatched `MarketEvent` out to `WsServer`'s subscribers,
+    /// alongside the strategy routing `dispatch_event` already does, so a
+    /// dashboard sees the same price/social stream the executor consumes.
+    ws_tx: broadcast::Sender<FanoutMessage>,
+    /// Shared with `WsServer`'s checkpoint handler, kept current here so a
+    /// newly-subscribing peer's checkpoint reflects the latest tick instead
+    /// of the zero value it was constructed with.
+    ws_sol_usd_price: Arc<RwLock<f64>>,
+    ws_last_prices: Arc<RwLock<HashMap<String, f64>>>,
 }
 
 impl MasterExecutor {
-    pub async fn new(db: Arc<Database>) -> Result<Self> {
+    pub async fn new(
+        db: Arc<Database>,
+        ws_tx: broadcast::Sender<FanoutMessage>,
+        ws_sol_usd_price: Arc<RwLock<f64>>,
+        ws_last_prices: Arc<RwLock<HashMap<String, f64>>>,
+    ) -> Result<Self> {
         let redis_client = redis::Client::open(CONFIG.redis_url.clone())
             .context("Failed to create Redis client")?;
-        let jupiter_client = Arc::new(JupiterClient::new());
+        let jupiter_client = Arc::new(if CONFIG.mock_jupiter {
+            info!("🧪 MOCK_JUPITER enabled, serving deterministic quotes/prices instead of quote-api.jup.ag");
+            JupiterClient::new_mock(CONFIG.mock_jupiter_fixture_path.as_deref())
+        } else {
+            JupiterClient::new().context("Failed to create Jupiter client")?
+        });
         let jito_client = Arc::new(JitoClient::new(&CONFIG.jito_rpc_url).await
             .context("Failed to create Jito client")?);
+        let signer_client = Arc::new(SignerClient::new(CONFIG.signer_url.clone()));
+        signer_client.spawn_health_check();
+        let execution_latency = Arc::new(ExecutionLatencyMetrics::new(redis_client.clone()));
+        execution_latency.spawn_publisher();
+        let circuit_breaker = Arc::new(CircuitBreaker::new());
+        let confirmation_tracker = Arc::new(ConfirmationTracker::new(
+            db.clone(),
+            jito_client.clone(),
+            signer_client.clone(),
+            circuit_breaker.clone(),
+            redis_client.clone(),
+        ));
+        let priority_fee = Arc::new(PriorityFeeEstimator::new(
+            &CONFIG.solana_rpc_url,
+            CONFIG.priority_fee_floor_micro_lamports,
+            CONFIG.priority_fee_max_usd,
+        ));
+        let sol_price_oracle = Arc::new(SolPriceOracle::new(
+            CONFIG.jupiter_api_url.clone(),
+            CONFIG.pyth_hermes_url.clone(),
+            std::time::Duration::from_secs(CONFIG.sol_price_refresh_interval_secs),
+            std::time::Duration::from_secs(CONFIG.sol_price_staleness_ttl_secs),
+        ));
+        sol_price_oracle.spawn_refresh();
+
+        let sol_price_ws_feed = Arc::new(SolPriceWsFeed::new(
+            CONFIG.sol_price_ws_url.clone(),
+            CONFIG.sol_price_ws_subscribe_frame.clone(),
+        ));
+        sol_price_ws_feed.spawn();
+
+        // Priority order: the live WS tick feed first (freshest when
+        // connected), then the dedicated oracle (it already falls back
+        // Jupiter -> Pyth internally), then Jupiter's own `/price` endpoint as
+        // an independent cross-check, then an external reference price (e.g.
+        // a CEX-ticker bridge) if one has been populated. A trade is only
+        // rejected once every source here is down or stale, instead of only
+        // when the cached value happens to be non-positive.
+        let rate_aggregator = Arc::new(RateAggregator::new(
+            vec![
+                sol_price_ws_feed.clone(),
+                Arc::new(SolOracleRate(sol_price_oracle.clone())),
+                Arc::new(JupiterRate(jupiter_client.clone())),
+                Arc::new(ExternalReferenceRate(redis_client.clone())),
+            ],
+            std::time::Duration::from_secs(CONFIG.price_max_staleness_secs),
+        ));
+
+        // Candidate discovery (strategy `on_event`) and execution (quote,
+        // risk check, send) run as separate concurrent pipelines: strategies
+        // only ever enqueue here, and this pool of workers is what actually
+        // awaits Jupiter/Jito, so a slow quote stalls one worker rather than
+        // every strategy's signal loop.
+        let (candidate_tx, candidate_rx) = mpsc::channel::<TradeCandidate>(CANDIDATE_QUEUE_CAPACITY);
+        let candidate_rx = Arc::new(Mutex::new(candidate_rx));
+        let mut execution_workers = Vec::with_capacity(CONFIG.executor_worker_pool_size);
+        for worker_id in 0..CONFIG.executor_worker_pool_size {
+            execution_workers.push(tokio::spawn(execution_worker(
+                worker_id,
+                candidate_rx.clone(),
+                db.clone(),
+                jupiter_client.clone(),
+                jito_client.clone(),
+                signer_client.clone(),
+                execution_latency.clone(),
+                confirmation_tracker.clone(),
+                priority_fee.clone(),
+                sol_price_oracle.clone(),
+                rate_aggregator.clone(),
+            )));
+        }
+
+        let (control_tx, control_rx) = mpsc::channel::<ControlCommand>(CONTROL_QUEUE_CAPACITY);
+
+        let connectivity = Arc::new(ConnectivityMonitor::new(redis_client.clone(), sol_price_ws_feed.clone()));
+        connectivity.spawn();
+
+        let strategy_config = StrategyConfigStore::new(CONFIG.strategy_config_path.clone())
+            .await
+            .context("Failed to load strategy_config_path")?;
+        strategy_config.spawn_watch(control_tx.clone());
 
         Ok(Self {
             db,
@@ -45,8 +223,23 @@ impl MasterExecutor {
             redis_client,
             jupiter_client,
             jito_client,
-            sol_usd_price: Arc::new(Mutex::new(0.0)),
+            signer_client,
+            execution_latency,
+            confirmation_tracker,
+            circuit_breaker,
+            priority_fee,
+            sol_price_oracle,
+            rate_aggregator,
             portfolio_paused: Arc::new(Mutex::new(false)),
+            candidate_tx,
+            execution_workers,
+            control_tx,
+            control_rx,
+            connectivity,
+            strategy_config,
+            ws_tx,
+            ws_sol_usd_price,
+            ws_last_prices,
         })
     }
 
@@ -54,10 +247,30 @@ impl MasterExecutor {
         self.portfolio_paused.clone()
     }
 
+    /// Shared with `ConfirmationTracker`, for anything outside the execution
+    /// path (metrics, control API) that needs to read live trading risk state.
+    pub fn circuit_breaker(&self) -> Arc<CircuitBreaker> {
+        self.circuit_breaker.clone()
+    }
+
+    /// Clonable sender for the control-plane HTTP server to forward
+    /// `ControlCommand`s onto, so every mutation still runs on this
+    /// executor's own task instead of racing its event loop.
+    pub fn control_handle(&self) -> Sender<ControlCommand> {
+        self.control_tx.clone()
+    }
+
     pub async fn run(&mut self) -> Result<()> {
         info!("🚀 MasterExecutor started, monitoring Redis streams and allocations...");
+        self.bootstrap_strategy_config().await;
         let mut conn = self.redis_client.get_async_connection().await?;
 
+        let ctrl_c = tokio::signal::ctrl_c();
+        tokio::pin!(ctrl_c);
+        #[cfg(unix)]
+        let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .context("Failed to install SIGTERM handler")?;
+
         let mut stream_ids: HashMap<String, String> = [
             ("allocations_channel", "0"),
             ("events:price", "0"),
@@ -90,7 +303,10 @@ impl MasterExecutor {
                                 }
                             }
                         }
-                        Err(e) => error!(error = %e, "Failed to read allocations stream")
+                        Err(e) => {
+                            error!(error = %e, "Failed to read allocations stream, reconnecting to Redis");
+                            conn = self.reconnect_redis().await;
+                        }
                     }
                 }
                 
@@ -113,11 +329,168 @@ impl MasterExecutor {
                                 }
                             }
                         }
-                        Err(e) => error!(error = %e, "Failed to read events stream")
+                        Err(e) => {
+                            error!(error = %e, "Failed to read events stream, reconnecting to Redis");
+                            conn = self.reconnect_redis().await;
+                        }
+                    }
+                }
+
+                // Handle social-mention events, same shape as the price stream above.
+                events_result = conn.xread(&["events:social"], &[stream_ids.get("events:social").unwrap()]) => {
+                    match events_result {
+                        Ok(streams) => {
+                            for stream in streams {
+                                for (id, data) in stream.ids {
+                                    stream_ids.insert("events:social".to_string(), id.clone());
+
+                                    if let Some(event_data) = data.get("data") {
+                                        match serde_json::from_str::<MarketEvent>(event_data) {
+                                            Ok(event) => {
+                                                self.dispatch_event(&event).await;
+                                            }
+                                            Err(e) => error!(error = %e, "Failed to parse social event")
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            error!(error = %e, "Failed to read social events stream, reconnecting to Redis");
+                            conn = self.reconnect_redis().await;
+                        }
+                    }
+                }
+
+                // SOL/USD ticks, kept current here purely for the WS fan-out
+                // checkpoint — the live trading path gets its SOL price from
+                // `rate_aggregator`/`sol_price_oracle`, not this stream.
+                events_result = conn.xread(&["events:sol_price"], &[stream_ids.get("events:sol_price").unwrap()]) => {
+                    match events_result {
+                        Ok(streams) => {
+                            for stream in streams {
+                                for (id, data) in stream.ids {
+                                    stream_ids.insert("events:sol_price".to_string(), id.clone());
+
+                                    if let Some(event_data) = data.get("data") {
+                                        match serde_json::from_str::<MarketEvent>(event_data) {
+                                            Ok(event) => {
+                                                self.dispatch_event(&event).await;
+                                            }
+                                            Err(e) => error!(error = %e, "Failed to parse SOL price event")
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            error!(error = %e, "Failed to read SOL price events stream, reconnecting to Redis");
+                            conn = self.reconnect_redis().await;
+                        }
                     }
                 }
+
+                // Handle control-plane commands from the HTTP API
+                Some(cmd) = self.control_rx.recv() => {
+                    self.handle_control_command(cmd).await;
+                }
+
+                _ = &mut ctrl_c => {
+                    info!("Received SIGINT, shutting down gracefully...");
+                    break;
+                }
+                #[cfg(unix)]
+                _ = sigterm.recv() => {
+                    info!("Received SIGTERM, shutting down gracefully...");
+                    break;
+                }
+            }
+        }
+
+        self.shutdown().await;
+        Ok(())
+    }
+
+    /// Re-establishes `run()`'s Redis connection after an xread error,
+    /// retrying with backoff until one succeeds rather than giving up and
+    /// leaving the event pump stuck on a dead socket. Stream IDs are tracked
+    /// separately in `run()`'s `stream_ids` map, so resuming on the new
+    /// connection picks up from the same offsets instead of re-reading history.
+    async fn reconnect_redis(&self) -> redis::aio::Connection {
+        let mut backoff = std::time::Duration::from_secs(1);
+        loop {
+            match self.redis_client.get_async_connection().await {
+                Ok(conn) => {
+                    info!("Reconnected to Redis");
+                    return conn;
+                }
+                Err(e) => {
+                    warn!(error = %e, "Failed to reconnect to Redis, retrying in {:?}", backoff);
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(std::time::Duration::from_secs(30));
+                }
+            }
+        }
+    }
+
+    /// Stop routing new events to strategies and wait (with a bounded
+    /// timeout) for any `execute_trade` call already in flight inside an
+    /// execution worker to finish, so a redeploy can't abandon a live
+    /// trade mid-submission.
+    async fn shutdown(&mut self) {
+        const SHUTDOWN_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(15);
+
+        info!("🛑 Draining in-flight trades before exit...");
+        // Dropping the senders closes each strategy's channel too, but the
+        // cooperative shutdown signal is what actually interrupts a
+        // `strategy_event_loop` blocked on `rx.recv().await` (or lets it
+        // finish handling its current event first) instead of waiting for
+        // the channel close to be noticed.
+        self.event_router_senders.clear();
+
+        let strategies: Vec<_> = self.active_strategies.drain().map(|(_, s)| s).collect();
+        for strategy in &strategies {
+            let _ = strategy.shutdown_tx.send(true);
+        }
+
+        let drain = async {
+            for strategy in strategies {
+                if let Err(e) = strategy.supervisor.await {
+                    warn!(error = %e, "Strategy supervisor panicked while draining");
+                }
+            }
+        };
+
+        if tokio::time::timeout(SHUTDOWN_GRACE_PERIOD, drain).await.is_err() {
+            warn!(
+                "Strategy tasks did not finish within {:?} of shutdown; exiting anyway",
+                SHUTDOWN_GRACE_PERIOD
+            );
+        }
+
+        // Every strategy supervisor that fed the candidate queue has now exited,
+        // so each execution worker's current `execute_trade` (if any) is the
+        // last one it'll ever run; give them the same grace period to finish
+        // it before giving up. Our own sender is swapped out first so the
+        // queue actually closes and each worker's `recv()` returns `None`.
+        let (closed_tx, _) = mpsc::channel(1);
+        drop(std::mem::replace(&mut self.candidate_tx, closed_tx));
+        let worker_handles = std::mem::take(&mut self.execution_workers);
+        let drain_workers = async {
+            for handle in worker_handles {
+                if let Err(e) = handle.await {
+                    warn!(error = %e, "Execution worker panicked while draining");
+                }
             }
+        };
+        if tokio::time::timeout(SHUTDOWN_GRACE_PERIOD, drain_workers).await.is_err() {
+            warn!(
+                "Execution workers did not finish within {:?} of shutdown; exiting anyway",
+                SHUTDOWN_GRACE_PERIOD
+            );
         }
+
+        info!("👋 MasterExecutor shut down cleanly");
     }
 
     async fn reconcile_strategies(&mut self, allocations: Vec<StrategyAllocation>) {
@@ -126,32 +499,46 @@ impl MasterExecutor {
             
             if !self.active_strategies.contains_key(&strategy_id) {
                 info!(strategy_id = %strategy_id, "Starting new strategy");
-                
-                let strategy_instance = match self.build_strategy(&allocation.strategy_family) {
-                    Ok(strategy) => strategy,
-                    Err(e) => {
-                        error!(error = %e, strategy_id = %strategy_id, "Failed to build strategy");
-                        continue;
-                    }
-                };
+
+                // Fail fast on an unknown family before committing to a
+                // supervisor, so a caller (e.g. `StartStrategy`) still gets a
+                // synchronous rejection instead of waiting on the
+                // supervisor's own internal build to fail.
+                if let Err(e) = self.build_strategy(&allocation.strategy_family) {
+                    error!(error = %e, strategy_id = %strategy_id, "Failed to build strategy");
+                    continue;
+                }
+                let family = allocation.strategy_family.clone();
 
                 let (tx, rx) = mpsc::channel(1000);
+                let rx = Arc::new(Mutex::new(rx));
                 let allocation_mutex = Arc::new(Mutex::new(allocation));
-                
-                let handle = tokio::spawn(strategy_task(
-                    strategy_instance,
+                let (shutdown_tx, shutdown_rx) = watch::channel(false);
+                let (reload_tx, reload_rx) = watch::channel(0u64);
+
+                let supervisor = tokio::spawn(strategy_supervisor(
+                    family,
                     rx,
-                    self.db.clone(),
-                    self.jupiter_client.clone(),
-                    self.jito_client.clone(),
-                    self.sol_usd_price.clone(),
+                    self.candidate_tx.clone(),
                     self.portfolio_paused.clone(),
                     allocation_mutex.clone(),
                     strategy_id.clone(),
+                    shutdown_rx,
+                    reload_rx,
                 ));
 
-                self.active_strategies.insert(strategy_id.clone(), (tx.clone(), handle, allocation_mutex));
-                
+                self.active_strategies.insert(
+                    strategy_id.clone(),
+                    ActiveStrategy {
+                        tx: tx.clone(),
+                        allocation: allocation_mutex,
+                        shutdown_tx,
+                        reload_tx,
+                        supervisor,
+                        started_at: Instant::now(),
+                    },
+                );
+
                 // Register for events
                 self.event_router_senders
                     .entry(EventType::Price)
@@ -161,6 +548,75 @@ impl MasterExecutor {
         }
     }
 
+    /// Starts every strategy listed in `strategies.json` at boot, under an
+    /// id equal to its family name (the file has no notion of running two
+    /// instances of the same family), so an operator's JSON-driven config
+    /// takes effect without needing a portfolio_manager allocation or a
+    /// manual `StartStrategy` call first.
+    async fn bootstrap_strategy_config(&mut self) {
+        for (family, mode, params) in self.strategy_config.all().await {
+            if self.active_strategies.contains_key(&family) {
+                continue;
+            }
+            info!(strategy_id = %family, "Starting strategy from strategies.json");
+            if let Err(e) = self.spawn_strategy(&family, &family, mode, params) {
+                error!(error = %e, strategy_id = %family, "Failed to bootstrap strategy from strategies.json");
+            }
+        }
+    }
+
+    /// Builds and registers one new active strategy under `id`, running
+    /// `family` with the given `mode`/`params`. Shared by
+    /// `bootstrap_strategy_config`; `reconcile_strategies`/`StartStrategy`
+    /// have their own inline version of this dance predating it.
+    fn spawn_strategy(&mut self, id: &str, family: &str, mode: TradeMode, params: Value) -> Result<()> {
+        self.build_strategy(family)?;
+
+        let (tx, rx) = mpsc::channel(1000);
+        let rx = Arc::new(Mutex::new(rx));
+        let allocation = Arc::new(Mutex::new(StrategyAllocation {
+            id: id.to_string(),
+            weight: 1.0,
+            sharpe_ratio: 0.0,
+            mode,
+            params,
+            init_health: 1.0,
+            maint_health: 1.0,
+        }));
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let (reload_tx, reload_rx) = watch::channel(0u64);
+
+        let supervisor = tokio::spawn(strategy_supervisor(
+            family.to_string(),
+            rx,
+            self.candidate_tx.clone(),
+            self.portfolio_paused.clone(),
+            allocation.clone(),
+            id.to_string(),
+            shutdown_rx,
+            reload_rx,
+        ));
+
+        self.active_strategies.insert(
+            id.to_string(),
+            ActiveStrategy {
+                tx: tx.clone(),
+                allocation,
+                shutdown_tx,
+                reload_tx,
+                supervisor,
+                started_at: Instant::now(),
+            },
+        );
+
+        self.event_router_senders
+            .entry(EventType::Price)
+            .or_insert_with(Vec::new)
+            .push(tx);
+
+        Ok(())
+    }
+
     async fn dispatch_event(&self, event: &MarketEvent) {
         let event_type = match event {
             MarketEvent::Price(_) => EventType::Price,
@@ -169,6 +625,9 @@ impl MasterExecutor {
             MarketEvent::Depth(_) => EventType::Depth,
             MarketEvent::Funding(_) => EventType::Funding,
             MarketEvent::SolPrice(_) => EventType::SolPrice,
+            MarketEvent::Candle(_) => EventType::Candle,
+            MarketEvent::Fill(_) => EventType::Fill,
+            MarketEvent::MarkPrice(_) => EventType::MarkPrice,
         };
 
         if let Some(senders) = self.event_router_senders.get(&event_type) {
@@ -178,11 +637,127 @@ impl MasterExecutor {
                 }
             }
         }
+
+        self.publish_ws_event(event).await;
+    }
+
+    /// Keeps `WsServer`'s checkpoint state current and mirrors price/social
+    /// events out to its subscribers. A no-op whenever there's nothing
+    /// listening on `ws_tx` (it always has at least `WsServer`'s own internal
+    /// receiver, so this only ever skips the broadcast on a `send` error).
+    async fn publish_ws_event(&self, event: &MarketEvent) {
+        match event {
+            MarketEvent::Price(tick) => {
+                self.ws_last_prices.write().await.insert(tick.token_address.clone(), tick.price_usd);
+                let _ = self.ws_tx.send(FanoutMessage::Market { channel: "price", event: event.clone() });
+            }
+            MarketEvent::SolPrice(sol_event) => {
+                *self.ws_sol_usd_price.write().await = sol_event.price_usd;
+            }
+            MarketEvent::Social(_) => {
+                let _ = self.ws_tx.send(FanoutMessage::Market { channel: "social", event: event.clone() });
+            }
+            _ => {}
+        }
     }
 
     fn build_strategy(&self, family: &str) -> Result<Box<dyn Strategy + Send>> {
         strategies::create_strategy(family)
     }
+
+    /// Services one control-plane request from `control_api`. Runs on this
+    /// same task as `reconcile_strategies`/`dispatch_event`, so a `StopStrategy`
+    /// or `StartStrategy` can never race an allocation update arriving over
+    /// the Redis stream.
+    async fn handle_control_command(&mut self, cmd: ControlCommand) {
+        match cmd {
+            ControlCommand::ListStrategies { reply } => {
+                let mut strategies = Vec::with_capacity(self.active_strategies.len());
+                for (id, strategy) in &self.active_strategies {
+                    strategies.push(StrategyInfo {
+                        id: id.clone(),
+                        mode: strategy.allocation.lock().await.mode,
+                        uptime_secs: strategy.started_at.elapsed().as_secs(),
+                    });
+                }
+                let _ = reply.send(strategies);
+            }
+
+            ControlCommand::StopStrategy { id, reply } => {
+                let stopped = if let Some(strategy) = self.active_strategies.remove(&id) {
+                    // Cooperative stop: the supervisor's event loop notices
+                    // `shutdown_rx.changed()` and returns without restarting,
+                    // rather than this task being aborted mid-`on_event`.
+                    let _ = strategy.shutdown_tx.send(true);
+                    for senders in self.event_router_senders.values_mut() {
+                        senders.retain(|s| !s.same_channel(&strategy.tx));
+                    }
+                    info!(strategy_id = %id, "Strategy cooperatively stopped via control API");
+                    true
+                } else {
+                    false
+                };
+                let _ = reply.send(stopped);
+            }
+
+            ControlCommand::StartStrategy { id, family, params, mode, reply } => {
+                if self.active_strategies.contains_key(&id) {
+                    let _ = reply.send(Err(format!("strategy '{}' is already active", id)));
+                    return;
+                }
+
+                let allocation = StrategyAllocation {
+                    id: id.clone(),
+                    weight: 1.0,
+                    sharpe_ratio: 0.0,
+                    mode,
+                    params,
+                    init_health: 1.0,
+                    maint_health: 1.0,
+                };
+                self.reconcile_strategies(vec![allocation]).await;
+
+                let result = if self.active_strategies.contains_key(&id) {
+                    info!(strategy_id = %id, family, "Strategy force-started via control API");
+                    Ok(())
+                } else {
+                    Err(format!("failed to build strategy family '{}'", family))
+                };
+                let _ = reply.send(result);
+            }
+
+            ControlCommand::InjectEvent { event, reply } => {
+                self.dispatch_event(&event).await;
+                let _ = reply.send(());
+            }
+
+            ControlCommand::GetSolPrice { reply } => {
+                let price = self.rate_aggregator.latest_rate(SOL_MINT).await.ok().map(|r| r.price_usd);
+                let _ = reply.send(price);
+            }
+
+            ControlCommand::GetConnectivity { reply } => {
+                let _ = reply.send(self.connectivity.status().await);
+            }
+
+            ControlCommand::ReloadStrategyConfig { reply } => {
+                let mut reloaded = 0usize;
+                for (id, strategy) in &self.active_strategies {
+                    if let Some((mode, params)) = self.strategy_config.get(id).await {
+                        let mut allocation = strategy.allocation.lock().await;
+                        allocation.mode = mode;
+                        allocation.params = params;
+                        drop(allocation);
+                        let next = *strategy.reload_tx.borrow() + 1;
+                        let _ = strategy.reload_tx.send(next);
+                        reloaded += 1;
+                    }
+                }
+                info!(reloaded, "Applied reloaded strategies.json to active strategies");
+                let _ = reply.send(reloaded);
+            }
+        }
+    }
 }
 
 // Placeholder strategy for testing
@@ -201,38 +776,79 @@ impl Strategy for PlaceholderStrategy {
     }
 }
 
+/// Why a `strategy_event_loop` run ended, so `strategy_supervisor` can tell
+/// an operator-requested stop (or the channel simply closing) apart from the
+/// task panicking, since only a panic should trigger a restart.
+enum StrategyExit {
+    Stopped,
+    ChannelClosed,
+    /// `strategies.json` changed and `handle_control_command` updated
+    /// this strategy's `allocation`; the supervisor should rebuild and
+    /// re-`init` it with the new params rather than treat this as a crash.
+    Reload,
+}
+
+/// Pure signal generation: turns market events into `TradeCandidate`s and
+/// enqueues them for the execution worker pool. Never awaits a quote itself,
+/// so a slow Jupiter response can't stall this strategy's event loop or let
+/// the market move past a signal still waiting to be picked up. `rx` is
+/// shared behind a mutex (mirroring `execution_worker`'s `candidate_rx`) so
+/// a restart by `strategy_supervisor` can hand it to a freshly built
+/// strategy instance without losing whatever was still queued.
 #[instrument(skip_all, fields(strategy_id))]
-async fn strategy_task(
+async fn strategy_event_loop(
     mut strategy_instance: Box<dyn Strategy>,
-    mut rx: Receiver<MarketEvent>,
-    db: Arc<Database>,
-    jupiter_client: Arc<JupiterClient>,
-    jito_client: Arc<JitoClient>,
-    sol_usd_price: Arc<Mutex<f64>>,
+    rx: Arc<Mutex<Receiver<MarketEvent>>>,
+    candidate_tx: Sender<TradeCandidate>,
     portfolio_paused: Arc<Mutex<bool>>,
     allocation: Arc<Mutex<StrategyAllocation>>,
     strategy_id: String,
-) {
-    info!("Strategy task started.");
-    while let Some(event) = rx.recv().await {
+    mut shutdown_rx: watch::Receiver<bool>,
+    mut reload_rx: watch::Receiver<u64>,
+) -> StrategyExit {
+    info!("Strategy event loop started.");
+    loop {
+        let event = {
+            let mut rx = rx.lock().await;
+            tokio::select! {
+                event = rx.recv() => event,
+                _ = shutdown_rx.changed() => {
+                    info!("Shutdown requested, stopping strategy event loop.");
+                    return StrategyExit::Stopped;
+                }
+                _ = reload_rx.changed() => {
+                    info!("strategies.json changed, reloading strategy with new params.");
+                    return StrategyExit::Reload;
+                }
+            }
+        };
+        let Some(event) = event else {
+            info!("Strategy event channel closed, exiting.");
+            return StrategyExit::ChannelClosed;
+        };
+
         if *portfolio_paused.lock().await {
             debug!("Portfolio paused. Skipping trade signal.");
             continue;
         }
 
         match strategy_instance.on_event(&event).await {
-            Ok(StrategyAction::Execute(details)) => {
-                let alloc = allocation.lock().await;
-                if let Err(e) = execute_trade(
-                    db.clone(),
-                    jupiter_client.clone(),
-                    jito_client.clone(),
-                    sol_usd_price.clone(),
+            Ok(StrategyAction::Execute(mut details)) => {
+                let (mode, maint_health) = {
+                    let allocation = allocation.lock().await;
+                    (allocation.mode, allocation.maint_health)
+                };
+                // Size against the conservative (maintenance) health figure
+                // rather than the raw signal, so a strategy whose drawdown
+                // has degraded its standing trades smaller instead of at
+                // full size right up until it's force-deallocated.
+                details.suggested_size_usd = Usd::from_f64(details.suggested_size_usd.to_f64() * maint_health);
+                if let Err(e) = candidate_tx.try_send(TradeCandidate {
                     details,
-                    &strategy_id,
-                    alloc.mode,
-                ).await {
-                    error!(error = %e, "Trade execution failed.");
+                    strategy_id: strategy_id.clone(),
+                    mode,
+                }) {
+                    warn!(error = %e, "Execution queue full or closed, dropping trade candidate");
                 }
             }
             Ok(StrategyAction::Hold) => {}
@@ -243,56 +859,351 @@ async fn strategy_task(
     }
 }
 
+/// Bounded restart attempts after consecutive panics, so a strategy that
+/// panics on every event doesn't spin the supervisor forever.
+const MAX_STRATEGY_RESTARTS: u32 = 5;
+const RESTART_INITIAL_BACKOFF: std::time::Duration = std::time::Duration::from_secs(1);
+const RESTART_MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Owns a strategy slot's full lifecycle. Rebuilds and re-`init`s the
+/// strategy from its stored `StrategyAllocation` and runs it via
+/// `strategy_event_loop`; if that task panics, it's recreated and resumed
+/// against the same shared `rx` with a bounded backoff instead of leaving
+/// the slot dead. Returns (without restarting) as soon as the loop exits
+/// cooperatively (`StrategyExit::Stopped`) or its channel closes, since
+/// neither is a crash the supervisor should recover from. A
+/// `StrategyExit::Reload` (strategies.json changed) rebuilds and re-`init`s
+/// the same way a panic-restart does, but resets the backoff/restart counter
+/// since it isn't a failure.
+#[instrument(skip_all, fields(strategy_id))]
+async fn strategy_supervisor(
+    family: String,
+    rx: Arc<Mutex<Receiver<MarketEvent>>>,
+    candidate_tx: Sender<TradeCandidate>,
+    portfolio_paused: Arc<Mutex<bool>>,
+    allocation: Arc<Mutex<StrategyAllocation>>,
+    strategy_id: String,
+    shutdown_rx: watch::Receiver<bool>,
+    reload_rx: watch::Receiver<u64>,
+) {
+    let mut restarts = 0u32;
+    let mut backoff = RESTART_INITIAL_BACKOFF;
+
+    loop {
+        let mut strategy_instance = match strategies::create_strategy(&family) {
+            Ok(strategy) => strategy,
+            Err(e) => {
+                error!(error = %e, "Failed to rebuild strategy, giving up.");
+                return;
+            }
+        };
+        let params = allocation.lock().await.params.clone();
+        if let Err(e) = strategy_instance.init(&params).await {
+            error!(error = %e, "Strategy init failed, giving up.");
+            return;
+        }
+
+        let handle = tokio::spawn(strategy_event_loop(
+            strategy_instance,
+            rx.clone(),
+            candidate_tx.clone(),
+            portfolio_paused.clone(),
+            allocation.clone(),
+            strategy_id.clone(),
+            shutdown_rx.clone(),
+            reload_rx.clone(),
+        ));
+
+        match handle.await {
+            Ok(StrategyExit::Stopped) | Ok(StrategyExit::ChannelClosed) => return,
+            Ok(StrategyExit::Reload) => {
+                info!("Rebuilding strategy with reloaded strategies.json params.");
+                restarts = 0;
+                backoff = RESTART_INITIAL_BACKOFF;
+            }
+            Err(join_err) => {
+                restarts += 1;
+                if restarts > MAX_STRATEGY_RESTARTS {
+                    error!(error = %join_err, restarts, "Strategy exceeded max restarts, giving up.");
+                    return;
+                }
+                error!(error = %join_err, restarts, ?backoff, "Strategy task panicked, restarting after backoff.");
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(RESTART_MAX_BACKOFF);
+            }
+        }
+    }
+}
+
+/// Pulls `TradeCandidate`s off the shared queue and runs them through
+/// quoting, risk checks, and submission. One slow/stuck candidate only ever
+/// occupies this one worker; the rest of the pool keeps draining the queue.
+#[allow(clippy::too_many_arguments)]
+async fn execution_worker(
+    worker_id: usize,
+    candidate_rx: Arc<Mutex<Receiver<TradeCandidate>>>,
+    db: Arc<Database>,
+    jupiter: Arc<JupiterClient>,
+    jito: Arc<JitoClient>,
+    signer_client: Arc<SignerClient>,
+    execution_latency: Arc<ExecutionLatencyMetrics>,
+    confirmation_tracker: Arc<ConfirmationTracker>,
+    priority_fee: Arc<PriorityFeeEstimator>,
+    sol_price_oracle: Arc<SolPriceOracle>,
+    rate_aggregator: Arc<RateAggregator>,
+) {
+    info!(worker_id, "Execution worker started.");
+    loop {
+        let candidate = {
+            let mut rx = candidate_rx.lock().await;
+            rx.recv().await
+        };
+        let Some(candidate) = candidate else {
+            info!(worker_id, "Candidate queue closed, execution worker exiting.");
+            return;
+        };
+
+        if let Err(e) = execute_trade(
+            db.clone(),
+            jupiter.clone(),
+            jito.clone(),
+            signer_client.clone(),
+            execution_latency.clone(),
+            confirmation_tracker.clone(),
+            priority_fee.clone(),
+            sol_price_oracle.clone(),
+            rate_aggregator.clone(),
+            candidate.details,
+            &candidate.strategy_id,
+            candidate.mode,
+        )
+        .await
+        {
+            error!(worker_id, error = %e, "Trade execution failed.");
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 #[instrument(skip_all, fields(strategy_id, token_address = %details.token_address, action = ?details.side))]
 async fn execute_trade(
     db: Arc<Database>,
     jupiter: Arc<JupiterClient>,
     jito: Arc<JitoClient>,
-    sol_price: Arc<Mutex<f64>>,
+    signer_client: Arc<SignerClient>,
+    execution_latency: Arc<ExecutionLatencyMetrics>,
+    confirmation_tracker: Arc<ConfirmationTracker>,
+    priority_fee: Arc<PriorityFeeEstimator>,
+    sol_price_oracle: Arc<SolPriceOracle>,
+    rate_aggregator: Arc<RateAggregator>,
     details: OrderDetails,
     strategy_id: &str,
     mode: TradeMode,
 ) -> Result<()> {
     let risk_manager = RiskManager::new();
     let redis_client = redis::Client::open(CONFIG.redis_url.clone())?;
-    if let Err(e) = risk_manager.validate_order(&details, &redis_client).await {
+    let risk_check_started = std::time::Instant::now();
+    let risk_result = risk_manager.validate_order(&details, &redis_client).await;
+    execution_latency.record(ExecutionStage::RiskCheck, mode, risk_check_started.elapsed().as_millis() as u64);
+    if let Err(e) = risk_result {
         warn!(error = %e, "Pre-trade risk check failed. Order rejected.");
         return Ok(());
     }
 
-    let current_sol_usd_price = *sol_price.lock().await;
-    if current_sol_usd_price <= 0.0 {
-        return Err(anyhow!("SOL/USD price not available or zero."));
+    let quote_timeout = std::time::Duration::from_secs(CONFIG.jupiter_quote_timeout_secs);
+
+    let sol_rate = rate_aggregator.latest_rate(SOL_MINT).await?;
+    let current_sol_usd_price = sol_rate.price_usd;
+    let quote_started = std::time::Instant::now();
+    let quote_result = tokio::time::timeout(
+        quote_timeout,
+        jupiter.get_quote(details.suggested_size_usd.to_f64() / current_sol_usd_price, &details.token_address, &sol_price_oracle),
+    )
+    .await;
+    execution_latency.record(ExecutionStage::Quote, mode, quote_started.elapsed().as_millis() as u64);
+    let price_quote = match quote_result {
+        Ok(result) => result?,
+        Err(_) => {
+            warn!(?quote_timeout, "Jupiter quote timed out, dropping candidate");
+            return Ok(());
+        }
+    };
+
+    if let OrderType::Limit { trigger_price_usd } = details.order_type {
+        if !limit_trigger_met(details.side, price_quote.price_per_token, trigger_price_usd) {
+            debug!(
+                quoted_price = price_quote.price_per_token,
+                trigger_price_usd = trigger_price_usd.to_f64(),
+                "Limit order trigger not met, dropping candidate"
+            );
+            return Ok(());
+        }
     }
-    
-    let price_quote = jupiter.get_quote(details.suggested_size_usd / current_sol_usd_price, &details.token_address).await?;
-    let trade_id = db.log_trade_attempt(&details, strategy_id, price_quote.price_per_token)?;
+
+    let trade_id = db.log_trade_attempt(&details, strategy_id, price_quote.price_per_token, Some(price_quote.out_amount))?;
+    let slippage_pct = CONFIG.slippage_bps as f64 / 10_000.0;
 
     match mode {
         TradeMode::Simulating => {
             simulate_trade(&redis_client, strategy_id, &details, price_quote.price_per_token).await?;
+            emit_fill_event(
+                &redis_client, trade_id, strategy_id, &details, mode,
+                price_quote.price_per_token, sol_rate.source, price_quote.out_amount, slippage_pct, FillStatus::New,
+            ).await?;
         }
         TradeMode::Paper => {
             info!(trade_id, "PAPER TRADING MODE: Simulating fill.");
             db.open_trade(trade_id, "paper-trade-signature")?;
+            emit_fill_event(
+                &redis_client, trade_id, strategy_id, &details, mode,
+                price_quote.price_per_token, sol_rate.source, price_quote.out_amount, slippage_pct, FillStatus::New,
+            ).await?;
         }
         TradeMode::Live => {
+            if !signer_client.is_healthy() {
+                warn!("Signer is unreachable, refusing to enter live trading for this candidate.");
+                return Ok(());
+            }
             info!(trade_id, "🔥 LIVE TRADING MODE: Executing real trade.");
-            let user_pk = Pubkey::from_str(&signer_client::get_pubkey().await?)?;
-            let swap_tx_b64 = jupiter.get_swap_transaction(&user_pk, &details.token_address, details.suggested_size_usd).await?;
-            let signed_tx_b64 = signer_client::sign_transaction(&swap_tx_b64).await?;
-            let mut tx = crate::jupiter::deserialize_transaction(&signed_tx_b64)?;
-
-            let bh = jito.get_recent_blockhash().await?;
-            tx.message.set_recent_blockhash(bh);
-            jito.attach_tip(&mut tx, CONFIG.jito_tip_lamports).await?;
-            let sig = jito.send_transaction(&tx).await?;
-            db.open_trade(trade_id, &sig.to_string())?;
+            let user_pk = Pubkey::from_str(&signer_client.get_pubkey().await?)?;
+            let swap_tx_b64 = match tokio::time::timeout(
+                quote_timeout,
+                jupiter.get_swap_transaction(
+                    &user_pk,
+                    &details.token_address,
+                    details.suggested_size_usd.to_f64(),
+                    CONFIG.slippage_bps,
+                    &sol_price_oracle,
+                ),
+            )
+            .await
+            {
+                Ok(result) => result?,
+                Err(_) => {
+                    warn!(?quote_timeout, "Jupiter swap transaction timed out, dropping candidate");
+                    return Ok(());
+                }
+            };
+
+            // Prepend a dynamic priority fee, fix the blockhash, and attach
+            // the Jito tip instruction before signing — every one of these
+            // rewrites the message bytes, so they all must land before the
+            // signer ever sees the transaction or its signature would cover
+            // stale bytes and fail verification on-chain.
+            let mut unsigned_tx = crate::jupiter::deserialize_transaction(
+                &swap_tx_b64,
+                &priority_fee,
+                FeeStrategy::Median,
+                current_sol_usd_price,
+            )
+            .await?;
+
+            let (bh, last_valid_block_height) = jito.get_recent_blockhash().await?;
+            unsigned_tx.message.set_recent_blockhash(bh);
+            jito.attach_tip(&mut unsigned_tx, CONFIG.jito_tip_lamports).await?;
+            let unsigned_tx_b64 = general_purpose::STANDARD.encode(bincode::serialize(&unsigned_tx)?);
+
+            let signing_started = std::time::Instant::now();
+            let signed_tx_b64 = signer_client.sign_transaction(&unsigned_tx_b64).await?;
+            execution_latency.record(ExecutionStage::Signing, mode, signing_started.elapsed().as_millis() as u64);
+            let tx: VersionedTransaction =
+                bincode::deserialize(&general_purpose::STANDARD.decode(signed_tx_b64)?)?;
+
+            let jito_send_started = std::time::Instant::now();
+            let send_result = jito.send_transaction(&tx).await;
+            execution_latency.record(ExecutionStage::JitoSend, mode, jito_send_started.elapsed().as_millis() as u64);
+
+            match send_result {
+                Ok(sig) => {
+                    db.open_trade(trade_id, &sig.to_string())?;
+                    // The send landing doesn't mean it's confirmed on-chain;
+                    // hand the signature off (along with everything needed to
+                    // re-sign and rebroadcast if its blockhash expires first)
+                    // so a worker polls it through to a filled/reverted/expired
+                    // outcome in the background.
+                    confirmation_tracker.track(
+                        trade_id,
+                        strategy_id.to_string(),
+                        sig,
+                        tx.clone(),
+                        last_valid_block_height,
+                        details.clone(),
+                        mode,
+                        price_quote.price_per_token,
+                        sol_rate.source,
+                        price_quote.out_amount,
+                        slippage_pct,
+                    );
+                    emit_fill_event(
+                        &redis_client, trade_id, strategy_id, &details, mode,
+                        price_quote.price_per_token, sol_rate.source, price_quote.out_amount, slippage_pct, FillStatus::New,
+                    ).await?;
+                }
+                Err(e) => {
+                    // The fill never landed on-chain; emit a Revoked record so
+                    // consumers that optimistically tracked this trade retract it.
+                    emit_fill_event(
+                        &redis_client, trade_id, strategy_id, &details, mode,
+                        price_quote.price_per_token, sol_rate.source, price_quote.out_amount, slippage_pct, FillStatus::Revoked,
+                    ).await?;
+                    return Err(e).context("Failed to submit live trade transaction");
+                }
+            }
         }
     }
     Ok(())
 }
 
+/// Normalize a fill into the canonical `FillEvent` schema and publish it on
+/// `events:fills`, so every `TradeMode` reports settlement through one code
+/// path instead of each mode writing its own ad-hoc payload.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn emit_fill_event(
+    redis_client: &redis::Client,
+    trade_id: i64,
+    strategy_id: &str,
+    details: &OrderDetails,
+    mode: TradeMode,
+    price_usd: f64,
+    price_source: &'static str,
+    filled_size_token: TokenAmount,
+    slippage_pct: f64,
+    status: FillStatus,
+) -> Result<()> {
+    let fill = FillEvent {
+        trade_id,
+        strategy_id: strategy_id.to_string(),
+        token_address: details.token_address.clone(),
+        side: details.side,
+        mode,
+        price_usd,
+        price_source: price_source.to_string(),
+        filled_size_usd: filled_size_token.to_f64() * price_usd,
+        filled_size_token,
+        // No per-fill fee accounting yet; the priority fee is paid in SOL
+        // separately from the swap and isn't attributed back to a trade_id.
+        fee_usd: 0.0,
+        slippage_pct,
+        status,
+        timestamp: chrono::Utc::now().timestamp(),
+    };
+
+    let mut conn = redis_client.get_async_connection().await?;
+    let payload = serde_json::to_string(&shared_models::MarketEvent::Fill(fill))?;
+    conn.xadd(FILLS_STREAM, "*", &[("data", payload)]).await?;
+    Ok(())
+}
+
+/// Whether a `Limit` order's trigger has been reached: a `Long` wants to buy
+/// at or below `trigger_price_usd`, a `Short` wants to sell at or above it.
+fn limit_trigger_met(side: Side, quoted_price_usd: f64, trigger_price_usd: Price) -> bool {
+    let trigger_price_usd = trigger_price_usd.to_f64();
+    match side {
+        Side::Long => quoted_price_usd <= trigger_price_usd,
+        Side::Short => quoted_price_usd >= trigger_price_usd,
+    }
+}
+
 async fn simulate_trade(
     redis_client: &redis::Client,
     strategy_id: &str,
@@ -300,16 +1211,16 @@ async fn simulate_trade(
     price: f64,
 ) -> Result<()> {
     let mut conn = redis_client.get_async_connection().await?;
-    let sim_pnl = details.suggested_size_usd * (rand::random::<f64>() * 0.02 - 0.01); // +/- 1% PnL
-    
+    let sim_pnl = details.suggested_size_usd.to_f64() * (rand::random::<f64>() * 0.02 - 0.01); // +/- 1% PnL
+
     let shadow_trade = serde_json::json!({
         "pnl": sim_pnl,
         "price": price,
     });
-    
+
     conn.xadd(format!("shadow_ledger:{}", strategy_id), "*", &[("trade", &serde_json::to_string(&shadow_trade)?)])
         .await?;
-    
+
     debug!(strategy_id, "Simulated trade recorded to shadow ledger.");
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,303 @@
+//! WebSocket fan-out server for shadow-ledger and live fills.
+//!
+//! Mirrors `ws_server.rs`'s peer/checkpoint pattern, but keys subscriptions by
+//! `strategy_id` instead of channel name, and the checkpoint sent on
+//! subscribe is the latest `CHECKPOINT_LEN` fills for that strategy rather
+//! than a single open-trades snapshot, so late joiners can render recent
+//! history before the live stream starts flowing.
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use shared_models::{FillEvent, MarketEvent, TradeMode};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, error, info, warn};
+
+const FILLS_STREAM: &str = "events:fills";
+const SHADOW_LEDGER_PREFIX: &str = "shadow_ledger:";
+/// Fills kept per strategy so a client that just subscribed has something to
+/// render before the next live fill arrives.
+const CHECKPOINT_LEN: usize = 20;
+
+/// A fill normalized across every source (`events:fills` for paper/live
+/// trades, `shadow_ledger:{strategy_id}` for simulated ones) into one shape,
+/// so subscribers don't need to know which mode produced it to render it.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnifiedFill {
+    pub strategy_id: String,
+    pub price: f64,
+    pub pnl: Option<f64>,
+    pub mode: TradeMode,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+enum OutboundMessage {
+    Fill(UnifiedFill),
+    Checkpoint { strategy_id: String, fills: Vec<UnifiedFill> },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "command", rename_all = "lowercase")]
+enum ControlMessage {
+    Subscribe { strategy_id: String },
+    Unsubscribe { strategy_id: String },
+}
+
+type PeerId = u64;
+
+/// Per-peer subscription state, keyed by a monotonically increasing peer id.
+type PeerMap = Arc<Mutex<HashMap<PeerId, HashSet<String>>>>;
+/// Latest `CHECKPOINT_LEN` fills per strategy, replayed to a peer on subscribe.
+type Checkpoints = Arc<Mutex<HashMap<String, VecDeque<UnifiedFill>>>>;
+
+pub struct FillsServer {
+    bind_addr: SocketAddr,
+    redis_client: redis::Client,
+    tx: broadcast::Sender<UnifiedFill>,
+    checkpoints: Checkpoints,
+}
+
+impl FillsServer {
+    pub fn new(bind_addr: SocketAddr, redis_client: redis::Client) -> Self {
+        let (tx, _rx) = broadcast::channel(2048);
+        Self { bind_addr, redis_client, tx, checkpoints: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    pub async fn run(self) -> Result<()> {
+        let listener = TcpListener::bind(self.bind_addr)
+            .await
+            .with_context(|| format!("Failed to bind fills fan-out server on {}", self.bind_addr))?;
+        info!(addr = %self.bind_addr, "📡 Fills fan-out server listening");
+
+        let reader_tx = self.tx.clone();
+        let reader_checkpoints = self.checkpoints.clone();
+        let reader_client = self.redis_client.clone();
+        tokio::spawn(async move {
+            if let Err(e) = stream_fills(reader_client, reader_tx, reader_checkpoints).await {
+                error!("Fills stream reader exited: {}", e);
+            }
+        });
+
+        let peers: PeerMap = Arc::new(Mutex::new(HashMap::new()));
+        let mut next_peer_id: PeerId = 0;
+
+        loop {
+            let (stream, addr) = match listener.accept().await {
+                Ok(v) => v,
+                Err(e) => {
+                    error!(error = %e, "Failed to accept fills WS connection");
+                    continue;
+                }
+            };
+
+            next_peer_id += 1;
+            let peer_id = next_peer_id;
+            let rx = self.tx.subscribe();
+            let peers = peers.clone();
+            let checkpoints = self.checkpoints.clone();
+
+            tokio::spawn(async move {
+                if let Err(e) = handle_peer(peer_id, stream, addr, peers.clone(), rx, checkpoints).await {
+                    warn!(peer_id, error = %e, "Fills WS peer connection ended with error");
+                }
+                peers.lock().unwrap().remove(&peer_id);
+            });
+        }
+    }
+}
+
+/// Reads `events:fills` and every discovered `shadow_ledger:*` stream,
+/// normalizes each entry into a `UnifiedFill`, feeds it into the checkpoint
+/// buffer, and broadcasts it to subscribed peers.
+async fn stream_fills(
+    redis_client: redis::Client,
+    tx: broadcast::Sender<UnifiedFill>,
+    checkpoints: Checkpoints,
+) -> Result<()> {
+    let mut conn = redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .context("Failed to connect fills reader to Redis")?;
+
+    let mut stream_ids: HashMap<String, String> = HashMap::new();
+    stream_ids.insert(FILLS_STREAM.to_string(), "$".to_string());
+
+    info!("📡 Fills stream reader started, monitoring {} and shadow ledgers...", FILLS_STREAM);
+
+    loop {
+        // There's no durable registry of which strategies exist, so newly
+        // created shadow ledgers are picked up by periodically listing keys
+        // rather than a push notification; cheap at this key count.
+        let shadow_keys: Vec<String> =
+            conn.keys(format!("{}*", SHADOW_LEDGER_PREFIX)).await.unwrap_or_default();
+        for key in shadow_keys {
+            stream_ids.entry(key).or_insert_with(|| "0".to_string());
+        }
+
+        let keys: Vec<String> = stream_ids.keys().cloned().collect();
+        let ids: Vec<String> = stream_ids.values().cloned().collect();
+
+        match conn
+            .xread_options::<String, String, redis::streams::StreamReadReply>(
+                &keys,
+                &ids,
+                &redis::streams::StreamReadOptions::default().block(1000).count(100),
+            )
+            .await
+        {
+            Ok(reply) => {
+                for stream_key in reply.keys {
+                    let stream_name = stream_key.key.clone();
+                    for message in stream_key.ids {
+                        stream_ids.insert(stream_name.clone(), message.id.clone());
+
+                        let Some(fill) = decode_fill(&stream_name, &message.map) else {
+                            continue;
+                        };
+
+                        {
+                            let mut checkpoints = checkpoints.lock().unwrap();
+                            let buffer = checkpoints.entry(fill.strategy_id.clone()).or_default();
+                            buffer.push_back(fill.clone());
+                            while buffer.len() > CHECKPOINT_LEN {
+                                buffer.pop_front();
+                            }
+                        }
+
+                        let _ = tx.send(fill);
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("Fills stream read error: {}, retrying", e);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
+
+fn decode_fill(stream_name: &str, fields: &HashMap<String, redis::Value>) -> Option<UnifiedFill> {
+    if stream_name == FILLS_STREAM {
+        let data = fields.get("data")?;
+        let event_str = redis::from_redis_value::<String>(data).ok()?;
+        let MarketEvent::Fill(FillEvent { strategy_id, price_usd, mode, timestamp, .. }) =
+            serde_json::from_str::<MarketEvent>(&event_str).ok()?
+        else {
+            return None;
+        };
+        return Some(UnifiedFill { strategy_id, price: price_usd, pnl: None, mode, timestamp });
+    }
+
+    let strategy_id = stream_name.strip_prefix(SHADOW_LEDGER_PREFIX)?.to_string();
+    let trade = fields.get("trade")?;
+    let trade_str = redis::from_redis_value::<String>(trade).ok()?;
+    let trade_json: serde_json::Value = serde_json::from_str(&trade_str).ok()?;
+    Some(UnifiedFill {
+        strategy_id,
+        price: trade_json.get("price")?.as_f64()?,
+        pnl: trade_json.get("pnl").and_then(|v| v.as_f64()),
+        mode: TradeMode::Simulating,
+        timestamp: chrono::Utc::now().timestamp(),
+    })
+}
+
+async fn handle_peer(
+    peer_id: PeerId,
+    stream: tokio::net::TcpStream,
+    addr: SocketAddr,
+    peers: PeerMap,
+    mut rx: broadcast::Receiver<UnifiedFill>,
+    checkpoints: Checkpoints,
+) -> Result<()> {
+    let ws_stream = tokio_tungstenite::accept_async(stream).await.context("WS handshake failed")?;
+    info!(peer_id, %addr, "Fills WS peer connected");
+    peers.lock().unwrap().insert(peer_id, HashSet::new());
+
+    let (mut ws_tx, mut ws_rx) = ws_stream.split();
+
+    loop {
+        tokio::select! {
+            incoming = ws_rx.next() => {
+                match incoming {
+                    Some(Ok(Message::Text(text))) => {
+                        if let Ok(ctrl) = serde_json::from_str::<ControlMessage>(&text) {
+                            handle_control(peer_id, ctrl, &peers, &checkpoints, &mut ws_tx).await?;
+                        } else {
+                            debug!(peer_id, "Ignoring unrecognized control message: {}", text);
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => {
+                        warn!(peer_id, error = %e, "Fills WS read error");
+                        break;
+                    }
+                }
+            }
+            msg = rx.recv() => {
+                match msg {
+                    Ok(fill) => {
+                        if peers.lock().unwrap().get(&peer_id).map(|s| s.contains(&fill.strategy_id)).unwrap_or(false) {
+                            let payload = serde_json::to_string(&OutboundMessage::Fill(fill))?;
+                            if ws_tx.send(Message::Text(payload)).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!(peer_id, skipped = n, "Fills WS peer lagged, dropping buffered fills");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        }
+    }
+
+    info!(peer_id, "Fills WS peer disconnected");
+    Ok(())
+}
+
+async fn handle_control(
+    peer_id: PeerId,
+    ctrl: ControlMessage,
+    peers: &PeerMap,
+    checkpoints: &Checkpoints,
+    ws_tx: &mut (impl SinkExt<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin),
+) -> Result<()> {
+    match ctrl {
+        ControlMessage::Subscribe { strategy_id } => {
+            {
+                let mut peers = peers.lock().unwrap();
+                if let Some(subs) = peers.get_mut(&peer_id) {
+                    subs.insert(strategy_id.clone());
+                }
+            }
+
+            // Send a checkpoint so the new subscriber starts from current
+            // state before the live stream delivers the next fill.
+            let fills: Vec<UnifiedFill> = checkpoints
+                .lock()
+                .unwrap()
+                .get(&strategy_id)
+                .map(|buf| buf.iter().cloned().collect())
+                .unwrap_or_default();
+            let payload = serde_json::to_string(&OutboundMessage::Checkpoint { strategy_id, fills })?;
+            let _ = ws_tx.send(Message::Text(payload)).await;
+        }
+        ControlMessage::Unsubscribe { strategy_id } => {
+            let mut peers = peers.lock().unwrap();
+            if let Some(subs) = peers.get_mut(&peer_id) {
+                subs.remove(&strategy_id);
+            }
+        }
+    }
+    Ok(())
+}
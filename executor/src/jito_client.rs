@@ -1,16 +1,56 @@
+//! Client for the Jito Block Engine. Mirrors `SignerClient`'s reused-HTTP-client
+//! approach: a single `reqwest::Client` for the JSON-RPC calls (`getTipAccounts`,
+//! `sendBundle`, `getBundleStatuses`) instead of one per call, plus a
+//! `solana_client` RPC handle for the plain-RPC fallback send and blockhash/
+//! signature-status lookups that aren't Jito-specific.
+use crate::jupiter::decompile;
 use anyhow::{anyhow, Context, Result};
+use base64::{Engine as _, engine::general_purpose};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     hash::Hash,
-    signature::{read_keypair_file, Signature, Signer},
+    message::{Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signature},
+    system_instruction,
     transaction::VersionedTransaction,
 };
 use std::sync::Arc;
-use tracing::info;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+const BLOCK_ENGINE_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a cached `getTipAccounts` response is trusted before it's
+/// re-fetched; tip accounts rotate occasionally but not every bundle.
+const TIP_ACCOUNTS_TTL: Duration = Duration::from_secs(300);
+
+const BUNDLE_STATUS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const BUNDLE_STATUS_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Outcome of polling `getBundleStatuses` to completion, mirroring
+/// `JitoClient::get_signature_status`'s landed/reverted/unknown split but
+/// collapsed to the three states a caller of `get_bundle_status` needs to
+/// branch on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleOutcome {
+    Landed,
+    Failed,
+    TimedOut,
+}
 
 pub struct JitoClient {
     auth_keypair: Arc<solana_sdk::signature::Keypair>,
     rpc_client: solana_client::nonblocking::rpc_client::RpcClient,
+    http: Client,
+    block_engine_url: String,
+    /// Cached `getTipAccounts` result alongside when it was fetched, so a tip
+    /// is attached without a round-trip on every single transaction.
+    tip_accounts: Mutex<Option<(Vec<Pubkey>, Instant)>>,
 }
 
 impl JitoClient {
@@ -22,32 +62,226 @@ impl JitoClient {
             read_keypair_file(&format!("/app/wallet/{}", auth_keypair_path))
                 .map_err(|e| anyhow!("Failed to read Jito auth keypair from {}: {}", auth_keypair_path, e))?
         );
-        
+
         let rpc_client = solana_client::nonblocking::rpc_client::RpcClient::new_with_commitment(
             jito_rpc_url.to_string(),
             CommitmentConfig::confirmed(),
         );
 
+        let http = Client::builder()
+            .timeout(BLOCK_ENGINE_REQUEST_TIMEOUT)
+            .build()
+            .context("Failed to build HTTP client for Jito Block Engine")?;
+
         info!("Jito client initialized successfully.");
-        Ok(Self { auth_keypair, rpc_client })
+        Ok(Self {
+            auth_keypair,
+            rpc_client,
+            http,
+            block_engine_url: jito_rpc_url.to_string(),
+            tip_accounts: Mutex::new(None),
+        })
+    }
+
+    /// Returns the latest blockhash alongside the block height past which it
+    /// is no longer valid, so a caller can tell a confirmation worker exactly
+    /// when to give up waiting and rebroadcast instead of guessing from a
+    /// fixed timeout.
+    pub async fn get_recent_blockhash(&self) -> Result<(Hash, u64)> {
+        self.rpc_client
+            .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+            .await
+            .context("Failed to get recent blockhash from RPC")
     }
 
-    pub async fn get_recent_blockhash(&self) -> Result<Hash> {
-        self.rpc_client.get_latest_blockhash().await.context("Failed to get recent blockhash from RPC")
+    /// Current block height, compared against a transaction's
+    /// `last_valid_block_height` to tell whether its blockhash has expired.
+    pub async fn get_block_height(&self) -> Result<u64> {
+        self.rpc_client.get_block_height().await.context("Failed to get current block height from RPC")
     }
 
+    /// Fetches the current set of Jito tip accounts via `getTipAccounts`,
+    /// reusing the cached set until it's older than `TIP_ACCOUNTS_TTL`.
+    async fn tip_account(&self) -> Result<Pubkey> {
+        {
+            let cache = self.tip_accounts.lock().await;
+            if let Some((accounts, fetched_at)) = cache.as_ref() {
+                if fetched_at.elapsed() < TIP_ACCOUNTS_TTL && !accounts.is_empty() {
+                    return Ok(pick_tip_account(accounts));
+                }
+            }
+        }
+
+        let response = self
+            .block_engine_rpc("getTipAccounts", json!([]))
+            .await
+            .context("Failed to fetch tip accounts from Jito Block Engine")?;
+        let addresses: Vec<String> = serde_json::from_value(response).context("Malformed getTipAccounts response")?;
+        let accounts: Vec<Pubkey> = addresses
+            .iter()
+            .map(|a| a.parse().context("Malformed tip account pubkey"))
+            .collect::<Result<_>>()?;
+        if accounts.is_empty() {
+            return Err(anyhow!("getTipAccounts returned no tip accounts"));
+        }
+
+        let picked = pick_tip_account(&accounts);
+        *self.tip_accounts.lock().await = Some((accounts, Instant::now()));
+        Ok(picked)
+    }
+
+    /// Appends a `SystemProgram::transfer` from the transaction's fee payer to
+    /// a cached Jito tip account, decompiling and recompiling the message the
+    /// same way `jupiter::deserialize_transaction` prepends its priority-fee
+    /// instruction, so the tip rides in the same atomic unit as the rest of
+    /// the transaction rather than being sent separately. Like that helper,
+    /// this invalidates any existing signature over the message.
     pub async fn attach_tip(&self, tx: &mut VersionedTransaction, tip_lamports: u64) -> Result<()> {
-        // In a real implementation, you would modify the transaction to include a tip
-        // This is a simplified placeholder
-        info!("Jito tip attachment of {} lamports simulated.", tip_lamports);
+        let tip_account = self.tip_account().await?;
+        let recent_blockhash = match &tx.message {
+            VersionedMessage::Legacy(message) => message.recent_blockhash,
+            VersionedMessage::V0(message) => message.recent_blockhash,
+        };
+
+        let (mut instructions, _account_keys, payer) = decompile(&tx.message)?;
+        instructions.push(system_instruction::transfer(&payer, &tip_account, tip_lamports));
+
+        let mut message = Message::new(&instructions, Some(&payer));
+        message.recent_blockhash = recent_blockhash;
+        let num_required_signatures = message.header.num_required_signatures as usize;
+
+        tx.message = VersionedMessage::Legacy(message);
+        if tx.signatures.len() != num_required_signatures {
+            tx.signatures = vec![Default::default(); num_required_signatures];
+        }
+
+        info!(tip_lamports, tip_account = %tip_account, "Attached Jito tip instruction to transaction.");
         Ok(())
     }
 
+    /// Sends directly to the configured RPC rather than a Jito bundle, for
+    /// call sites that don't need bundle-level atomicity across multiple
+    /// transactions.
     pub async fn send_transaction(&self, tx: &VersionedTransaction) -> Result<Signature> {
-        // In a real implementation, this would send to Jito's block engine
-        // For now, sending to regular RPC
         let sig = self.rpc_client.send_transaction(tx).await?;
         info!("Transaction sent. Signature: {}", sig);
         Ok(sig)
     }
+
+    /// Submits `txs` as a single atomic Jito bundle via `sendBundle`,
+    /// returning the bundle UUID `get_bundle_status` can then poll.
+    pub async fn send_bundle(&self, txs: &[VersionedTransaction]) -> Result<String> {
+        let encoded: Vec<String> = txs
+            .iter()
+            .map(|tx| bincode::serialize(tx).map(|bytes| general_purpose::STANDARD.encode(bytes)))
+            .collect::<std::result::Result<_, _>>()
+            .context("Failed to encode bundle transactions")?;
+
+        let response = self
+            .block_engine_rpc("sendBundle", json!([encoded, { "encoding": "base64" }]))
+            .await
+            .context("Failed to submit bundle to Jito Block Engine")?;
+        let bundle_uuid: String = serde_json::from_value(response).context("Malformed sendBundle response")?;
+        info!(bundle_uuid = %bundle_uuid, num_txs = txs.len(), "Submitted Jito bundle.");
+        Ok(bundle_uuid)
+    }
+
+    /// Polls `getBundleStatuses` for `bundle_uuid` until it lands, fails, or
+    /// `BUNDLE_STATUS_TIMEOUT` elapses.
+    pub async fn get_bundle_status(&self, bundle_uuid: &str) -> Result<BundleOutcome> {
+        let deadline = Instant::now() + BUNDLE_STATUS_TIMEOUT;
+        loop {
+            let response = self
+                .block_engine_rpc("getBundleStatuses", json!([[bundle_uuid]]))
+                .await
+                .context("Failed to poll bundle status from Jito Block Engine")?;
+            let statuses: GetBundleStatusesResult =
+                serde_json::from_value(response).context("Malformed getBundleStatuses response")?;
+
+            match statuses.value.first() {
+                Some(status)
+                    if status.confirmation_status.as_deref() == Some("finalized")
+                        || status.confirmation_status.as_deref() == Some("confirmed") =>
+                {
+                    if status.err.is_some() {
+                        warn!(bundle_uuid, "Jito bundle landed but reverted on-chain.");
+                        return Ok(BundleOutcome::Failed);
+                    }
+                    info!(bundle_uuid, "Jito bundle landed.");
+                    return Ok(BundleOutcome::Landed);
+                }
+                _ => {
+                    if Instant::now() >= deadline {
+                        warn!(bundle_uuid, "Timed out waiting for Jito bundle to land.");
+                        return Ok(BundleOutcome::TimedOut);
+                    }
+                    tokio::time::sleep(BUNDLE_STATUS_POLL_INTERVAL).await;
+                }
+            }
+        }
+    }
+
+    /// Posts a `{jsonrpc, method, params}` JSON-RPC request to the Block
+    /// Engine URL and returns the `result` field, the same shape every
+    /// Jito Block Engine HTTP method (`getTipAccounts`/`sendBundle`/
+    /// `getBundleStatuses`) responds with.
+    async fn block_engine_rpc(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let response: Value = self
+            .http
+            .post(format!("{}/api/v1/bundles", self.block_engine_url))
+            .json(&body)
+            .send()
+            .await
+            .context("Failed to reach Jito Block Engine")?
+            .json()
+            .await
+            .context("Failed to parse Jito Block Engine response")?;
+
+        if let Some(error) = response.get("error") {
+            return Err(anyhow!("Jito Block Engine returned an error for {}: {}", method, error));
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("Jito Block Engine response for {} had no result field", method))
+    }
+
+    /// `None` means the signature hasn't landed (or been dropped) yet;
+    /// `Some(Ok(()))`/`Some(Err(_))` are the on-chain success/revert outcome
+    /// once it has.
+    pub async fn get_signature_status(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<std::result::Result<(), solana_sdk::transaction::TransactionError>>> {
+        let response = self
+            .rpc_client
+            .get_signature_statuses(&[*signature])
+            .await
+            .context("Failed to fetch signature status from RPC")?;
+        Ok(response.value.into_iter().next().flatten().map(|status| status.status))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GetBundleStatusesResult {
+    value: Vec<BundleStatusEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BundleStatusEntry {
+    confirmation_status: Option<String>,
+    #[serde(default)]
+    err: Option<Value>,
+}
+
+/// Picks a tip account to spread tips across the set rather than hammering
+/// the same one every bundle, mirroring Jito's own documented recommendation.
+fn pick_tip_account(accounts: &[Pubkey]) -> Pubkey {
+    accounts[rand::random::<usize>() % accounts.len()]
 }
@@ -1,15 +1,25 @@
+use crate::priority_fee::{FeeStrategy, PriorityFeeEstimator};
+use crate::sol_price_oracle::SolPriceOracle;
 use anyhow::{anyhow, Context, Result};
 use base64::{Engine as _, engine::general_purpose};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
+use shared_models::TokenAmount;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{AccountMeta, Instruction},
+    message::{Message, VersionedMessage},
+    pubkey::Pubkey,
+    transaction::VersionedTransaction,
+};
+use std::collections::HashMap;
 use std::time::Duration;
-use tracing::info;
+use tracing::{info, warn};
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct JupiterQuote {
-    pub out_amount: String,
+    pub out_amount: TokenAmount,
     #[serde(rename = "marketInfos")]
     pub market_infos: Vec<MarketInfo>,
 }
@@ -43,12 +53,48 @@ pub struct SwapResponse {
 
 #[derive(Debug, Serialize)]
 pub struct QuoteResult {
-    pub out_amount: u64,
+    pub out_amount: TokenAmount,
     pub price_per_token: f64,
 }
 
+/// Deterministic fallback price (USD) for mock mode when a token isn't in the
+/// seeded fixture, so an unexpected token still gets a usable quote instead
+/// of an error.
+const DEFAULT_MOCK_PRICE_USD: f64 = 1.0;
+
+/// In-memory price map backing `Backend::Mock`, optionally seeded from a JSON
+/// fixture of `{ "<token_mint>": <price_usd>, ... }`.
+pub struct MockState {
+    prices: HashMap<String, f64>,
+}
+
+impl MockState {
+    fn new(fixture_path: Option<&str>) -> Self {
+        let prices = fixture_path
+            .and_then(|path| match std::fs::read_to_string(path) {
+                Ok(contents) => serde_json::from_str(&contents).ok(),
+                Err(e) => {
+                    warn!("Failed to read mock Jupiter fixture {}: {}", path, e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self { prices }
+    }
+
+    fn price_for(&self, token_mint: &str) -> f64 {
+        *self.prices.get(token_mint).unwrap_or(&DEFAULT_MOCK_PRICE_USD)
+    }
+}
+
+enum Backend {
+    Live(Client),
+    Mock(MockState),
+}
+
 pub struct JupiterClient {
-    client: Client,
+    backend: Backend,
 }
 
 impl JupiterClient {
@@ -57,66 +103,188 @@ impl JupiterClient {
             .timeout(Duration::from_secs(15))
             .build()
             .context("Failed to create HTTP client for Jupiter")?;
-        
-        Ok(Self { client })
+
+        Ok(Self { backend: Backend::Live(client) })
     }
 
-    pub async fn get_quote(&self, amount_sol: f64, output_mint: &str) -> Result<JupiterQuote> {
-        // Hardcoded for now - should be passed as parameter
-        let slippage_bps = 30;
-        
-        let input_mint = "So11111111111111111111111111111111111111112"; // SOL mint
-        let amount_lamports = (amount_sol * 1_000_000_000.0) as u64;
+    /// Backed by an in-memory price map instead of `quote-api.jup.ag`, so the
+    /// simulated-trading strategy pipeline produces deterministic, reproducible
+    /// fills in CI. `fixture_path`, if given, seeds the map from a JSON file of
+    /// `{ "<token_mint>": <price_usd> }`; unseeded tokens fall back to
+    /// `DEFAULT_MOCK_PRICE_USD`.
+    pub fn new_mock(fixture_path: Option<&str>) -> Self {
+        Self { backend: Backend::Mock(MockState::new(fixture_path)) }
+    }
+
+    pub async fn get_quote(
+        &self,
+        amount_sol: f64,
+        output_mint: &str,
+        sol_price_oracle: &SolPriceOracle,
+    ) -> Result<QuoteResult> {
+        let sol_usd_price = sol_price_oracle.current_sol_price().await?;
+        let amount_usd = amount_sol * sol_usd_price;
+
+        match &self.backend {
+            Backend::Live(client) => {
+                // Hardcoded for now - should be passed as parameter
+                let slippage_bps = 30;
 
-        let quote_url = format!(
-            "https://quote-api.jup.ag/v6/quote?inputMint={}&outputMint={}&amount={}&slippageBps={}",
-            input_mint, output_mint, amount_lamports, slippage_bps
-        );
+                let input_mint = "So11111111111111111111111111111111111111112"; // SOL mint
+                let amount_lamports = (amount_sol * 1_000_000_000.0) as u64;
 
-        let response: JupiterQuote = self.client.get(&quote_url).send().await?.json().await?;
-        Ok(response)
+                let quote_url = format!(
+                    "https://quote-api.jup.ag/v6/quote?inputMint={}&outputMint={}&amount={}&slippageBps={}",
+                    input_mint, output_mint, amount_lamports, slippage_bps
+                );
+
+                let response: JupiterQuote = client.get(&quote_url).send().await?.json().await?;
+                let price_per_token = amount_usd / response.out_amount.to_f64();
+                Ok(QuoteResult { out_amount: response.out_amount, price_per_token })
+            }
+            Backend::Mock(mock) => {
+                let price_usd = mock.price_for(output_mint);
+                let out_amount = TokenAmount::from_human(amount_usd / price_usd, TokenAmount::DEFAULT_DECIMALS);
+                Ok(QuoteResult { out_amount, price_per_token: price_usd })
+            }
+        }
     }
 
     pub async fn get_price(&self, token_mint: &str) -> Result<f64> {
-        // Hardcoded jupiter URL for now
-        let jupiter_url = "https://quote-api.jup.ag/v6";
-        let url = format!("{}/price?ids={}", jupiter_url, token_mint);
-        
-        let response: serde_json::Value = self.client.get(&url).send().await?.json().await?;
-        
-        if let Some(price_data) = response.get("data").and_then(|d| d.get(token_mint)) {
-            if let Some(price) = price_data.get("price").and_then(|p| p.as_f64()) {
-                return Ok(price);
+        match &self.backend {
+            Backend::Live(client) => {
+                // Hardcoded jupiter URL for now
+                let jupiter_url = "https://quote-api.jup.ag/v6";
+                let url = format!("{}/price?ids={}", jupiter_url, token_mint);
+
+                let response: serde_json::Value = client.get(&url).send().await?.json().await?;
+
+                if let Some(price_data) = response.get("data").and_then(|d| d.get(token_mint)) {
+                    if let Some(price) = price_data.get("price").and_then(|p| p.as_f64()) {
+                        return Ok(price);
+                    }
+                }
+
+                Err(anyhow!("Failed to get price for token {}", token_mint))
             }
+            Backend::Mock(mock) => Ok(mock.price_for(token_mint)),
         }
-        
-        Err(anyhow!("Failed to get price for token {}", token_mint))
     }
 
-    pub async fn get_swap_transaction(&self, user_pubkey: &Pubkey, output_mint: &str, amount_usd_to_swap: f64, slippage_bps: u16) -> Result<String> {
-        let amount_sol_approx = amount_usd_to_swap / 150.0;
-        let amount_lamports = (amount_sol_approx * 1_000_000_000.0) as u64;
-
-        let quote_url = format!(
-            "https://quote-api.jup.ag/v6/quote?inputMint=So11111111111111111111111111111111111111112&outputMint={}&amount={}&slippageBps={}",
-            output_mint, amount_lamports, slippage_bps
-        );
-        let quote_response: serde_json::Value = self.client.get(&quote_url).send().await?.json().await?;
-        
-        let swap_payload = serde_json::json!({
-            "quoteResponse": quote_response,
-            "userPublicKey": user_pubkey.to_string(),
-            "wrapAndUnwrapSol": true,
-        });
-
-        let swap_url = "https://quote-api.jup.ag/v6/swap";
-        let response: SwapResponse = self.client.post(swap_url).json(&swap_payload).send().await?.json().await?;
-        info!("Generated Jupiter swap transaction for {} USD.", amount_usd_to_swap);
-        Ok(response.swap_transaction)
+    pub async fn get_swap_transaction(
+        &self,
+        user_pubkey: &Pubkey,
+        output_mint: &str,
+        amount_usd_to_swap: f64,
+        slippage_bps: u16,
+        sol_price_oracle: &SolPriceOracle,
+    ) -> Result<String> {
+        match &self.backend {
+            Backend::Live(client) => {
+                let sol_usd_price = sol_price_oracle.current_sol_price().await?;
+                let amount_sol_approx = amount_usd_to_swap / sol_usd_price;
+                let amount_lamports = (amount_sol_approx * 1_000_000_000.0) as u64;
+
+                let quote_url = format!(
+                    "https://quote-api.jup.ag/v6/quote?inputMint=So11111111111111111111111111111111111111112&outputMint={}&amount={}&slippageBps={}",
+                    output_mint, amount_lamports, slippage_bps
+                );
+                let quote_response: serde_json::Value = client.get(&quote_url).send().await?.json().await?;
+
+                let swap_payload = serde_json::json!({
+                    "quoteResponse": quote_response,
+                    "userPublicKey": user_pubkey.to_string(),
+                    "wrapAndUnwrapSol": true,
+                });
+
+                let swap_url = "https://quote-api.jup.ag/v6/swap";
+                let response: SwapResponse = client.post(swap_url).json(&swap_payload).send().await?.json().await?;
+                info!("Generated Jupiter swap transaction for {} USD.", amount_usd_to_swap);
+                Ok(response.swap_transaction)
+            }
+            Backend::Mock(_) => {
+                info!("Generated mock Jupiter swap transaction for {} USD.", amount_usd_to_swap);
+                Ok(dummy_transaction_b64(user_pubkey)?)
+            }
+        }
     }
 }
 
-pub fn deserialize_transaction(tx_b64: &str) -> Result<VersionedTransaction> {
+/// A well-formed, unsigned, single-account no-op `VersionedTransaction` used
+/// as the mock swap "transaction" — enough to exercise priority-fee
+/// prepending and the signing pipeline without touching the network.
+fn dummy_transaction_b64(payer: &Pubkey) -> Result<String> {
+    let message = Message::new(&[], Some(payer));
+    let tx = VersionedTransaction {
+        signatures: vec![Default::default(); message.header.num_required_signatures.max(1) as usize],
+        message: VersionedMessage::Legacy(message),
+    };
+    Ok(general_purpose::STANDARD.encode(bincode::serialize(&tx)?))
+}
+
+/// Deserialize a base64, unsigned Jupiter swap transaction and prepend a
+/// `SetComputeUnitPrice` instruction sized by `priority_fee` so the swap is
+/// competitive with current network activity. Must be called before signing:
+/// prepending an instruction changes the message, which would invalidate any
+/// existing signature.
+pub async fn deserialize_transaction(
+    tx_b64: &str,
+    priority_fee: &PriorityFeeEstimator,
+    fee_strategy: FeeStrategy,
+    sol_usd_price: f64,
+) -> Result<VersionedTransaction> {
     let tx_bytes = general_purpose::STANDARD.decode(tx_b64)?;
-    bincode::deserialize(&tx_bytes).context("Failed to deserialize transaction")
+    let tx: VersionedTransaction =
+        bincode::deserialize(&tx_bytes).context("Failed to deserialize transaction")?;
+
+    let (mut instructions, account_keys, payer) = decompile(&tx.message)?;
+    let writable_accounts: Vec<Pubkey> = account_keys
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| tx.message.is_maybe_writable(*i))
+        .map(|(_, key)| *key)
+        .collect();
+
+    let micro_lamports_per_cu = priority_fee
+        .estimate_micro_lamports(&writable_accounts, fee_strategy, sol_usd_price)
+        .await;
+
+    instructions.insert(0, ComputeBudgetInstruction::set_compute_unit_price(micro_lamports_per_cu));
+
+    let message = Message::new(&instructions, Some(&payer));
+    Ok(VersionedTransaction {
+        signatures: vec![Default::default(); message.header.num_required_signatures as usize],
+        message: VersionedMessage::Legacy(message),
+    })
+}
+
+/// Reconstruct plain `Instruction`s (with resolved pubkeys) from a compiled
+/// `VersionedMessage`, so a fee instruction can be prepended and the message
+/// recompiled before signing. `pub(crate)` so `jito_client::attach_tip` can
+/// reuse it to append a tip instruction the same way.
+pub(crate) fn decompile(message: &VersionedMessage) -> Result<(Vec<Instruction>, Vec<Pubkey>, Pubkey)> {
+    let account_keys = message.static_account_keys().to_vec();
+    let payer = *account_keys
+        .first()
+        .ok_or_else(|| anyhow!("Transaction message has no account keys"))?;
+
+    let instructions = message
+        .instructions()
+        .iter()
+        .map(|compiled| Instruction {
+            program_id: account_keys[compiled.program_id_index as usize],
+            accounts: compiled
+                .accounts
+                .iter()
+                .map(|&index| AccountMeta {
+                    pubkey: account_keys[index as usize],
+                    is_signer: message.is_signer(index as usize),
+                    is_writable: message.is_maybe_writable(index as usize),
+                })
+                .collect(),
+            data: compiled.data.clone(),
+        })
+        .collect();
+
+    Ok((instructions, account_keys, payer))
 }
@@ -1,18 +1,38 @@
+mod candle_aggregator;
 mod config;
+mod confirmation_tracker;
+mod connectivity;
+mod control_api;
 mod database;
+mod execution_latency;
 mod executor;
+mod fills_server;
+mod metrics;
 mod risk_manager;
 mod jito_client;
 mod jupiter;
+mod price_oracle;
+mod priority_fee;
 mod signer_client;
+mod sol_price_oracle;
+mod sol_price_ws_feed;
 mod strategies;
+mod strategy_config;
+mod ws_server;
 
 use anyhow::{Context, Result};
+use candle_aggregator::CandleAggregator;
 use database::Database;
 use executor::MasterExecutor;
+use fills_server::FillsServer;
+use metrics::ExecutorMetrics;
+use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use tokio::sync::RwLock;
 use tracing::{error, info};
 use tracing_subscriber::EnvFilter;
+use ws_server::WsServer;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -36,12 +56,73 @@ async fn main() -> Result<()> {
     );
     info!("💾 Database initialized");
 
+    // Start the WS fan-out server so dashboards can subscribe to its
+    // price/social/positions/pnl channels. The price state and broadcast
+    // sender are shared with `MasterExecutor` below, which is the one
+    // actually consuming `events:price`/`events:social` and is therefore
+    // what keeps them current; it feeds only the price and social channels,
+    // and nothing publishes to positions/pnl.
+    let ws_bind_addr: SocketAddr = config::CONFIG
+        .ws_fanout_bind_addr
+        .parse()
+        .context("Invalid WS_FANOUT_BIND_ADDR")?;
+    let ws_sol_usd_price = Arc::new(RwLock::new(0.0));
+    let ws_last_prices = Arc::new(RwLock::new(HashMap::new()));
+    let ws_server = WsServer::new(
+        ws_bind_addr,
+        db.clone(),
+        ws_sol_usd_price.clone(),
+        ws_last_prices.clone(),
+    );
+    let ws_tx = ws_server.sender();
+    tokio::spawn(async move {
+        if let Err(e) = ws_server.run().await {
+            error!("WS fan-out server failed: {}", e);
+        }
+    });
+
+    // Start the fills fan-out server so dashboards can subscribe to a single
+    // strategy's shadow-ledger/live fills instead of polling Redis.
+    let fills_bind_addr: SocketAddr = config::CONFIG
+        .fills_ws_bind_addr
+        .parse()
+        .context("Invalid FILLS_WS_BIND_ADDR")?;
+    let fills_redis_client =
+        redis::Client::open(config::CONFIG.redis_url.clone()).context("Invalid Redis URL for fills server")?;
+    let fills_server = FillsServer::new(fills_bind_addr, fills_redis_client);
+    tokio::spawn(async move {
+        if let Err(e) = fills_server.run().await {
+            error!("Fills fan-out server failed: {}", e);
+        }
+    });
+
+    // Expose Prometheus metrics for the event and execution paths.
+    let executor_metrics = ExecutorMetrics::new().context("Failed to initialize executor metrics")?;
+    executor_metrics.spawn_percentile_publisher();
+    executor_metrics.spawn_server(&config::CONFIG.metrics_bind_addr);
+
+    // Start the OHLCV candle aggregator: backfills recent candles from the
+    // events:price stream history, then keeps building 1m/5m/15m/1h buckets live.
+    let candle_redis_client =
+        redis::Client::open(config::CONFIG.redis_url.clone()).context("Invalid Redis URL for candle aggregator")?;
+    let candle_aggregator = CandleAggregator::new(db.clone(), candle_redis_client);
+    tokio::spawn(async move {
+        if let Err(e) = candle_aggregator.run().await {
+            error!("Candle aggregator failed: {}", e);
+        }
+    });
+
     // Create master executor
-    let mut executor = MasterExecutor::new(db)
+    let mut executor = MasterExecutor::new(db, ws_tx, ws_sol_usd_price, ws_last_prices)
         .await
         .context("Failed to create master executor")?;
     info!("🎯 MasterExecutor initialized, starting event loop...");
 
+    // Start the control-plane HTTP API so an operator can list/stop/start
+    // strategies, inject a synthetic event, or read the current SOL/USD
+    // price without restarting the process.
+    control_api::spawn_server(&config::CONFIG.control_api_bind_addr, executor.control_handle());
+
     // Run the executor - this blocks forever
     if let Err(e) = executor.run().await {
         error!("💥 Executor failed: {}", e);
@@ -50,8 +131,3 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
-        return Err(e);
-    }
-
-    Ok(())
-}
@@ -0,0 +1,135 @@
+//! Prometheus metrics for the executor's event and execution paths. Mirrors the
+//! `/metrics` pattern already used by the market data gateway's
+//! `DataValidationMetrics`, plus HDR-backed latency histograms whose p50/p90/p99
+//! are periodically snapshotted into gauges so they're scrapeable.
+use anyhow::{Context, Result};
+use axum::{extract::State, http::StatusCode, response::Response, routing::get, Router};
+use prometheus::{CounterVec, Encoder, Gauge, Opts, Registry, TextEncoder};
+use shared_models::LatencyHistogram;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+#[derive(Clone)]
+pub struct ExecutorMetrics {
+    registry: Registry,
+    pub events_processed: CounterVec,
+    pub nav_usd: Gauge,
+    pub drawdown_pct: Gauge,
+    pub event_handling_latency: Arc<LatencyHistogram>,
+    pub jupiter_quote_latency: Arc<LatencyHistogram>,
+    event_handling_p50_ms: Gauge,
+    event_handling_p90_ms: Gauge,
+    event_handling_p99_ms: Gauge,
+    jupiter_quote_p50_ms: Gauge,
+    jupiter_quote_p90_ms: Gauge,
+    jupiter_quote_p99_ms: Gauge,
+}
+
+impl ExecutorMetrics {
+    pub fn new() -> Result<Self> {
+        let registry = Registry::new();
+
+        let events_processed = CounterVec::new(
+            Opts::new("executor_events_processed_total", "Events processed per Redis stream"),
+            &["stream"],
+        )
+        .context("Failed to create events_processed counter")?;
+        let nav_usd = Gauge::new("executor_portfolio_nav_usd", "Current portfolio NAV in USD")
+            .context("Failed to create nav_usd gauge")?;
+        let drawdown_pct = Gauge::new("executor_portfolio_drawdown_pct", "Current portfolio drawdown percentage")
+            .context("Failed to create drawdown_pct gauge")?;
+        let event_handling_p50_ms = Gauge::new("executor_event_handling_latency_p50_ms", "p50 end-to-end event handling latency")
+            .context("Failed to create event_handling_p50_ms gauge")?;
+        let event_handling_p90_ms = Gauge::new("executor_event_handling_latency_p90_ms", "p90 end-to-end event handling latency")
+            .context("Failed to create event_handling_p90_ms gauge")?;
+        let event_handling_p99_ms = Gauge::new("executor_event_handling_latency_p99_ms", "p99 end-to-end event handling latency")
+            .context("Failed to create event_handling_p99_ms gauge")?;
+        let jupiter_quote_p50_ms = Gauge::new("executor_jupiter_quote_latency_p50_ms", "p50 Jupiter quote round-trip latency")
+            .context("Failed to create jupiter_quote_p50_ms gauge")?;
+        let jupiter_quote_p90_ms = Gauge::new("executor_jupiter_quote_latency_p90_ms", "p90 Jupiter quote round-trip latency")
+            .context("Failed to create jupiter_quote_p90_ms gauge")?;
+        let jupiter_quote_p99_ms = Gauge::new("executor_jupiter_quote_latency_p99_ms", "p99 Jupiter quote round-trip latency")
+            .context("Failed to create jupiter_quote_p99_ms gauge")?;
+
+        registry.register(Box::new(events_processed.clone())).context("Failed to register events_processed")?;
+        registry.register(Box::new(nav_usd.clone())).context("Failed to register nav_usd")?;
+        registry.register(Box::new(drawdown_pct.clone())).context("Failed to register drawdown_pct")?;
+        registry.register(Box::new(event_handling_p50_ms.clone())).context("Failed to register event_handling_p50_ms")?;
+        registry.register(Box::new(event_handling_p90_ms.clone())).context("Failed to register event_handling_p90_ms")?;
+        registry.register(Box::new(event_handling_p99_ms.clone())).context("Failed to register event_handling_p99_ms")?;
+        registry.register(Box::new(jupiter_quote_p50_ms.clone())).context("Failed to register jupiter_quote_p50_ms")?;
+        registry.register(Box::new(jupiter_quote_p90_ms.clone())).context("Failed to register jupiter_quote_p90_ms")?;
+        registry.register(Box::new(jupiter_quote_p99_ms.clone())).context("Failed to register jupiter_quote_p99_ms")?;
+
+        Ok(Self {
+            registry,
+            events_processed,
+            nav_usd,
+            drawdown_pct,
+            event_handling_latency: Arc::new(LatencyHistogram::new()),
+            jupiter_quote_latency: Arc::new(LatencyHistogram::new()),
+            event_handling_p50_ms,
+            event_handling_p90_ms,
+            event_handling_p99_ms,
+            jupiter_quote_p50_ms,
+            jupiter_quote_p90_ms,
+            jupiter_quote_p99_ms,
+        })
+    }
+
+    /// Periodically flush the HDR histograms' percentiles into scrapeable gauges.
+    pub fn spawn_percentile_publisher(&self) {
+        let metrics = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(10));
+            loop {
+                interval.tick().await;
+
+                let (p50, p90, p99) = metrics.event_handling_latency.percentiles();
+                metrics.event_handling_p50_ms.set(p50);
+                metrics.event_handling_p90_ms.set(p90);
+                metrics.event_handling_p99_ms.set(p99);
+
+                let (p50, p90, p99) = metrics.jupiter_quote_latency.percentiles();
+                metrics.jupiter_quote_p50_ms.set(p50);
+                metrics.jupiter_quote_p90_ms.set(p90);
+                metrics.jupiter_quote_p99_ms.set(p99);
+            }
+        });
+    }
+
+    pub fn spawn_server(&self, bind_addr: &str) {
+        let metrics = self.clone();
+        let bind_addr = bind_addr.to_string();
+        tokio::spawn(async move {
+            let app = Router::new().route("/metrics", get(metrics_handler)).with_state(metrics);
+            match tokio::net::TcpListener::bind(&bind_addr).await {
+                Ok(listener) => {
+                    info!("📊 Executor metrics server listening on {}", bind_addr);
+                    if let Err(e) = axum::serve(listener, app).await {
+                        error!("Executor metrics server failed: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to bind executor metrics server to {}: {}", bind_addr, e),
+            }
+        });
+    }
+}
+
+async fn metrics_handler(State(metrics): State<ExecutorMetrics>) -> Result<Response<String>, StatusCode> {
+    let encoder = TextEncoder::new();
+    let metric_families = metrics.registry.gather();
+    let mut buffer = Vec::new();
+
+    encoder
+        .encode(&metric_families, &mut buffer)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let body = String::from_utf8(buffer).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Response::builder()
+        .header("content-type", "text/plain; version=0.0.4")
+        .body(body)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
@@ -0,0 +1,143 @@
+//! Pluggable price-source abstraction with priority-ordered fallback.
+//!
+//! `execute_trade` used to depend on a single `SolPriceOracle` call for
+//! sizing; if that one source were stale or down the trade just failed. A
+//! `LatestRate` implementor can be anything — Jupiter, an on-chain oracle, an
+//! external CEX-ticker bridge — and a `RateAggregator` tries them in priority
+//! order, only rejecting once every known source has failed or exceeded
+//! `max_staleness`, so the fill can record which source actually priced it.
+use anyhow::{anyhow, Result};
+use redis::AsyncCommands;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// SOL/USD mint, matching `jupiter::get_quote`'s hardcoded input mint and
+/// `sol_price_oracle`'s feed.
+pub const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// One source's view of a token's USD price, tagged with where it came from
+/// and when it was taken so callers can judge staleness without re-querying.
+#[derive(Debug, Clone, Copy)]
+pub struct Rate {
+    pub price_usd: f64,
+    pub source: &'static str,
+    pub fetched_at: Instant,
+}
+
+impl Rate {
+    fn is_stale(&self, max_age: Duration) -> bool {
+        self.fetched_at.elapsed() > max_age
+    }
+}
+
+#[async_trait::async_trait]
+pub trait LatestRate: Send + Sync {
+    fn source_name(&self) -> &'static str;
+    async fn latest_rate(&self, token_address: &str) -> Result<Rate>;
+}
+
+/// Queries its sources in priority order, returning the first rate that's
+/// both available and within `max_staleness`. Only errors once every source
+/// has been exhausted, instead of failing as soon as the first one is down.
+pub struct RateAggregator {
+    sources: Vec<Arc<dyn LatestRate>>,
+    max_staleness: Duration,
+}
+
+impl RateAggregator {
+    pub fn new(sources: Vec<Arc<dyn LatestRate>>, max_staleness: Duration) -> Self {
+        Self { sources, max_staleness }
+    }
+
+    pub async fn latest_rate(&self, token_address: &str) -> Result<Rate> {
+        for source in &self.sources {
+            match source.latest_rate(token_address).await {
+                Ok(rate) if !rate.is_stale(self.max_staleness) => return Ok(rate),
+                Ok(rate) => warn!(
+                    source = source.source_name(),
+                    age_secs = rate.fetched_at.elapsed().as_secs(),
+                    max_staleness_secs = self.max_staleness.as_secs(),
+                    "Price source stale, trying next"
+                ),
+                Err(e) => warn!(source = source.source_name(), error = %e, "Price source unavailable, trying next"),
+            }
+        }
+        Err(anyhow!(
+            "All {} price sources are unavailable or exceed max staleness ({:?}) for {}",
+            self.sources.len(),
+            self.max_staleness,
+            token_address
+        ))
+    }
+}
+
+/// Wraps the existing Jupiter-then-Pyth-backed `SolPriceOracle`. Only
+/// meaningful for `SOL_MINT`, since that's the only mint it tracks.
+pub struct SolOracleRate(pub Arc<crate::sol_price_oracle::SolPriceOracle>);
+
+#[async_trait::async_trait]
+impl LatestRate for SolOracleRate {
+    fn source_name(&self) -> &'static str {
+        "sol_price_oracle"
+    }
+
+    async fn latest_rate(&self, token_address: &str) -> Result<Rate> {
+        if token_address != SOL_MINT {
+            return Err(anyhow!("sol_price_oracle only prices {}", SOL_MINT));
+        }
+        // current_sol_price() already enforces its own staleness_ttl, so a
+        // successful return is fresh as of now.
+        let price_usd = self.0.current_sol_price().await?;
+        Ok(Rate {
+            price_usd,
+            source: self.source_name(),
+            fetched_at: Instant::now(),
+        })
+    }
+}
+
+/// Jupiter's `/price` endpoint, usable for any mint (not just SOL).
+pub struct JupiterRate(pub Arc<crate::jupiter::JupiterClient>);
+
+#[async_trait::async_trait]
+impl LatestRate for JupiterRate {
+    fn source_name(&self) -> &'static str {
+        "jupiter"
+    }
+
+    async fn latest_rate(&self, token_address: &str) -> Result<Rate> {
+        let price_usd = self.0.get_price(token_address).await?;
+        Ok(Rate {
+            price_usd,
+            source: self.source_name(),
+            fetched_at: Instant::now(),
+        })
+    }
+}
+
+/// An out-of-process reference price (e.g. a CEX ticker bridge) published to
+/// `price_reference:{token_address}` in Redis. Lowest priority: nothing ships
+/// a writer for that key yet, so a missing key is just "source unavailable"
+/// rather than an error, and the aggregator falls through to rejecting the
+/// trade only if Jupiter/the SOL oracle are also down.
+pub struct ExternalReferenceRate(pub redis::Client);
+
+#[async_trait::async_trait]
+impl LatestRate for ExternalReferenceRate {
+    fn source_name(&self) -> &'static str {
+        "external_reference"
+    }
+
+    async fn latest_rate(&self, token_address: &str) -> Result<Rate> {
+        let mut conn = self.0.get_async_connection().await?;
+        let price_usd: Option<f64> = conn.get(format!("price_reference:{}", token_address)).await?;
+        let price_usd = price_usd
+            .ok_or_else(|| anyhow!("No external reference price cached for {}", token_address))?;
+        Ok(Rate {
+            price_usd,
+            source: self.source_name(),
+            fetched_at: Instant::now(),
+        })
+    }
+}
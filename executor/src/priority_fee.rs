@@ -0,0 +1,144 @@
+//! Dynamic priority-fee estimation for swap transactions.
+//!
+//! Jupiter-built swaps land on-chain via ordinary fee-market competition, so
+//! during congestion a fire-and-forget swap with only slippage control can
+//! silently fail to land. `PriorityFeeEstimator` samples recent prioritization
+//! fees for the accounts a swap touches and turns them into a compute-unit
+//! price a caller can prepend to the transaction before signing.
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_response::RpcPrioritizationFee;
+use solana_sdk::pubkey::Pubkey;
+use tracing::{debug, warn};
+
+/// Assumed compute-unit budget for a single Jupiter swap, used to translate a
+/// micro-lamports-per-CU price into an estimated total fee for the USD cap.
+/// Jupiter swaps rarely exceed this; strategies that need a tighter bound
+/// should request a lower `FeeStrategy` instead.
+const ASSUMED_COMPUTE_UNITS: u64 = 200_000;
+
+/// How aggressively to price the swap relative to recent network activity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeStrategy {
+    Median,
+    P75,
+    P90,
+    P95,
+}
+
+impl FeeStrategy {
+    fn pick(self, estimate: &FeeEstimate) -> u64 {
+        match self {
+            FeeStrategy::Median => estimate.median,
+            FeeStrategy::P75 => estimate.p75,
+            FeeStrategy::P90 => estimate.p90,
+            FeeStrategy::P95 => estimate.p95,
+        }
+    }
+}
+
+/// Percentile summary of recent prioritization fees, in micro-lamports per CU.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FeeEstimate {
+    pub min: u64,
+    pub median: u64,
+    pub p75: u64,
+    pub p90: u64,
+    pub p95: u64,
+    pub max: u64,
+}
+
+fn percentile(sorted: &[u64], pct: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let idx = ((sorted.len() - 1) as f64 * pct).round() as usize;
+    sorted[idx.min(sorted.len() - 1)]
+}
+
+fn summarize(fees: &[u64]) -> FeeEstimate {
+    let mut sorted = fees.to_vec();
+    sorted.sort_unstable();
+
+    FeeEstimate {
+        min: *sorted.first().unwrap_or(&0),
+        median: percentile(&sorted, 0.50),
+        p75: percentile(&sorted, 0.75),
+        p90: percentile(&sorted, 0.90),
+        p95: percentile(&sorted, 0.95),
+        max: *sorted.last().unwrap_or(&0),
+    }
+}
+
+pub struct PriorityFeeEstimator {
+    rpc_client: RpcClient,
+    /// Minimum for every estimate, and so the value used when the RPC returns
+    /// no samples (e.g. a quiet network or an RPC that doesn't support the
+    /// method).
+    floor_micro_lamports: u64,
+    /// Caps the estimated total fee so a fee spike can't dwarf a small order.
+    max_fee_usd: f64,
+}
+
+impl PriorityFeeEstimator {
+    pub fn new(rpc_url: &str, floor_micro_lamports: u64, max_fee_usd: f64) -> Self {
+        Self {
+            rpc_client: RpcClient::new(rpc_url.to_string()),
+            floor_micro_lamports,
+            max_fee_usd,
+        }
+    }
+
+    /// Sample recent prioritization fees for the given writable accounts and
+    /// return a compute-unit price (micro-lamports per CU) for `strategy`,
+    /// floored and then capped so the estimated total fee stays under
+    /// `max_fee_usd` at the current SOL price.
+    pub async fn estimate_micro_lamports(
+        &self,
+        writable_accounts: &[Pubkey],
+        strategy: FeeStrategy,
+        sol_usd_price: f64,
+    ) -> u64 {
+        let samples = match self.rpc_client.get_recent_prioritization_fees(writable_accounts).await {
+            Ok(fees) => fees,
+            Err(e) => {
+                warn!("Failed to fetch recent prioritization fees, using floor: {}", e);
+                Vec::new()
+            }
+        };
+
+        let fees: Vec<u64> = samples
+            .iter()
+            .map(|RpcPrioritizationFee { prioritization_fee, .. }| *prioritization_fee)
+            .collect();
+
+        let chosen = if fees.is_empty() {
+            self.floor_micro_lamports
+        } else {
+            summarize(&fees).pick(strategy).max(self.floor_micro_lamports)
+        };
+
+        let capped = self.cap_by_max_fee_usd(chosen, sol_usd_price);
+        debug!(
+            chosen_micro_lamports = chosen,
+            capped_micro_lamports = capped,
+            "Estimated priority fee"
+        );
+        capped
+    }
+
+    fn cap_by_max_fee_usd(&self, micro_lamports_per_cu: u64, sol_usd_price: f64) -> u64 {
+        if sol_usd_price <= 0.0 || self.max_fee_usd <= 0.0 {
+            return micro_lamports_per_cu;
+        }
+
+        let fee_lamports = (micro_lamports_per_cu as f64 * ASSUMED_COMPUTE_UNITS as f64) / 1_000_000.0;
+        let fee_usd = (fee_lamports / 1_000_000_000.0) * sol_usd_price;
+
+        if fee_usd <= self.max_fee_usd {
+            return micro_lamports_per_cu;
+        }
+
+        let max_fee_lamports = (self.max_fee_usd / sol_usd_price) * 1_000_000_000.0;
+        ((max_fee_lamports * 1_000_000.0) / ASSUMED_COMPUTE_UNITS as f64) as u64
+    }
+}
@@ -1,5 +1,6 @@
 use crate::config::Config;
 use crate::database::Database;
+use crate::metrics::ExecutorMetrics;
 use anyhow::Result;
 use shared_models::CircuitBreaker;
 use std::sync::Arc;
@@ -11,6 +12,7 @@ pub struct RiskManager {
     config: Arc<Config>,
     db: Arc<Database>,
     circuit_breaker: Arc<CircuitBreaker>,
+    metrics: Option<ExecutorMetrics>,
 }
 
 impl RiskManager {
@@ -19,9 +21,17 @@ impl RiskManager {
             config,
             db,
             circuit_breaker: Arc::new(CircuitBreaker::new()),
+            metrics: None,
         }
     }
 
+    /// Attach the executor's Prometheus metrics so NAV and drawdown are
+    /// published as gauges alongside the existing debug logging.
+    pub fn with_metrics(mut self, metrics: ExecutorMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     pub fn get_circuit_breaker(&self) -> Arc<CircuitBreaker> {
         self.circuit_breaker.clone()
     }
@@ -30,19 +40,21 @@ impl RiskManager {
         let config = self.config.clone();
         let db = self.db.clone();
         let circuit_breaker = self.circuit_breaker.clone();
+        let metrics = self.metrics.clone();
 
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_secs(30));
-            
+
             info!("🛡️  Risk manager started, monitoring portfolio health every 30s");
-            
+
             loop {
                 interval.tick().await;
-                
+
                 if let Err(e) = Self::check_portfolio_health(
                     &config,
                     &db,
                     &circuit_breaker,
+                    metrics.as_ref(),
                 ).await {
                     error!("Portfolio health check failed: {}", e);
                 }
@@ -54,26 +66,40 @@ impl RiskManager {
         config: &Config,
         db: &Database,
         circuit_breaker: &CircuitBreaker,
+        metrics: Option<&ExecutorMetrics>,
     ) -> Result<()> {
         let total_pnl = db.get_total_realized_pnl()?;
         let initial_capital = config.initial_capital_usd;
-        
-        let current_nav = initial_capital + total_pnl;
+
+        // Unrealized PnL for open positions isn't marked-to-market on this
+        // path yet, so NAV snapshots only reflect realized PnL for now.
+        let unrealized_pnl = 0.0;
+        let current_nav = initial_capital + total_pnl + unrealized_pnl;
+
+        if let Err(e) = db.record_nav_snapshot(current_nav, total_pnl, unrealized_pnl) {
+            error!("Failed to record NAV snapshot: {}", e);
+        }
+
         let max_nav = db.get_max_nav(initial_capital).unwrap_or(initial_capital);
-        
+
         let drawdown_pct = if max_nav > 0.0 {
             ((max_nav - current_nav) / max_nav) * 100.0
         } else {
             0.0
         };
-        
+
         let risk_level = circuit_breaker.update_drawdown(drawdown_pct);
-        
+
+        if let Some(metrics) = metrics {
+            metrics.nav_usd.set(current_nav);
+            metrics.drawdown_pct.set(drawdown_pct);
+        }
+
         debug!(
             "📊 Portfolio health: NAV=${:.2}, Drawdown={:.2}%, Risk={:?}",
             current_nav, drawdown_pct, risk_level
         );
-        
+
         Ok(())
     }
 }
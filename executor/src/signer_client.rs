@@ -1,29 +1,132 @@
+//! Long-lived client for the remote signer, following the same
+//! periodic-probe-and-cache approach `SolPriceOracle` uses for its upstream:
+//! a reused `reqwest::Client` instead of one per call, a background task that
+//! keeps `is_healthy()` current, and bounded retries with backoff so a
+//! momentarily unreachable signer doesn't abort a trade outright.
 use anyhow::{anyhow, Result};
 use reqwest::Client;
 use shared_models::{SignRequest, SignResponse};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
+use tracing::{debug, warn};
 
-pub async fn get_pubkey(signer_url: &str) -> Result<String> {
-    let client = Client::new();
-    let url = format!("{}/pubkey", signer_url);
-    let response = client.get(&url).timeout(Duration::from_secs(5)).send().await?
-        .json::<serde_json::Value>().await?;
-    
-    response["pubkey"].as_str()
-        .map(|s| s.to_string())
-        .ok_or_else(|| anyhow!("Pubkey not found in signer response"))
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+const MAX_RETRIES: u32 = 3;
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_millis(250);
+
+pub struct SignerClient {
+    http: Client,
+    base_url: String,
+    healthy: AtomicBool,
 }
 
-pub async fn sign_transaction(signer_url: &str, tx_b64: &str) -> Result<String> {
-    let client = Client::new();
-    let url = format!("{}/sign", signer_url);
-    let request = SignRequest { transaction_b64: tx_b64.to_string() };
-    
-    let response: SignResponse = client.post(&url)
-        .json(&request)
-        .timeout(Duration::from_secs(5))
-        .send().await?
-        .json().await?;
-    
-    Ok(response.signed_transaction_b64)
+impl SignerClient {
+    pub fn new(signer_url: String) -> Self {
+        Self {
+            http: Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("Failed to build HTTP client for signer"),
+            base_url: signer_url,
+            // Assume unhealthy until the first probe lands, so callers don't
+            // race a Live trade against a signer that's never been reached.
+            healthy: AtomicBool::new(false),
+        }
+    }
+
+    /// Probes `/pubkey` once immediately, then spawns a background task that
+    /// re-probes every `HEALTH_CHECK_INTERVAL`. `self` must be wrapped in an
+    /// `Arc` so the spawned task can outlive the caller.
+    pub fn spawn_health_check(self: &Arc<Self>) {
+        let client = self.clone();
+        tokio::spawn(async move {
+            client.probe_once().await;
+
+            let mut interval = tokio::time::interval(HEALTH_CHECK_INTERVAL);
+            interval.tick().await; // first tick fires immediately; we already probed above
+            loop {
+                interval.tick().await;
+                client.probe_once().await;
+            }
+        });
+    }
+
+    async fn probe_once(&self) {
+        match self.fetch_pubkey().await {
+            Ok(_) => {
+                self.healthy.store(true, Ordering::Relaxed);
+                debug!("Signer health check passed");
+            }
+            Err(e) => {
+                self.healthy.store(false, Ordering::Relaxed);
+                warn!("Signer health check failed: {}", e);
+            }
+        }
+    }
+
+    /// Whether the last health check reached the signer. `execute_trade`
+    /// checks this before entering `TradeMode::Live` so a down signer is
+    /// refused up front instead of failing mid-trade after a quote and a
+    /// risk check already ran.
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    pub async fn get_pubkey(&self) -> Result<String> {
+        with_retries(|| self.fetch_pubkey()).await
+    }
+
+    pub async fn sign_transaction(&self, tx_b64: &str) -> Result<String> {
+        with_retries(|| self.post_sign(tx_b64)).await
+    }
+
+    async fn fetch_pubkey(&self) -> Result<String> {
+        let url = format!("{}/pubkey", self.base_url);
+        let response = self.http.get(&url).send().await?.json::<serde_json::Value>().await?;
+
+        response["pubkey"]
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| anyhow!("Pubkey not found in signer response"))
+    }
+
+    async fn post_sign(&self, tx_b64: &str) -> Result<String> {
+        let url = format!("{}/sign", self.base_url);
+        let request = SignRequest { transaction_b64: tx_b64.to_string(), partial: false };
+
+        let response: SignResponse = self.http.post(&url).json(&request).send().await?.json().await?;
+        Ok(response.signed_transaction_b64)
+    }
+}
+
+/// Calls `f` up to `MAX_RETRIES` times in total with exponential backoff, for the
+/// transient failures (timeouts, connection resets) a momentarily unreachable
+/// signer produces; a persistent failure still surfaces as an error after the
+/// last attempt rather than hanging indefinitely.
+async fn with_retries<T, F, Fut>(mut f: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut backoff = INITIAL_RETRY_BACKOFF;
+    let mut last_err = None;
+
+    for attempt in 1..=MAX_RETRIES {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                warn!(attempt, error = %e, "Signer call failed, retrying");
+                last_err = Some(e);
+                if attempt < MAX_RETRIES {
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+            }
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| anyhow!("Signer call failed with no recorded error")))
 }
@@ -0,0 +1,151 @@
+//! Background-refreshed SOL/USD price oracle.
+//!
+//! `JupiterClient` needs an accurate SOL/USD price to turn
+//! `OrderDetails.suggested_size_usd` into lamports; a hardcoded guess silently
+//! mis-sizes every order whenever SOL moves away from it. `SolPriceOracle`
+//! polls Jupiter's price endpoint on a timer, falling back to Pyth/Hermes if
+//! Jupiter is unavailable, and caches the latest quote behind an `RwLock` so
+//! callers never block on a network round-trip.
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+/// Pyth/Hermes SOL/USD feed id, same one `market_data_gateway`'s `pyth_consumer` subscribes to.
+const SOL_USD_PYTH_FEED_ID: &str = "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56";
+
+#[derive(Debug, Clone, Copy)]
+struct Quote {
+    price_usd: f64,
+    fetched_at: std::time::Instant,
+}
+
+pub struct SolPriceOracle {
+    client: Client,
+    jupiter_api_url: String,
+    pyth_hermes_url: String,
+    refresh_interval: Duration,
+    staleness_ttl: Duration,
+    latest: RwLock<Option<Quote>>,
+}
+
+impl SolPriceOracle {
+    pub fn new(
+        jupiter_api_url: String,
+        pyth_hermes_url: String,
+        refresh_interval: Duration,
+        staleness_ttl: Duration,
+    ) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .expect("Failed to build HTTP client for SOL price oracle"),
+            jupiter_api_url,
+            pyth_hermes_url,
+            refresh_interval,
+            staleness_ttl,
+            latest: RwLock::new(None),
+        }
+    }
+
+    /// Fetch once immediately, then spawn a background task that refreshes
+    /// every `refresh_interval`. `self` must be wrapped in an `Arc` so the
+    /// spawned task can outlive the caller.
+    pub fn spawn_refresh(self: &Arc<Self>) {
+        let oracle = self.clone();
+        tokio::spawn(async move {
+            oracle.refresh_once().await;
+
+            let mut interval = tokio::time::interval(oracle.refresh_interval);
+            interval.tick().await; // first tick fires immediately; we already refreshed above
+            loop {
+                interval.tick().await;
+                oracle.refresh_once().await;
+            }
+        });
+    }
+
+    async fn refresh_once(&self) {
+        match self.fetch_jupiter().await {
+            Ok(price_usd) => {
+                self.store(price_usd).await;
+                return;
+            }
+            Err(e) => warn!("Jupiter SOL/USD price fetch failed, falling back to Pyth/Hermes: {}", e),
+        }
+
+        match self.fetch_pyth().await {
+            Ok(price_usd) => self.store(price_usd).await,
+            Err(e) => warn!("Pyth/Hermes SOL/USD price fetch also failed, keeping last known price: {}", e),
+        }
+    }
+
+    async fn fetch_jupiter(&self) -> Result<f64> {
+        let url = format!("{}/price?ids={}", self.jupiter_api_url, SOL_MINT);
+        let response: serde_json::Value = self.client.get(&url).send().await?.json().await?;
+
+        response
+            .get("data")
+            .and_then(|d| d.get(SOL_MINT))
+            .and_then(|p| p.get("price"))
+            .and_then(|p| p.as_f64())
+            .ok_or_else(|| anyhow!("Jupiter price response missing SOL/USD price"))
+    }
+
+    async fn fetch_pyth(&self) -> Result<f64> {
+        let mut url = reqwest::Url::parse(&format!("{}/v2/updates/price/latest", self.pyth_hermes_url))?;
+        url.query_pairs_mut().append_pair("ids[]", SOL_USD_PYTH_FEED_ID);
+
+        let response: HermesLatestResponse = self.client.get(url).send().await?.json().await?;
+        let parsed = response
+            .parsed
+            .first()
+            .ok_or_else(|| anyhow!("Pyth/Hermes response had no parsed prices"))?;
+
+        let raw: i64 = parsed.price.price.parse()?;
+        Ok(raw as f64 * 10f64.powi(parsed.price.expo))
+    }
+
+    async fn store(&self, price_usd: f64) {
+        debug!(price_usd, "Refreshed SOL/USD price");
+        *self.latest.write().await = Some(Quote {
+            price_usd,
+            fetched_at: std::time::Instant::now(),
+        });
+    }
+
+    /// Returns the cached SOL/USD price, erroring if no quote has landed yet
+    /// or the last good one is older than `staleness_ttl`.
+    pub async fn current_sol_price(&self) -> Result<f64> {
+        match &*self.latest.read().await {
+            Some(quote) if quote.fetched_at.elapsed() <= self.staleness_ttl => Ok(quote.price_usd),
+            Some(quote) => Err(anyhow!(
+                "SOL/USD price is stale ({:?} old, ttl {:?})",
+                quote.fetched_at.elapsed(),
+                self.staleness_ttl
+            )),
+            None => Err(anyhow!("SOL/USD price not available yet")),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HermesLatestResponse {
+    parsed: Vec<HermesParsedPrice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HermesParsedPrice {
+    price: HermesPrice,
+}
+
+#[derive(Debug, Deserialize)]
+struct HermesPrice {
+    price: String,
+    expo: i32,
+}
@@ -0,0 +1,128 @@
+//! Persistent WebSocket subscriber for a live SOL/USD tick feed, added as the
+//! freshest entry in `RateAggregator`'s priority list. Mirrors
+//! `market_data_gateway`'s `StreamingProvider` reconnect idiom: on `Close` or
+//! a transport error the task backs off and reconnects rather than
+//! terminating, since a dropped socket must not leave the cached price frozen.
+use crate::price_oracle::{LatestRate, Rate, SOL_MINT};
+use anyhow::{anyhow, Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, error, info, warn};
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// How old the cached tick may be before `is_connected` reports the feed as
+/// down, even if the socket itself hasn't errored yet (e.g. upstream stopped
+/// publishing without closing the connection).
+const HEALTH_STALENESS: Duration = Duration::from_secs(60);
+
+/// One incremental frame from the upstream feed. Heartbeat/system-status
+/// frames don't carry a `price` field and fall into `Other` via
+/// `#[serde(other)]` instead of failing the parse.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum TickFrame {
+    Ticker { price: f64 },
+    #[serde(other)]
+    Other,
+}
+
+/// Caches the freshest SOL/USD tick behind an `RwLock`, kept current by a
+/// background reconnect-on-drop task; `latest_rate` just reads the cache.
+pub struct SolPriceWsFeed {
+    ws_url: String,
+    subscribe_frame: String,
+    latest: RwLock<Option<(f64, Instant)>>,
+}
+
+impl SolPriceWsFeed {
+    pub fn new(ws_url: String, subscribe_frame: String) -> Self {
+        Self { ws_url, subscribe_frame, latest: RwLock::new(None) }
+    }
+
+    /// `self` must be wrapped in an `Arc` so the spawned task can outlive the
+    /// caller, same as `SolPriceOracle::spawn_refresh`.
+    pub fn spawn(self: &Arc<Self>) {
+        let feed = self.clone();
+        tokio::spawn(async move { feed.run().await });
+    }
+
+    /// Whether a tick has landed recently enough to trust the socket is still
+    /// alive, so `connectivity`'s watchdog can report this feed's health
+    /// without needing its own separate liveness signal.
+    pub async fn is_connected(&self) -> bool {
+        matches!(*self.latest.read().await, Some((_, fetched_at)) if fetched_at.elapsed() < HEALTH_STALENESS)
+    }
+
+    async fn run(self: Arc<Self>) {
+        let mut backoff = INITIAL_BACKOFF;
+        loop {
+            match self.connect_and_stream().await {
+                Ok(()) => warn!("SOL/USD WS feed closed cleanly, reconnecting"),
+                Err(e) => error!("SOL/USD WS feed error: {}, reconnecting in {:?}", e, backoff),
+            }
+
+            let jitter = Duration::from_millis(rand::random::<u64>() % 250);
+            tokio::time::sleep(backoff + jitter).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+
+    async fn connect_and_stream(&self) -> Result<()> {
+        let (ws_stream, _) = connect_async(&self.ws_url)
+            .await
+            .with_context(|| format!("Failed to connect to SOL/USD WS feed at {}", self.ws_url))?;
+        info!(url = %self.ws_url, "Connected to SOL/USD WS feed");
+
+        let (mut write, mut read) = ws_stream.split();
+        write
+            .send(Message::Text(self.subscribe_frame.clone()))
+            .await
+            .context("Failed to send subscribe frame")?;
+
+        while let Some(frame) = read.next().await {
+            match frame.context("WebSocket read error")? {
+                Message::Text(text) => self.handle_text(&text).await,
+                Message::Close(_) => return Ok(()),
+                // Ping/Pong/Binary/raw Frame carry no tick data; tungstenite
+                // already answers pings automatically.
+                _ => {}
+            }
+        }
+        Ok(()) // socket closed cleanly
+    }
+
+    async fn handle_text(&self, text: &str) {
+        match serde_json::from_str::<TickFrame>(text) {
+            Ok(TickFrame::Ticker { price }) if price > 0.0 => {
+                *self.latest.write().await = Some((price, Instant::now()));
+            }
+            Ok(_) => debug!("Ignoring non-ticker SOL/USD WS frame"),
+            Err(e) => debug!(error = %e, "Failed to parse SOL/USD WS frame, ignoring"),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl LatestRate for SolPriceWsFeed {
+    fn source_name(&self) -> &'static str {
+        "sol_ws_feed"
+    }
+
+    async fn latest_rate(&self, token_address: &str) -> Result<Rate> {
+        if token_address != SOL_MINT {
+            return Err(anyhow!("sol_ws_feed only prices {}", SOL_MINT));
+        }
+        let (price_usd, fetched_at) = self
+            .latest
+            .read()
+            .await
+            .ok_or_else(|| anyhow!("No SOL/USD tick received yet from WS feed"))?;
+        Ok(Rate { price_usd, source: self.source_name(), fetched_at })
+    }
+}
@@ -1,11 +1,11 @@
-use crate::{register_strategy, strategies::{Strategy, MarketEvent, StrategyAction, OrderDetails, EventType}};
+use crate::{register_strategy, strategies::{Strategy, MarketEvent, StrategyAction, OrderDetails, OrderType, EventType}};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::Value;
 use std::collections::{HashSet, HashMap};
 use tracing::info;
-use shared_models::Side;
+use shared_models::{Side, Usd};
 
 #[derive(Default, Deserialize)]
 pub struct AirdropRotation {
@@ -36,9 +36,10 @@ impl Strategy for AirdropRotation {
                         info!(id = self.id(), token = %on_chain.token_address, "BUY signal: Detected airdrop with {} new holders.", delta);
                         return Ok(StrategyAction::Execute(OrderDetails {
                             token_address: on_chain.token_address.clone(),
-                            suggested_size_usd: 600.0,
+                            suggested_size_usd: Usd::from_f64(600.0),
                             confidence: 0.7,
                             side: Side::Long,
+                            order_type: OrderType::Market,
                         }));
                     }
                 }
@@ -52,9 +53,10 @@ impl Strategy for AirdropRotation {
                     info!(id = self.id(), token = %mention.token_address, "BUY signal: Simulated airdrop detected with {} new holders.", new_holders_simulated);
                     return Ok(StrategyAction::Execute(OrderDetails {
                         token_address: mention.token_address.clone(),
-                        suggested_size_usd: 600.0,
+                        suggested_size_usd: Usd::from_f64(600.0),
                         confidence: 0.7,
                         side: Side::Long,
+                        order_type: OrderType::Market,
                     }));
                 }
             }
@@ -1,11 +1,11 @@
-use crate::{register_strategy, strategies::{Strategy, MarketEvent, StrategyAction, OrderDetails, EventType}};
+use crate::{register_strategy, strategies::{Strategy, MarketEvent, StrategyAction, OrderDetails, OrderType, EventType}};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::Value;
 use std::collections::HashSet;
 use tracing::info;
-use shared_models::Side;
+use shared_models::{Side, Usd};
 
 #[derive(Default, Deserialize)]
 pub struct DevWalletDrain {
@@ -36,9 +36,10 @@ impl Strategy for DevWalletDrain {
                         info!(id = self.id(), token = %on_chain.token_address, "SHORT signal: Dev wallet dump detected ({:.1}% transferred).", transfer_pct);
                         return Ok(StrategyAction::Execute(OrderDetails {
                             token_address: on_chain.token_address.clone(),
-                            suggested_size_usd: 1200.0,
+                            suggested_size_usd: Usd::from_f64(1200.0),
                             confidence: 0.85,
                             side: Side::Short,
+                            order_type: OrderType::Market,
                         }));
                     }
                 }
@@ -47,9 +48,10 @@ impl Strategy for DevWalletDrain {
                 info!(id = self.id(), token = %tick.token_address, "SHORT signal: Possible dev wallet dump detected (simulated).");
                 return Ok(StrategyAction::Execute(OrderDetails {
                     token_address: tick.token_address.clone(),
-                    suggested_size_usd: 1200.0,
+                    suggested_size_usd: Usd::from_f64(1200.0),
                     confidence: 0.85,
                     side: Side::Short,
+                    order_type: OrderType::Market,
                 }));
             }
             _ => {}
@@ -1,4 +1,4 @@
-use crate::strategies::{Strategy, MarketEvent, StrategyAction, OrderDetails, EventType};
+use crate::strategies::{Strategy, MarketEvent, StrategyAction, OrderDetails, OrderType, EventType};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::Deserialize;
@@ -6,7 +6,7 @@ use serde_json::Value;
 use std::collections::HashSet;
 use tracing::info;
 use chrono::{Timelike, Utc};
-use shared_models::Side;
+use shared_models::{Side, Usd};
 
 #[derive(Default, Deserialize)]
 pub struct KoreanTimeBurst {
@@ -48,9 +48,10 @@ impl Strategy for KoreanTimeBurst {
                     self.active_burst_tokens.insert(tick.token_address.clone());
                     return Ok(StrategyAction::Execute(OrderDetails {
                         token_address: tick.token_address.clone(),
-                        suggested_size_usd: 650.0,
+                        suggested_size_usd: Usd::from_f64(650.0),
                         confidence: 0.7,
                         side: Side::Long,
+                        order_type: OrderType::Market,
                     }));
                 }
             } else if !is_korean_trading_hour {
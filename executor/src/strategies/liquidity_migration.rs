@@ -1,15 +1,15 @@
-use crate::{register_strategy, strategies::{Strategy, MarketEvent, StrategyAction, OrderDetails, EventType}};
+use crate::{config::CONFIG, register_strategy, strategies::{Strategy, MarketEvent, StrategyAction, OrderDetails, OrderType, EventType}};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::Value;
 use std::collections::HashSet;
 use tracing::info;
-use shared_models::Side;
+use shared_models::{Side, Usd};
 
 #[derive(Default, Deserialize)]
 struct LiquidityMigration {
-    min_volume_migrate_usd: f64,
+    min_volume_migrate_usd: Usd,
     #[serde(skip)] migrated_tokens: HashSet<String>,
 }
 
@@ -23,7 +23,7 @@ impl Strategy for LiquidityMigration {
     async fn init(&mut self, params: &Value) -> Result<()> {
         #[derive(Deserialize)] struct P { min_volume_migrate_usd: f64 }
         let p: P = serde_json::from_value(params.clone())?;
-        self.min_volume_migrate_usd = p.min_volume_migrate_usd;
+        self.min_volume_migrate_usd = Usd::from_f64(p.min_volume_migrate_usd);
         info!(strategy = self.id(), "Initialized with min_volume_migrate_usd: {}", self.min_volume_migrate_usd);
         Ok(())
     }
@@ -31,7 +31,7 @@ impl Strategy for LiquidityMigration {
     async fn on_event(&mut self, event: &MarketEvent) -> Result<StrategyAction> {
         match event {
             MarketEvent::Bridge(bridge_event) => {
-                if bridge_event.volume_usd > self.min_volume_migrate_usd && 
+                if Usd::from_f64(bridge_event.volume_usd) > self.min_volume_migrate_usd &&
                    !self.migrated_tokens.contains(&bridge_event.token_address) {
                     info!(
                         id = self.id(),
@@ -42,15 +42,18 @@ impl Strategy for LiquidityMigration {
                     self.migrated_tokens.insert(bridge_event.token_address.clone());
                     return Ok(StrategyAction::Execute(OrderDetails {
                         token_address: bridge_event.token_address.clone(),
-                        suggested_size_usd: 700.0,
+                        suggested_size_usd: Usd::from_f64(700.0),
                         confidence: 0.85,
                         side: Side::Long,
+                        // A migration token can pump then fade fast, so manage the exit with
+                        // a trailing stop instead of holding to an arbitrary close.
+                        order_type: OrderType::TrailingStop { trail_percent: CONFIG.trailing_stop_loss_percent },
                     }));
                 }
             }
             MarketEvent::OnChain(on_chain) if on_chain.event_type == "LP_MIGRATION" => {
                 if let Some(volume) = on_chain.details.get("volume_usd").and_then(|v| v.as_f64()) {
-                    if volume > self.min_volume_migrate_usd && 
+                    if Usd::from_f64(volume) > self.min_volume_migrate_usd &&
                        !self.migrated_tokens.contains(&on_chain.token_address) {
                         info!(
                             id = self.id(),
@@ -61,9 +64,12 @@ impl Strategy for LiquidityMigration {
                         self.migrated_tokens.insert(on_chain.token_address.clone());
                         return Ok(StrategyAction::Execute(OrderDetails {
                             token_address: on_chain.token_address.clone(),
-                            suggested_size_usd: 700.0,
+                            suggested_size_usd: Usd::from_f64(700.0),
                             confidence: 0.85,
                             side: Side::Long,
+                            // A migration token can pump then fade fast, so manage the exit with
+                            // a trailing stop instead of holding to an arbitrary close.
+                            order_type: OrderType::TrailingStop { trail_percent: CONFIG.trailing_stop_loss_percent },
                         }));
                     }
                 }
@@ -15,7 +15,7 @@ pub use liquidity_migration::*;
 pub use perp_basis_arb::*;
 
 // Re-export Strategy trait and related types from shared-models
-pub use shared_models::{Strategy, MarketEvent, StrategyAction, OrderDetails, EventType};
+pub use shared_models::{Strategy, MarketEvent, StrategyAction, OrderDetails, OrderType, EventType};
 
 // Strategy creation function
 pub fn create_strategy(strategy_type: &str) -> Result<Box<dyn Strategy + Send>, anyhow::Error> {
@@ -1,80 +1,238 @@
-use crate::{register_strategy, strategies::{Strategy, MarketEvent, StrategyAction, OrderDetails, EventType}};
+use crate::{config::CONFIG, register_strategy, strategies::{Strategy, MarketEvent, StrategyAction, OrderDetails, OrderType, EventType}};
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Utc, Weekday};
 use serde::Deserialize;
 use serde_json::Value;
 use std::collections::{HashSet, HashMap};
-use tracing::info;
-use shared_models::Side;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+use shared_models::{Price, Side, Usd};
+
+/// Perps typically pay funding on an 8-hour cycle; annualizing multiplies by
+/// 3 cycles/day * 365 days so it's comparable to the basis percentage.
+const FUNDING_CYCLES_PER_YEAR: f64 = 3.0 * 365.0;
+
+/// Used when `init` params omit `max_staleness_secs`, mirroring
+/// `SolPriceWsFeed::HEALTH_STALENESS`'s order of magnitude for "stop trusting
+/// this feed" rather than inventing an unrelated default.
+const DEFAULT_MAX_STALENESS_SECS: u64 = 60;
+
+fn default_max_staleness_secs() -> u64 {
+    DEFAULT_MAX_STALENESS_SECS
+}
+
+/// Weekly expiry boundary a fresh leg defaults to, mirroring a typical
+/// weekly perp/CFD settlement schedule.
+const ROLLOVER_WEEKDAY: Weekday = Weekday::Sun;
+const ROLLOVER_HOUR_UTC: u32 = 15;
+
+/// Used when `init` params omit `rollover_window_secs`: how long before a
+/// leg's `expiry_at` a rollover is allowed to fire.
+const DEFAULT_ROLLOVER_WINDOW_SECS: i64 = 3600;
+
+fn default_rollover_window_secs() -> i64 {
+    DEFAULT_ROLLOVER_WINDOW_SECS
+}
+
+/// Moment (UTC) of the next `ROLLOVER_WEEKDAY`/`ROLLOVER_HOUR_UTC`
+/// strictly after `now`, mirroring `position_manager`'s
+/// `next_funding_rollover_after` so a leg's default expiry lines up with the
+/// same weekly boundary real perp/CFD contracts settle on.
+fn next_rollover_boundary_after(now: DateTime<Utc>) -> DateTime<Utc> {
+    let mut days_ahead =
+        (ROLLOVER_WEEKDAY.num_days_from_monday() as i64 - now.weekday().num_days_from_monday() as i64)
+            .rem_euclid(7);
+
+    let candidate_at = |days_ahead: i64| {
+        (now.date_naive() + ChronoDuration::days(days_ahead))
+            .and_hms_opt(ROLLOVER_HOUR_UTC, 0, 0)
+            .expect("ROLLOVER_HOUR_UTC must be 0-23")
+            .and_utc()
+    };
+
+    if candidate_at(days_ahead) <= now {
+        days_ahead += 7;
+    }
+
+    candidate_at(days_ahead)
+}
+
+/// A `PerpBasisArb` leg this strategy instance itself opened, tracked purely
+/// in memory (the strategy has no DB access) so a rollover only fires for a
+/// position it actually holds rather than for every token still signaling.
+#[derive(Debug, Clone, Copy)]
+struct OpenLeg {
+    side: Side,
+    expiry_at: DateTime<Utc>,
+}
 
 #[derive(Default, Deserialize)]
 struct PerpBasisArb {
     basis_threshold_pct: f64,
-    #[serde(skip)] spot_prices: HashMap<String, f64>,
-    #[serde(skip)] funding_rates: HashMap<String, f64>,
+    #[serde(default = "default_rollover_window_secs")]
+    rollover_window_secs: i64,
+    #[serde(skip)] max_staleness: Option<Duration>,
+    /// Cached value alongside the local time it was received, so a frozen
+    /// feed can be detected even though the cached value itself never changes.
+    #[serde(skip)] spot_prices: HashMap<String, (Price, Instant)>,
+    #[serde(skip)] mark_prices: HashMap<String, (Price, Instant)>,
+    #[serde(skip)] funding_rates: HashMap<String, (f64, Instant)>,
+    /// Legs this instance has opened, keyed by token, so expiry/rollover is
+    /// only evaluated for positions it's actually carrying.
+    #[serde(skip)] open_legs: HashMap<String, OpenLeg>,
+}
+
+impl PerpBasisArb {
+    /// Evaluates the cash-and-carry opportunity for `token` once spot, mark,
+    /// and funding are all known and none of them is older than
+    /// `max_staleness`. Funding only confirms the trade (it must agree in
+    /// sign with the basis once annualized); it's never the signal by itself.
+    ///
+    /// If this instance already holds a leg in `token`, a fresh entry is
+    /// never re-signaled; instead, once `now` enters `rollover_window_secs`
+    /// of the leg's `expiry_at`, the leg is rolled as long as the basis still
+    /// justifies the same side: one same-side `Execute` is emitted and the
+    /// tracked expiry moves to the next weekly boundary. No close order is
+    /// emitted for the expiring leg.
+    fn evaluate(&mut self, token: &str, now: DateTime<Utc>) -> Option<StrategyAction> {
+        let max_staleness = self.max_staleness.unwrap_or(Duration::from_secs(DEFAULT_MAX_STALENESS_SECS));
+        let (spot_price, spot_at) = *self.spot_prices.get(token)?;
+        let (mark_price, mark_at) = *self.mark_prices.get(token)?;
+        let (funding_rate_pct, funding_at) = *self.funding_rates.get(token)?;
+
+        if spot_at.elapsed() > max_staleness
+            || mark_at.elapsed() > max_staleness
+            || funding_at.elapsed() > max_staleness
+        {
+            debug!(id = self.id(), token = %token, "Skipping signal, an oracle input is stale.");
+            return None;
+        }
+
+        // Basis is a dimensionless ratio of two prices, so it's computed in
+        // `f64` from the fixed-point `Price`s rather than carried as one itself.
+        let spot_price = spot_price.to_f64();
+        let mark_price = mark_price.to_f64();
+        if spot_price <= 0.0 {
+            return None;
+        }
+
+        let basis_pct = (mark_price - spot_price) / spot_price * 100.0;
+        let annualized_funding_pct = funding_rate_pct * FUNDING_CYCLES_PER_YEAR;
+        let basis_justifies_position = basis_pct.abs() > self.basis_threshold_pct
+            && basis_pct.signum() == annualized_funding_pct.signum();
+        let side = if basis_pct > 0.0 { Side::Short } else { Side::Long };
+
+        if let Some(leg) = self.open_legs.get(token).copied() {
+            if now < leg.expiry_at - ChronoDuration::seconds(self.rollover_window_secs) {
+                return None;
+            }
+            if !basis_justifies_position || side != leg.side {
+                info!(
+                    id = self.id(),
+                    token = %token,
+                    "Basis no longer justifies the {} leg approaching expiry, letting it expire instead of rolling.",
+                    leg.side,
+                );
+                self.open_legs.remove(token);
+                return None;
+            }
+
+            let new_expiry = next_rollover_boundary_after(leg.expiry_at);
+            self.open_legs.insert(token.to_string(), OpenLeg { side: leg.side, expiry_at: new_expiry });
+            info!(
+                id = self.id(),
+                token = %token,
+                expiry = %new_expiry,
+                "Rolling {} PERP leg ahead of its weekly expiry.",
+                leg.side,
+            );
+            return Some(StrategyAction::Execute(OrderDetails {
+                token_address: token.to_string(),
+                suggested_size_usd: Usd::from_f64(800.0),
+                confidence: 0.9,
+                side: leg.side,
+                order_type: OrderType::TrailingStop {
+                    trail_percent: CONFIG.trailing_stop_loss_percent,
+                },
+            }));
+        }
+
+        if !basis_justifies_position {
+            return None;
+        }
+
+        let expiry_at = next_rollover_boundary_after(now);
+        self.open_legs.insert(token.to_string(), OpenLeg { side, expiry_at });
+        info!(
+            id = self.id(),
+            token = %token,
+            "{} PERP signal: basis {:.2}% (annualized funding {:.2}%) confirms the carry.",
+            side, basis_pct, annualized_funding_pct,
+        );
+        Some(StrategyAction::Execute(OrderDetails {
+            token_address: token.to_string(),
+            suggested_size_usd: Usd::from_f64(800.0),
+            confidence: 0.9,
+            side,
+            // The carry trade's edge decays as basis reverts, so every entry
+            // gets a trailing stop rather than relying on a naked market order.
+            order_type: OrderType::TrailingStop {
+                trail_percent: CONFIG.trailing_stop_loss_percent,
+            },
+        }))
+    }
 }
 
 #[async_trait]
 impl Strategy for PerpBasisArb {
     fn id(&self) -> &'static str { "perp_basis_arb" }
     fn subscriptions(&self) -> HashSet<EventType> {
-        [EventType::Price, EventType::Funding].iter().cloned().collect()
+        [EventType::Price, EventType::MarkPrice, EventType::Funding].iter().cloned().collect()
     }
 
     async fn init(&mut self, params: &Value) -> Result<()> {
-        #[derive(Deserialize)] struct P { basis_threshold_pct: f64 }
+        #[derive(Deserialize)]
+        struct P {
+            basis_threshold_pct: f64,
+            #[serde(default = "default_max_staleness_secs")]
+            max_staleness_secs: u64,
+            #[serde(default = "default_rollover_window_secs")]
+            rollover_window_secs: i64,
+        }
         let p: P = serde_json::from_value(params.clone())?;
         self.basis_threshold_pct = p.basis_threshold_pct;
-        info!(strategy = self.id(), "Initialized with basis_threshold_pct: {}", self.basis_threshold_pct);
+        self.max_staleness = Some(Duration::from_secs(p.max_staleness_secs));
+        self.rollover_window_secs = p.rollover_window_secs;
+        info!(
+            strategy = self.id(),
+            "Initialized with basis_threshold_pct: {}, max_staleness_secs: {}, rollover_window_secs: {}",
+            self.basis_threshold_pct, p.max_staleness_secs, self.rollover_window_secs,
+        );
         Ok(())
     }
 
     async fn on_event(&mut self, event: &MarketEvent) -> Result<StrategyAction> {
-        match event {
+        let token = match event {
             MarketEvent::Price(tick) => {
-                self.spot_prices.insert(tick.token_address.clone(), tick.price_usd);
+                self.spot_prices.insert(tick.token_address.clone(), (Price::from_f64(tick.price_usd), Instant::now()));
+                &tick.token_address
+            }
+            MarketEvent::MarkPrice(mark) => {
+                self.mark_prices.insert(mark.token_address.clone(), (Price::from_f64(mark.mark_price_usd), Instant::now()));
+                &mark.token_address
             }
             MarketEvent::Funding(funding_event) => {
-                self.funding_rates.insert(funding_event.token_address.clone(), funding_event.funding_rate_pct);
-                
-                // Check for arbitrage opportunity when we have both spot and funding data
-                if let Some(&spot_price) = self.spot_prices.get(&funding_event.token_address) {
-                    let basis = funding_event.funding_rate_pct;
-                    
-                    if basis.abs() > self.basis_threshold_pct {
-                        if basis > 0.0 {
-                            info!(
-                                id = self.id(), 
-                                token = %funding_event.token_address, 
-                                "SHORT PERP signal: Positive basis {:.2}% exceeds threshold.",
-                                basis
-                            );
-                            return Ok(StrategyAction::Execute(OrderDetails {
-                                token_address: funding_event.token_address.clone(),
-                                suggested_size_usd: 800.0,
-                                confidence: 0.9,
-                                side: Side::Short,
-                            }));
-                        } else {
-                            info!(
-                                id = self.id(), 
-                                token = %funding_event.token_address, 
-                                "LONG PERP signal: Negative basis {:.2}% exceeds threshold.",
-                                basis
-                            );
-                            return Ok(StrategyAction::Execute(OrderDetails {
-                                token_address: funding_event.token_address.clone(),
-                                suggested_size_usd: 800.0,
-                                confidence: 0.9,
-                                side: Side::Long,
-                            }));
-                        }
-                    }
-                }
+                self.funding_rates.insert(
+                    funding_event.token_address.clone(),
+                    (funding_event.funding_rate_pct, Instant::now()),
+                );
+                &funding_event.token_address
             }
-            _ => {}
-        }
-        Ok(StrategyAction::Hold)
+            _ => return Ok(StrategyAction::Hold),
+        };
+
+        Ok(self.evaluate(token, Utc::now()).unwrap_or(StrategyAction::Hold))
     }
 }
 register_strategy!(PerpBasisArb, "perp_basis_arb");
@@ -0,0 +1,125 @@
+//! JSON-file-driven per-strategy configuration, hot-reloaded on a timer.
+//!
+//! Per-strategy tunables (`PerpBasisArb`'s `basis_threshold_pct`,
+//! `LiquidityMigration`'s sizing, ...) used to only be reachable by
+//! recompiling a hardcoded literal or pushing a `StartStrategy`/allocation
+//! call. `strategies.json` gives an operator one file to edit instead,
+//! picked up within `strategy_config_reload_interval_secs` without a
+//! restart. Secrets and URLs stay in `Config`/env vars; this only ever
+//! carries `Strategy::init` params.
+use crate::config::CONFIG;
+use crate::control_api::ControlCommand;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use shared_models::TradeMode;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::{mpsc::Sender, oneshot, RwLock};
+use tracing::{error, info, warn};
+
+/// One `strategies.json` entry: the `TradeMode` to bootstrap the strategy
+/// under, plus whatever `Strategy::init` params it needs. Kept flat (no
+/// nested `params` key) so the file reads as one object per strategy id.
+#[derive(Debug, Clone, Deserialize)]
+struct StrategyFileEntry {
+    mode: TradeMode,
+    #[serde(flatten)]
+    params: Value,
+}
+
+/// Loads `strategies.json` (strategy id -> `StrategyFileEntry`) once at
+/// startup and re-reads it whenever its mtime changes, so `MasterExecutor`
+/// can bootstrap strategies from it and re-`init` already-running ones on
+/// edit.
+pub struct StrategyConfigStore {
+    path: PathBuf,
+    entries: RwLock<HashMap<String, StrategyFileEntry>>,
+    last_modified: RwLock<Option<SystemTime>>,
+}
+
+impl StrategyConfigStore {
+    /// A missing or unparseable file isn't fatal, since an operator may not
+    /// have written one yet; `MasterExecutor` just starts with nothing
+    /// JSON-configured until the file shows up.
+    pub async fn new(path: String) -> Result<Arc<Self>> {
+        let path = PathBuf::from(path);
+        let entries = Self::read(&path).await.unwrap_or_else(|e| {
+            warn!(error = %e, path = %path.display(), "No usable strategy config file yet, starting with none configured");
+            HashMap::new()
+        });
+        let last_modified = Self::mtime(&path).await;
+        Ok(Arc::new(Self {
+            path,
+            entries: RwLock::new(entries),
+            last_modified: RwLock::new(last_modified),
+        }))
+    }
+
+    async fn read(path: &PathBuf) -> Result<HashMap<String, StrategyFileEntry>> {
+        let raw = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("reading {}", path.display()))?;
+        serde_json::from_str(&raw).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    async fn mtime(path: &PathBuf) -> Option<SystemTime> {
+        tokio::fs::metadata(path).await.ok()?.modified().ok()
+    }
+
+    /// Every configured `(id, mode, params)`, e.g. for `MasterExecutor` to
+    /// bootstrap its initial set of active strategies at startup.
+    pub async fn all(&self) -> Vec<(String, TradeMode, Value)> {
+        self.entries
+            .read()
+            .await
+            .iter()
+            .map(|(id, entry)| (id.clone(), entry.mode, entry.params.clone()))
+            .collect()
+    }
+
+    pub async fn get(&self, id: &str) -> Option<(TradeMode, Value)> {
+        self.entries.read().await.get(id).map(|entry| (entry.mode, entry.params.clone()))
+    }
+
+    /// Spawns a background task that polls `path`'s mtime every
+    /// `strategy_config_reload_interval_secs` and, on a change, reloads it
+    /// then asks `MasterExecutor` (over `control_tx`) to re-`init` every
+    /// active strategy the file still describes.
+    pub fn spawn_watch(self: &Arc<Self>, control_tx: Sender<ControlCommand>) {
+        let store = self.clone();
+        tokio::spawn(async move {
+            let interval = std::time::Duration::from_secs(CONFIG.strategy_config_reload_interval_secs);
+            loop {
+                tokio::time::sleep(interval).await;
+
+                let current_mtime = Self::mtime(&store.path).await;
+                if current_mtime == *store.last_modified.read().await {
+                    continue;
+                }
+
+                match Self::read(&store.path).await {
+                    Ok(entries) => {
+                        *store.entries.write().await = entries;
+                        *store.last_modified.write().await = current_mtime;
+
+                        let (reply_tx, reply_rx) = oneshot::channel();
+                        if control_tx.send(ControlCommand::ReloadStrategyConfig { reply: reply_tx }).await.is_ok() {
+                            if let Ok(reloaded) = reply_rx.await {
+                                info!(reloaded, "strategies.json changed, reloaded strategy config");
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        error!(error = %e, "strategies.json changed but failed to parse, keeping previous config");
+                        // Still record the new mtime so a bad edit doesn't
+                        // get retried every tick until it's fixed.
+                        *store.last_modified.write().await = current_mtime;
+                    }
+                }
+            }
+        });
+    }
+}
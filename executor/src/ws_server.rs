@@ -0,0 +1,248 @@
+//! WebSocket fan-out server for market events and open positions.
+//!
+//! Mirrors the checkpoint pattern used by fills-gateways: each peer is tracked in a
+//! `PeerMap`, fed from a `broadcast::Sender<FanoutMessage>` that `MasterExecutor`
+//! publishes onto as it dispatches the same `events:price`/`events:social` streams
+//! it consumes internally, and on subscribe gets a consistent snapshot before
+//! deltas start flowing so late joiners never see a gap.
+use crate::database::Database;
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use shared_models::MarketEvent;
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, RwLock};
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, error, info, warn};
+
+const CHANNELS: &[&str] = &["price", "social", "positions", "pnl"];
+
+/// A unit of data fanned out to subscribed peers. `channel` is one of `CHANNELS`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum FanoutMessage {
+    Market {
+        channel: &'static str,
+        event: MarketEvent,
+    },
+    Checkpoint {
+        open_trades: Vec<CheckpointTrade>,
+        sol_usd_price: f64,
+        last_prices: HashMap<String, f64>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckpointTrade {
+    pub id: i64,
+    pub token_address: String,
+    pub side: String,
+    pub amount_usd: f64,
+    pub entry_price_usd: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "command", rename_all = "lowercase")]
+enum ControlMessage {
+    Subscribe { channels: Vec<String> },
+    Unsubscribe { channels: Vec<String> },
+}
+
+type PeerId = u64;
+
+/// Per-peer subscription state, keyed by a monotonically increasing peer id.
+type PeerMap = Arc<Mutex<HashMap<PeerId, HashSet<String>>>>;
+
+pub struct WsServer {
+    bind_addr: SocketAddr,
+    db: Arc<Database>,
+    sol_usd_price: Arc<RwLock<f64>>,
+    last_prices: Arc<RwLock<HashMap<String, f64>>>,
+    tx: broadcast::Sender<FanoutMessage>,
+}
+
+impl WsServer {
+    pub fn new(
+        bind_addr: SocketAddr,
+        db: Arc<Database>,
+        sol_usd_price: Arc<RwLock<f64>>,
+        last_prices: Arc<RwLock<HashMap<String, f64>>>,
+    ) -> Self {
+        let (tx, _rx) = broadcast::channel(2048);
+        Self {
+            bind_addr,
+            db,
+            sol_usd_price,
+            last_prices,
+            tx,
+        }
+    }
+
+    /// Handle fed by `MasterExecutor::dispatch_event` to fan deltas out to peers.
+    pub fn sender(&self) -> broadcast::Sender<FanoutMessage> {
+        self.tx.clone()
+    }
+
+    pub async fn run(self) -> Result<()> {
+        let listener = TcpListener::bind(self.bind_addr)
+            .await
+            .with_context(|| format!("Failed to bind WS fan-out server on {}", self.bind_addr))?;
+        info!(addr = %self.bind_addr, "📡 WS fan-out server listening");
+
+        let peers: PeerMap = Arc::new(Mutex::new(HashMap::new()));
+        let mut next_peer_id: PeerId = 0;
+
+        loop {
+            let (stream, addr) = match listener.accept().await {
+                Ok(v) => v,
+                Err(e) => {
+                    error!(error = %e, "Failed to accept WS connection");
+                    continue;
+                }
+            };
+
+            next_peer_id += 1;
+            let peer_id = next_peer_id;
+            let db = self.db.clone();
+            let sol_usd_price = self.sol_usd_price.clone();
+            let last_prices = self.last_prices.clone();
+            let rx = self.tx.subscribe();
+            let peers = peers.clone();
+
+            tokio::spawn(async move {
+                if let Err(e) =
+                    handle_peer(peer_id, stream, addr, peers.clone(), rx, db, sol_usd_price, last_prices).await
+                {
+                    warn!(peer_id, error = %e, "WS peer connection ended with error");
+                }
+                peers.lock().unwrap().remove(&peer_id);
+            });
+        }
+    }
+}
+
+async fn handle_peer(
+    peer_id: PeerId,
+    stream: tokio::net::TcpStream,
+    addr: SocketAddr,
+    peers: PeerMap,
+    mut rx: broadcast::Receiver<FanoutMessage>,
+    db: Arc<Database>,
+    sol_usd_price: Arc<RwLock<f64>>,
+    last_prices: Arc<RwLock<HashMap<String, f64>>>,
+) -> Result<()> {
+    let ws_stream = tokio_tungstenite::accept_async(stream)
+        .await
+        .context("WS handshake failed")?;
+    info!(peer_id, %addr, "WS peer connected");
+    peers.lock().unwrap().insert(peer_id, HashSet::new());
+
+    let (mut ws_tx, mut ws_rx) = ws_stream.split();
+
+    loop {
+        tokio::select! {
+            incoming = ws_rx.next() => {
+                match incoming {
+                    Some(Ok(Message::Text(text))) => {
+                        if let Ok(ctrl) = serde_json::from_str::<ControlMessage>(&text) {
+                            handle_control(peer_id, ctrl, &peers, &db, &sol_usd_price, &last_prices, &mut ws_tx).await?;
+                        } else {
+                            debug!(peer_id, "Ignoring unrecognized control message: {}", text);
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => {
+                        warn!(peer_id, error = %e, "WS read error");
+                        break;
+                    }
+                }
+            }
+            msg = rx.recv() => {
+                match msg {
+                    Ok(FanoutMessage::Market { channel, event }) => {
+                        if peers.lock().unwrap().get(&peer_id).map(|s| s.contains(channel)).unwrap_or(false) {
+                            let payload = serde_json::to_string(&FanoutMessage::Market { channel, event })?;
+                            if ws_tx.send(Message::Text(payload)).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    Ok(other) => {
+                        let payload = serde_json::to_string(&other)?;
+                        if ws_tx.send(Message::Text(payload)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!(peer_id, skipped = n, "WS peer lagged, dropping buffered deltas");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        }
+    }
+
+    info!(peer_id, "WS peer disconnected");
+    Ok(())
+}
+
+async fn handle_control(
+    peer_id: PeerId,
+    ctrl: ControlMessage,
+    peers: &PeerMap,
+    db: &Arc<Database>,
+    sol_usd_price: &Arc<RwLock<f64>>,
+    last_prices: &Arc<RwLock<HashMap<String, f64>>>,
+    ws_tx: &mut (impl SinkExt<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin),
+) -> Result<()> {
+    match ctrl {
+        ControlMessage::Subscribe { channels } => {
+            let requested: HashSet<String> = channels
+                .into_iter()
+                .filter(|c| CHANNELS.contains(&c.as_str()))
+                .collect();
+
+            {
+                let mut peers = peers.lock().unwrap();
+                if let Some(subs) = peers.get_mut(&peer_id) {
+                    subs.extend(requested.iter().cloned());
+                }
+            }
+
+            // Send a checkpoint so the new subscriber starts from consistent state.
+            let open_trades = db
+                .get_all_trades()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|t| t.status == "OPEN")
+                .map(|t| CheckpointTrade {
+                    id: t.id,
+                    token_address: t.token_address,
+                    side: t.side,
+                    amount_usd: t.amount_usd,
+                    entry_price_usd: t.entry_price_usd,
+                })
+                .collect();
+            let checkpoint = FanoutMessage::Checkpoint {
+                open_trades,
+                sol_usd_price: *sol_usd_price.read().await,
+                last_prices: last_prices.read().await.clone(),
+            };
+            let payload = serde_json::to_string(&checkpoint)?;
+            let _ = ws_tx.send(Message::Text(payload)).await;
+        }
+        ControlMessage::Unsubscribe { channels } => {
+            let mut peers = peers.lock().unwrap();
+            if let Some(subs) = peers.get_mut(&peer_id) {
+                for c in channels {
+                    subs.remove(&c);
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,9 +1,10 @@
 use crate::providers::validate_simulated_event;
-use anyhow::Result;
+use crate::reorder::{Admit, ReorderBuffer};
+use anyhow::{Context, Result};
 use redis::AsyncCommands;
 use shared_models::MarketEvent;
 use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::{mpsc, watch, Mutex};
 use tracing::{error, info, warn, level_filters::LevelFilter};
 use tracing_subscriber;
 use std::time::Duration;
@@ -11,12 +12,14 @@ use prometheus::{Counter, Gauge, HistogramVec, Registry, Encoder, TextEncoder};
 use axum::{extract::State, http::StatusCode, response::Response, routing::get, Router};
 
 mod providers;
+mod reorder;
 
 // Configuration - normally this would be in a separate config.rs file
 pub struct Config {
     pub redis_url: String,
     pub helius_api_key: String,
     pub pyth_api_key: String,
+    pub pyth_hermes_url: String,
     pub twitter_bearer_token: String,
     pub farcaster_api_url: String,
 }
@@ -27,6 +30,7 @@ impl Config {
             redis_url: std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string()),
             helius_api_key: std::env::var("HELIUS_API_KEY").unwrap_or_else(|_| "demo_key".to_string()),
             pyth_api_key: std::env::var("PYTH_API_KEY").unwrap_or_else(|_| "demo_key".to_string()),
+            pyth_hermes_url: std::env::var("PYTH_HERMES_URL").unwrap_or_else(|_| "https://hermes.pyth.network".to_string()),
             twitter_bearer_token: std::env::var("TWITTER_BEARER_TOKEN").unwrap_or_else(|_| "demo_token".to_string()),
             farcaster_api_url: std::env::var("FARCASTER_API_URL").unwrap_or_else(|_| "https://api.farcaster.xyz".to_string()),
         }
@@ -51,6 +55,7 @@ pub struct DataValidationMetrics {
     pub circuit_breaker_active: Gauge,
     pub validation_latency: HistogramVec,
     pub provider_events: Counter,
+    pub reconnect_count: Counter,
 }
 
 impl DataValidationMetrics {
@@ -69,7 +74,9 @@ impl DataValidationMetrics {
         ).context("Failed to create validation_latency histogram")?;
         let provider_events = Counter::new("data_provider_events_total", "Events received per provider")
             .context("Failed to create provider_events counter")?;
-        
+        let reconnect_count = Counter::new("data_provider_reconnects_total", "WebSocket reconnects across all streaming providers")
+            .context("Failed to create reconnect_count counter")?;
+
         registry.register(Box::new(events_total.clone()))
             .context("Failed to register events_total metric")?;
         registry.register(Box::new(events_invalid.clone()))
@@ -80,7 +87,9 @@ impl DataValidationMetrics {
             .context("Failed to register validation_latency metric")?;
         registry.register(Box::new(provider_events.clone()))
             .context("Failed to register provider_events metric")?;
-        
+        registry.register(Box::new(reconnect_count.clone()))
+            .context("Failed to register reconnect_count metric")?;
+
         Ok(Self {
             registry,
             events_total,
@@ -88,10 +97,43 @@ impl DataValidationMetrics {
             circuit_breaker_active,
             validation_latency,
             provider_events,
+            reconnect_count,
         })
     }
 }
 
+/// How long to wait for provider tasks to notice a shutdown signal and
+/// return before giving up and exiting anyway.
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+/// Wait on SIGINT or SIGTERM (whichever arrives first) and flip `shutdown_tx`
+/// so every provider loop and the main event loop stop accepting new work.
+async fn wait_for_shutdown_signal(shutdown_tx: watch::Sender<bool>) {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sig) => {
+                sig.recv().await;
+            }
+            Err(e) => error!("Failed to install SIGTERM handler: {}", e),
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("Received SIGINT"),
+        _ = terminate => info!("Received SIGTERM"),
+    }
+
+    info!("🛑 Shutdown signal received, draining in-flight events...");
+    let _ = shutdown_tx.send(true);
+}
+
 // Metrics endpoint handler
 async fn metrics_handler(State(metrics): State<DataValidationMetrics>) -> Result<Response<String>, StatusCode> {
     let encoder = TextEncoder::new();
@@ -147,79 +189,180 @@ async fn main() -> Result<()> {
     let redis_client = redis::Client::open(config::CONFIG.redis_url.as_str())?;
     let mut redis_conn = redis_client.get_async_connection().await?;
 
-    // Create channel for receiving market events from simulated providers
+    // Create channel for receiving market events from data providers
     let (tx, mut rx) = mpsc::channel::<MarketEvent>(1000);
 
-    // Spawn simulated data provider (as per README - market data is simulated but designed for easy replacement)
+    // Coordinated shutdown: flipped by `wait_for_shutdown_signal` on SIGINT/SIGTERM,
+    // observed by every provider loop and the main event loop below so a redeploy
+    // doesn't cut a reconnect mid-stream or drop buffered-but-unpublished events.
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    tokio::spawn(wait_for_shutdown_signal(shutdown_tx));
+
+    // SOL/USD cache shared with `helius_consumer`, which needs a price to
+    // convert a pool's wSOL reserve into USD but has no oracle of its own.
+    let sol_price_cache: providers::SolPriceCache = Arc::new(tokio::sync::RwLock::new(150.0));
+
+    // Real Pyth/Hermes price feed replaces the old simulated SOL price walk.
+    let tx_pyth = tx.clone();
+    let shutdown_pyth = shutdown_rx.clone();
+    let sol_price_cache_pyth = sol_price_cache.clone();
+    let pyth_handle = tokio::spawn(async move {
+        if let Err(e) = providers::pyth_consumer::run(tx_pyth, sol_price_cache_pyth, shutdown_pyth).await {
+            error!("Pyth data consumer failed: {}", e);
+        }
+    });
+
+    // Real Helius account-subscription feed (price and depth) replaces the
+    // old simulated price/depth generator.
+    let tx_helius = tx.clone();
+    let shutdown_helius = shutdown_rx.clone();
+    let sol_price_cache_helius = sol_price_cache.clone();
+    let helius_handle = tokio::spawn(async move {
+        if let Err(e) = providers::helius_consumer::run(tx_helius, sol_price_cache_helius, shutdown_helius).await {
+            error!("Helius data consumer failed: {}", e);
+        }
+    });
+
+    // Real Farcaster cast stream replaces the old simulated cast generator.
+    let tx_farcaster = tx.clone();
+    let metrics_farcaster = metrics.clone();
+    let shutdown_farcaster = shutdown_rx.clone();
+    let farcaster_handle = tokio::spawn(async move {
+        if let Err(e) =
+            providers::farcaster_consumer::run(tx_farcaster, Some(&metrics_farcaster), shutdown_farcaster).await
+        {
+            error!("Farcaster data consumer failed: {}", e);
+        }
+    });
+
+    // Spawn simulated data provider for the event types not yet backed by a real feed
+    // (as per README - market data is simulated but designed for easy replacement)
     let tx_sim = tx.clone();
-    tokio::spawn(async move {
-        if let Err(e) = run_simulated_data_provider(tx_sim).await {
+    let shutdown_sim = shutdown_rx.clone();
+    let sim_handle = tokio::spawn(async move {
+        if let Err(e) = run_simulated_data_provider(tx_sim, shutdown_sim).await {
             error!("Simulated data provider failed: {}", e);
         }
     });
+    // The main loop is the only remaining holder of `tx`; drop this one so
+    // `rx.recv()` can observe the channel closing once the providers above
+    // (which hold their own clones) finish.
+    drop(tx);
 
     // Main event processing loop
     info!("🔍 Starting market data processing loop");
-    while let Some(event) = rx.recv().await {
-        let start_time = std::time::Instant::now();
-        
-        // Validate event (simple validation for simulation mode)
-        if validate_simulated_event(&event) {
-            // Publish to Redis
-            match redis_conn.xadd::<&str, &str, &str, &str>(
-                "events:price",
-                "*",
-                &[("data", &serde_json::to_string(&event).unwrap_or_default())],
-            ).await {
-                Ok(_) => {
-                    metrics.events_total.inc();
-                    info!("📡 Published market event: {:?}", event);
+    let mut reorder_buffer = ReorderBuffer::default();
+    let mut reorder_flush = tokio::time::interval(Duration::from_millis(50));
+    let mut shutdown_rx_main = shutdown_rx;
+
+    loop {
+        tokio::select! {
+            maybe_event = rx.recv() => {
+                let Some(event) = maybe_event else { break };
+
+                let Some(seq) = event.sequence() else {
+                    publish_event(&mut redis_conn, &metrics, event).await;
+                    continue;
+                };
+
+                match reorder_buffer.admit(event, seq) {
+                    Admit::Ready(events) => {
+                        for event in events {
+                            publish_event(&mut redis_conn, &metrics, event).await;
+                        }
+                    }
+                    Admit::Held => {}
+                    Admit::Stale => {
+                        metrics.events_invalid.inc();
+                        warn!("🚫 Dropped stale out-of-order event");
+                    }
                 }
-                Err(e) => {
-                    metrics.events_invalid.inc();
-                    error!("Failed to publish event to Redis: {}", e);
+            }
+            _ = reorder_flush.tick() => {
+                for event in reorder_buffer.flush_expired() {
+                    publish_event(&mut redis_conn, &metrics, event).await;
                 }
             }
-        } else {
-            metrics.events_invalid.inc();
-            warn!("🚫 Invalid event dropped: {:?}", event);
+            _ = shutdown_rx_main.changed() => {
+                info!("Main event loop stopping new work, draining reorder buffer...");
+                break;
+            }
         }
-        
-        // Update metrics
-        let processing_duration = start_time.elapsed().as_millis() as f64;
-        metrics.validation_latency
-            .with_label_values(&[&event.get_type().to_string(), "simulated"])
-            .observe(processing_duration);
     }
 
+    // Drain whatever the reorder buffer was still holding back so a
+    // restart doesn't lose events that had already been admitted.
+    for event in reorder_buffer.flush_all() {
+        publish_event(&mut redis_conn, &metrics, event).await;
+    }
+
+    // Give the provider tasks a bounded window to notice the shutdown signal
+    // and return cleanly before giving up on them.
+    let drain = async {
+        let _ = tokio::join!(pyth_handle, helius_handle, farcaster_handle, sim_handle);
+    };
+    if tokio::time::timeout(SHUTDOWN_GRACE_PERIOD, drain).await.is_err() {
+        warn!(
+            "Provider tasks did not finish within {:?} of shutdown; exiting anyway",
+            SHUTDOWN_GRACE_PERIOD
+        );
+    }
+
+    info!("👋 Market data gateway shut down cleanly");
     Ok(())
 }
+
+/// Validate, publish to `events:price`, and record metrics for one event
+/// that has already cleared the reorder buffer.
+async fn publish_event(
+    redis_conn: &mut redis::aio::Connection,
+    metrics: &DataValidationMetrics,
+    event: MarketEvent,
+) {
+    let start_time = std::time::Instant::now();
+
+    if validate_simulated_event(&event) {
+        match redis_conn.xadd::<&str, &str, &str, &str>(
+            "events:price",
+            "*",
+            &[("data", &serde_json::to_string(&event).unwrap_or_default())],
+        ).await {
+            Ok(_) => {
+                metrics.events_total.inc();
+                info!("📡 Published market event: {:?}", event);
+            }
+            Err(e) => {
+                metrics.events_invalid.inc();
+                error!("Failed to publish event to Redis: {}", e);
+            }
+        }
+    } else {
+        metrics.events_invalid.inc();
+        warn!("🚫 Invalid event dropped: {:?}", event);
+    }
+
+    let processing_duration = start_time.elapsed().as_millis() as f64;
+    metrics.validation_latency
+        .with_label_values(&[&event.get_type().to_string(), "simulated"])
+        .observe(processing_duration);
 }
 
-/// Simulated data provider for development and testing
-/// As per README: "Market data is currently simulated but designed for easy replacement with real feeds"
-async fn run_simulated_data_provider(tx: mpsc::Sender<MarketEvent>) -> Result<()> {
-    let mut interval = tokio::time::interval(Duration::from_secs(1));
-    let mut sol_price = 100.0; // Starting SOL price
-    
-    info!("🎲 Starting simulated data provider");
-    
+/// Simulated data provider for development and testing.
+/// SOL/USD and Farcaster casts are now sourced from real feeds
+/// (`providers::pyth_consumer`, `providers::farcaster_consumer`); this loop
+/// is kept as a placeholder for the event types not yet backed by a real feed.
+async fn run_simulated_data_provider(_tx: mpsc::Sender<MarketEvent>, mut shutdown: watch::Receiver<bool>) -> Result<()> {
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+
+    info!("🎲 Starting simulated data provider (non-price, non-Farcaster events only)");
+
     loop {
-        interval.tick().await;
-        
-        // Simulate SOL price with random walk
-        sol_price += (rand::random::<f64>() - 0.5) * 2.0; // +/- $1 volatility
-        sol_price = sol_price.max(50.0).min(200.0); // Keep within reasonable bounds
-        
-        let sol_event = MarketEvent::SolPrice(shared_models::SolPriceEvent {
-            price_usd: sol_price,
-        });
-        
-        if tx.send(sol_event).await.is_err() {
-            warn!("Receiver dropped, stopping simulated data provider");
-            break;
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = shutdown.changed() => {
+                info!("Shutdown requested, stopping simulated data provider");
+                return Ok(());
+            }
         }
     }
-    
-    Ok(())
 }
\ No newline at end of file
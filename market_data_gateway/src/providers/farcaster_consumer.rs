@@ -1,27 +1,63 @@
 use crate::config::CONFIG;
+use crate::providers::streaming::StreamingProvider;
+use crate::DataValidationMetrics;
 use anyhow::Result;
-use reqwest::Client;
-use shared_models::{MarketEvent, FarcasterRawEvent};
-use tokio::sync::mpsc;
-use tracing::{error, info};
+use serde::Deserialize;
+use shared_models::{FarcasterRawEvent, MarketEvent};
 use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use tracing::warn;
 
-pub async fn run(tx: mpsc::Sender<MarketEvent>) -> Result<()> {
-    info!("Starting Farcaster Data Consumer...");
-    let client = Client::new();
-    let farcaster_api_url = CONFIG.farcaster_api_url.clone();
+/// Raw cast payload as streamed by the Farcaster hub's websocket feed.
+#[derive(Debug, Deserialize)]
+struct CastFrame {
+    hash: String,
+    text: String,
+    author_fid: u64,
+    timestamp: i64,
+}
+
+/// Reconnect if the hub goes quiet for this long, since casts arrive
+/// frequently enough that silence this long means the socket is dead.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub async fn run(
+    tx: mpsc::Sender<MarketEvent>,
+    metrics: Option<&DataValidationMetrics>,
+    shutdown: watch::Receiver<bool>,
+) -> Result<()> {
+    let url = format!(
+        "{}/v1/stream/casts",
+        CONFIG.farcaster_api_url.replacen("https://", "wss://", 1)
+    );
 
-    loop {
-        // Simulate Farcaster data
-        let cast_hash = format!("cast_{}", rand::random::<u64>());
-        
-        if let Err(e) = tx.send(MarketEvent::FarcasterRaw(FarcasterRawEvent {
-            cast_hash,
-            text: "New memecoin alert: $PEPE2 launching on pump.fun".to_string(),
-            author_fid: format!("fid_{}", rand::random::<u32>() % 1000),
-            timestamp: chrono::Utc::now().timestamp(),
-        })).await { error!("Failed to send FarcasterRawEvent: {}", e); }
+    let provider = StreamingProvider {
+        name: "farcaster",
+        url,
+        subscribe_messages: vec![serde_json::json!({"type": "subscribe", "channel": "casts"}).to_string()],
+        idle_timeout: IDLE_TIMEOUT,
+        on_message: move |text: String| {
+            let tx = tx.clone();
+            async move {
+                match serde_json::from_str::<CastFrame>(&text) {
+                    Ok(frame) => {
+                        if let Err(e) = tx
+                            .send(MarketEvent::FarcasterRaw(FarcasterRawEvent {
+                                cast_hash: frame.hash,
+                                text: frame.text,
+                                author_fid: frame.author_fid.to_string(),
+                                timestamp: frame.timestamp,
+                            }))
+                            .await
+                        {
+                            warn!("Failed to send FarcasterRawEvent: {}", e);
+                        }
+                    }
+                    Err(e) => warn!("Failed to parse Farcaster cast frame: {}", e),
+                }
+            }
+        },
+    };
 
-        tokio::time::sleep(Duration::from_secs(5)).await;
-    }
+    provider.run(metrics, shutdown).await
 }
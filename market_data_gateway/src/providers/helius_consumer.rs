@@ -1,60 +1,326 @@
+//! Real Helius account-subscription feed, replacing the old timer-driven
+//! simulated events.
+//!
+//! Each tracked meme token is priced off a constant-product pool: one
+//! `accountSubscribe` tracks the pool's base-token vault, the other its
+//! wSOL vault. Account writes arrive over the websocket out of order across
+//! slots (a reconnect can replay a write the client already applied, and
+//! concurrent writers can race), so every update is gated on
+//! `(slot, write_version)` per token_address — anything at or behind the
+//! last-applied pair for that token is dropped before it can clobber a
+//! fresher reserve with a stale one.
 use crate::config::CONFIG;
-use anyhow::{anyhow, Result};
-use async_trait::async_trait;
-use reqwest::Client;
-use serde_json::Value;
-use shared_models::{MarketEvent, PriceTick, DepthEvent, BridgeEvent, OnChainEvent};
-use tokio::sync::mpsc;
-use tracing::{error, info, warn};
+use crate::providers::SolPriceCache;
+use anyhow::{anyhow, Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use shared_models::{DepthEvent, MarketEvent, PriceTick};
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{mpsc, watch, Mutex};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{error, info, warn};
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// `(token_address, base_vault, base_decimals, quote_vault)` for the pools
+/// this consumer prices. The quote side is always a wSOL vault.
+const TRACKED_POOLS: &[(&str, &str, u8, &str)] = &[
+    (
+        "MEME1111111111111111111111111111111111111",
+        "Base1VauLt1111111111111111111111111111111",
+        6,
+        "Quote1VauLt111111111111111111111111111111",
+    ),
+    (
+        "MEME2222222222222222222222222222222222222",
+        "Base2VauLt2222222222222222222222222222222",
+        6,
+        "Quote2VauLt222222222222222222222222222222",
+    ),
+];
+
+/// Which side of a pool an `accountSubscribe` tracks.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum VaultSide {
+    Base,
+    Quote,
+}
+
+/// One leg of a tracked pool, resolved by subscription id once the
+/// websocket acks the `accountSubscribe` request.
+#[derive(Clone)]
+struct VaultSubscription {
+    token_address: &'static str,
+    side: VaultSide,
+    base_decimals: u8,
+}
+
+/// Latest reserves observed for a token's pool, gated by `(slot, write_version)`
+/// so an out-of-order write can't roll a reserve backwards.
+#[derive(Default, Clone, Copy)]
+struct PoolState {
+    base_reserve_raw: Option<u64>,
+    quote_reserve_lamports: Option<u64>,
+    last_slot: u64,
+    last_write_version: u64,
+}
+
+impl PoolState {
+    fn is_stale(&self, slot: u64, write_version: u64) -> bool {
+        (slot, write_version) <= (self.last_slot, self.last_write_version)
+    }
+}
+
+/// Raw `accountNotification` push, matching Helius's enhanced websocket
+/// shape (a superset of the standard Solana `accountSubscribe` payload that
+/// additionally carries `write_version` for geyser-equivalent ordering).
+#[derive(Debug, Deserialize)]
+struct AccountNotification {
+    params: AccountNotificationParams,
+}
 
-pub struct HeliusConsumer;
+#[derive(Debug, Deserialize)]
+struct AccountNotificationParams {
+    subscription: u64,
+    result: AccountNotificationResult,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountNotificationResult {
+    context: AccountNotificationContext,
+    value: AccountValue,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountNotificationContext {
+    slot: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountValue {
+    data: (String, String),
+    #[serde(default)]
+    write_version: u64,
+}
 
-pub async fn run(tx: mpsc::Sender<MarketEvent>) -> Result<()> {
-    info!("Starting Helius Data Consumer...");
-    let client = Client::new();
-    let api_key = CONFIG.helius_api_key.clone();
-    let rpc_url = format!("https://rpc.helius.xyz/?api-key={}", api_key);
+/// Subscribe-request ack, e.g. `{"jsonrpc":"2.0","result":12345,"id":1}`.
+#[derive(Debug, Deserialize)]
+struct SubscribeAck {
+    id: u64,
+    result: u64,
+}
+
+pub async fn run(
+    tx: mpsc::Sender<MarketEvent>,
+    sol_price_cache: SolPriceCache,
+    mut shutdown: watch::Receiver<bool>,
+) -> Result<()> {
+    info!("Starting Helius account-subscription consumer...");
+
+    // Ordering state survives reconnects, so a replayed-but-already-applied
+    // write from a fresh subscription is dropped rather than re-emitted.
+    let pool_states: Arc<Mutex<HashMap<&'static str, PoolState>>> = Arc::new(Mutex::new(HashMap::new()));
+    let mut backoff = INITIAL_BACKOFF;
 
     loop {
-        // Simulate various event types for demo purposes
-        // In production, these would come from real Helius WebSocket connections
-        
-        // Price events
-        if let Err(e) = tx.send(MarketEvent::Price(PriceTick {
-            token_address: "So11111111111111111111111111111111111111112".to_string(),
-            price_usd: 150.0 + (rand::random::<f64>() * 10.0 - 5.0),
-            volume_usd_1m: rand::random::<f64>() * 100000.0,
-        })).await { error!("Failed to send PriceTick: {}", e); }
-
-        // Depth events
-        if let Err(e) = tx.send(MarketEvent::Depth(DepthEvent {
-            token_address: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
-            bid_price: 0.999,
-            ask_price: 1.001,
-            bid_size_usd: rand::random::<f64>() * 50000.0,
-            ask_size_usd: rand::random::<f64>() * 50000.0,
-        })).await { error!("Failed to send DepthEvent: {}", e); }
-
-        // Bridge events
-        if rand::random::<f64>() < 0.1 {
-            if let Err(e) = tx.send(MarketEvent::Bridge(BridgeEvent {
-                token_address: format!("MEME{}", rand::random::<u32>() % 100),
-                source_chain: "ethereum".to_string(),
-                destination_chain: "solana".to_string(),
-                volume_usd: rand::random::<f64>() * 1000000.0,
-            })).await { error!("Failed to send BridgeEvent: {}", e); }
+        tokio::select! {
+            result = connect_and_stream(&tx, &sol_price_cache, &pool_states) => {
+                match result {
+                    Ok(()) => warn!("Helius account stream ended cleanly, reconnecting..."),
+                    Err(e) => error!("Helius account stream error: {}, reconnecting in {:?}", e, backoff),
+                }
+            }
+            _ = shutdown.changed() => {
+                info!("Shutdown requested, stopping Helius consumer");
+                return Ok(());
+            }
         }
 
-        // OnChain events
-        if rand::random::<f64>() < 0.05 {
-            if let Err(e) = tx.send(MarketEvent::OnChain(OnChainEvent {
-                token_address: format!("MEME{}", rand::random::<u32>() % 100),
-                event_type: "LP_LOCK".to_string(),
-                details: serde_json::json!({"locked": true, "duration_days": 30}),
-            })).await { error!("Failed to send OnChainEvent: {}", e); }
+        if *shutdown.borrow() {
+            info!("Shutdown requested, stopping Helius consumer");
+            return Ok(());
         }
 
-        tokio::time::sleep(Duration::from_secs(1)).await;
+        let jitter = Duration::from_millis(rand::random::<u64>() % 250);
+        tokio::select! {
+            _ = tokio::time::sleep(backoff + jitter) => {}
+            _ = shutdown.changed() => {
+                info!("Shutdown requested during backoff, stopping Helius consumer");
+                return Ok(());
+            }
+        }
+        backoff = (backoff * 2).min(MAX_BACKOFF);
     }
 }
+
+async fn connect_and_stream(
+    tx: &mpsc::Sender<MarketEvent>,
+    sol_price_cache: &SolPriceCache,
+    pool_states: &Arc<Mutex<HashMap<&'static str, PoolState>>>,
+) -> Result<()> {
+    let url = format!("wss://mainnet.helius-rpc.com/?api-key={}", CONFIG.helius_api_key);
+    let (ws_stream, _) = connect_async(&url)
+        .await
+        .with_context(|| format!("Failed to connect to {}", url))?;
+    info!("Connected to Helius account-subscription feed");
+
+    let (mut write, mut read) = ws_stream.split();
+
+    // Fixed request ids (by index) so the subscription-id they resolve to
+    // can be mapped back to a vault without any handshake bookkeeping beyond
+    // this one table, rebuilt fresh on every reconnect.
+    let mut subscriptions: Vec<VaultSubscription> = Vec::with_capacity(TRACKED_POOLS.len() * 2);
+    for &(token_address, base_vault, base_decimals, quote_vault) in TRACKED_POOLS {
+        subscriptions.push(VaultSubscription { token_address, side: VaultSide::Base, base_decimals });
+        subscriptions.push(VaultSubscription { token_address, side: VaultSide::Quote, base_decimals });
+
+        let base_req_id = subscriptions.len() as u64 - 1;
+        let quote_req_id = subscriptions.len() as u64;
+        write
+            .send(Message::Text(account_subscribe_request(base_req_id, base_vault)))
+            .await
+            .context("Failed to send base vault accountSubscribe")?;
+        write
+            .send(Message::Text(account_subscribe_request(quote_req_id, quote_vault)))
+            .await
+            .context("Failed to send quote vault accountSubscribe")?;
+    }
+
+    let mut sub_id_to_vault: HashMap<u64, VaultSubscription> = HashMap::new();
+
+    loop {
+        let Some(frame) = read.next().await else {
+            return Ok(()); // socket closed cleanly
+        };
+        let text = match frame.context("Helius websocket read error")? {
+            Message::Text(text) => text,
+            Message::Binary(_) | Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => continue,
+            Message::Close(_) => return Ok(()),
+        };
+
+        if let Ok(ack) = serde_json::from_str::<SubscribeAck>(&text) {
+            // Subscribe requests were sent with id = their 1-based index into
+            // `subscriptions`.
+            if let Some(vault) = subscriptions.get((ack.id - 1) as usize) {
+                sub_id_to_vault.insert(ack.result, vault.clone());
+            }
+            continue;
+        }
+
+        let Ok(notification) = serde_json::from_str::<AccountNotification>(&text) else {
+            continue;
+        };
+        let Some(vault) = sub_id_to_vault.get(&notification.params.subscription) else {
+            continue;
+        };
+
+        handle_account_write(tx, sol_price_cache, pool_states, vault, notification).await;
+    }
+}
+
+fn account_subscribe_request(id: u64, account: &str) -> String {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "accountSubscribe",
+        "params": [account, {"encoding": "base64", "commitment": "confirmed"}],
+    })
+    .to_string()
+}
+
+async fn handle_account_write(
+    tx: &mpsc::Sender<MarketEvent>,
+    sol_price_cache: &SolPriceCache,
+    pool_states: &Arc<Mutex<HashMap<&'static str, PoolState>>>,
+    vault: &VaultSubscription,
+    notification: AccountNotification,
+) {
+    let slot = notification.params.result.context.slot;
+    let write_version = notification.params.result.value.write_version;
+
+    let amount = match decode_token_amount(&notification.params.result.value.data.0) {
+        Ok(amount) => amount,
+        Err(e) => {
+            warn!(token_address = vault.token_address, "Failed to decode vault account data: {}", e);
+            return;
+        }
+    };
+
+    let mut states = pool_states.lock().await;
+    let state = states.entry(vault.token_address).or_default();
+
+    if state.is_stale(slot, write_version) {
+        warn!(
+            token_address = vault.token_address,
+            slot, write_version, "Dropping out-of-order Helius account write"
+        );
+        return;
+    }
+    state.last_slot = slot;
+    state.last_write_version = write_version;
+    match vault.side {
+        VaultSide::Base => state.base_reserve_raw = Some(amount),
+        VaultSide::Quote => state.quote_reserve_lamports = Some(amount),
+    }
+
+    let (Some(base_reserve_raw), Some(quote_reserve_lamports)) = (state.base_reserve_raw, state.quote_reserve_lamports)
+    else {
+        // Only one side of the pool has reported in so far; wait for the other.
+        return;
+    };
+    drop(states);
+
+    if base_reserve_raw == 0 {
+        return;
+    }
+    let sol_price_usd = *sol_price_cache.read().await;
+    let quote_value_usd = quote_reserve_lamports as f64 / 1e9 * sol_price_usd;
+    let price_usd = quote_value_usd / (base_reserve_raw as f64 / 10f64.powi(vault.base_decimals as i32));
+
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    if let Err(e) = tx
+        .send(MarketEvent::Price(PriceTick {
+            token_address: vault.token_address.to_string(),
+            price_usd,
+            volume_usd_1m: 0.0,
+            timestamp_ms: now_ms,
+        }))
+        .await
+    {
+        error!("Failed to send PriceTick from Helius: {}", e);
+    }
+
+    // A constant-product pool implies a single reserve-derived price rather
+    // than a real bid/ask spread, so both sides of the depth quote are set
+    // to the same pool-implied values.
+    if let Err(e) = tx
+        .send(MarketEvent::Depth(DepthEvent {
+            token_address: vault.token_address.to_string(),
+            bid_price: price_usd,
+            ask_price: price_usd,
+            bid_size_usd: quote_value_usd,
+            ask_size_usd: quote_value_usd,
+        }))
+        .await
+    {
+        error!("Failed to send DepthEvent from Helius: {}", e);
+    }
+}
+
+/// Decode an SPL token account's `amount` field (u64 LE at byte offset 64)
+/// out of its base64-encoded account data.
+fn decode_token_amount(data_b64: &str) -> Result<u64> {
+    use base64::Engine;
+    let raw = base64::engine::general_purpose::STANDARD
+        .decode(data_b64)
+        .context("Invalid base64 account data")?;
+    let amount_bytes: [u8; 8] = raw
+        .get(64..72)
+        .ok_or_else(|| anyhow!("Account data too short for an SPL token account"))?
+        .try_into()
+        .context("Malformed amount field")?;
+    Ok(u64::from_le_bytes(amount_bytes))
+}
@@ -1,7 +1,18 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use shared_models::MarketEvent;
-use tokio::sync::mpsc;
+use std::sync::Arc;
+use tokio::sync::{mpsc, RwLock};
+
+pub mod farcaster_consumer;
+pub mod helius_consumer;
+pub mod pyth_consumer;
+pub mod streaming;
+
+/// Last-known SOL/USD price, kept fresh by `pyth_consumer` and read by any
+/// other provider (e.g. `helius_consumer`) that needs to convert a
+/// SOL-denominated reserve into a USD value but has no oracle of its own.
+pub type SolPriceCache = Arc<RwLock<f64>>;
 
 #[async_trait]
 pub trait DataProvider: Send + Sync {
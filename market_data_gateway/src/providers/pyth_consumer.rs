@@ -1,37 +1,190 @@
 use crate::config::CONFIG;
+use crate::providers::SolPriceCache;
 use anyhow::{anyhow, Result};
-use async_trait::async_trait;
-use reqwest::Client;
-use serde_json::Value;
-use shared_models::{MarketEvent, SolPriceEvent, FundingEvent};
-use tokio::sync::mpsc;
-use tracing::{error, info, warn};
+use futures_util::StreamExt;
+use serde::Deserialize;
+use shared_models::{FundingEvent, MarketEvent, SolPriceEvent};
 use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use tracing::{error, info, warn};
 
 pub struct PythConsumer;
 
-pub async fn run(tx: mpsc::Sender<MarketEvent>) -> Result<()> {
-    info!("Starting Pyth Data Consumer...");
-    let client = Client::new();
-    let pyth_api_key = CONFIG.pyth_api_key.clone();
+/// Raw price update as reported by Hermes' `/v2/updates/price/stream` SSE feed.
+#[derive(Debug, Deserialize)]
+struct HermesStreamUpdate {
+    parsed: Vec<HermesParsedPrice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HermesParsedPrice {
+    id: String,
+    price: HermesPrice,
+}
+
+#[derive(Debug, Deserialize)]
+struct HermesPrice {
+    price: String,
+    conf: String,
+    expo: i32,
+    publish_time: i64,
+}
+
+/// Normalize a Pyth fixed-point `(price, expo)` pair into a USD `f64`.
+fn normalize(price: &str, expo: i32) -> Result<f64> {
+    let raw: i64 = price.parse()?;
+    Ok(raw as f64 * 10f64.powi(expo))
+}
+
+/// Fraction of price above which we distrust a Hermes update's confidence interval
+/// and drop it rather than forward a low-quality tick to the risk manager.
+const MAX_CONF_RATIO: f64 = 0.02;
+
+pub async fn run(
+    tx: mpsc::Sender<MarketEvent>,
+    sol_price_cache: SolPriceCache,
+    mut shutdown: watch::Receiver<bool>,
+) -> Result<()> {
+    info!("Starting Pyth/Hermes Data Consumer...");
+
+    let mut backoff = Duration::from_secs(1);
+    const MAX_BACKOFF: Duration = Duration::from_secs(30);
 
     loop {
-        // SOL price updates
-        let sol_price = 150.0 + (rand::random::<f64>() * 20.0 - 10.0);
-        if let Err(e) = tx.send(MarketEvent::SolPrice(SolPriceEvent { 
-            price_usd: sol_price 
-        })).await {
+        tokio::select! {
+            result = stream_once(&tx, &sol_price_cache) => {
+                match result {
+                    Ok(()) => warn!("Pyth/Hermes stream ended cleanly, reconnecting..."),
+                    Err(e) => error!("Pyth/Hermes stream error: {}, reconnecting in {:?}", e, backoff),
+                }
+            }
+            _ = shutdown.changed() => {
+                info!("Shutdown requested, stopping Pyth/Hermes consumer");
+                return Ok(());
+            }
+        }
+
+        if *shutdown.borrow() {
+            info!("Shutdown requested, stopping Pyth/Hermes consumer");
+            return Ok(());
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(backoff) => {}
+            _ = shutdown.changed() => {
+                info!("Shutdown requested during backoff, stopping Pyth/Hermes consumer");
+                return Ok(());
+            }
+        }
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+async fn stream_once(tx: &mpsc::Sender<MarketEvent>, sol_price_cache: &SolPriceCache) -> Result<()> {
+    let client = reqwest::Client::new();
+    let price_feed_ids = [
+        // SOL/USD
+        "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56",
+    ];
+
+    let mut url = reqwest::Url::parse(&format!("{}/v2/updates/price/stream", CONFIG.pyth_hermes_url))?;
+    {
+        let mut qp = url.query_pairs_mut();
+        for id in price_feed_ids {
+            qp.append_pair("ids[]", id);
+        }
+    }
+
+    let response = client
+        .get(url)
+        .header("Accept", "text/event-stream")
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let mut stream = response.bytes_stream();
+    let mut buffer = String::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(newline_pos) = buffer.find('\n') {
+            let line = buffer[..newline_pos].trim_end_matches('\r').to_string();
+            buffer.drain(..=newline_pos);
+
+            // SSE frames look like "data: {...json...}"; ignore keep-alives/comments.
+            let Some(data) = line.strip_prefix("data:") else {
+                continue;
+            };
+            let data = data.trim();
+            if data.is_empty() {
+                continue;
+            }
+
+            match serde_json::from_str::<HermesStreamUpdate>(data) {
+                Ok(update) => handle_update(tx, sol_price_cache, update).await,
+                Err(e) => warn!("Failed to parse Hermes SSE frame: {}", e),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_update(tx: &mpsc::Sender<MarketEvent>, sol_price_cache: &SolPriceCache, update: HermesStreamUpdate) {
+    for parsed in update.parsed {
+        let price_usd = match normalize(&parsed.price.price, parsed.price.expo) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!(feed_id = %parsed.id, "Failed to normalize Pyth price: {}", e);
+                continue;
+            }
+        };
+        let conf_usd = match normalize(&parsed.price.conf, parsed.price.expo) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+
+        if price_usd <= 0.0 {
+            continue;
+        }
+        if conf_usd / price_usd > MAX_CONF_RATIO {
+            warn!(
+                feed_id = %parsed.id,
+                price_usd,
+                conf_usd,
+                "Dropping low-quality Pyth update: confidence interval exceeds threshold"
+            );
+            continue;
+        }
+
+        *sol_price_cache.write().await = price_usd;
+
+        if let Err(e) = tx
+            .send(MarketEvent::SolPrice(SolPriceEvent {
+                price_usd,
+                publish_time: parsed.price.publish_time,
+            }))
+            .await
+        {
             error!("Failed to send SolPriceEvent: {}", e);
         }
+    }
+}
 
-        // Funding rate events
-        if rand::random::<f64>() < 0.2 {
-            if let Err(e) = tx.send(MarketEvent::Funding(FundingEvent {
+/// Demo funding-rate emitter, not called anywhere: Hermes doesn't publish
+/// funding rates, so this consumer emits no `Funding` events.
+#[allow(dead_code)]
+async fn emit_demo_funding(tx: &mpsc::Sender<MarketEvent>) {
+    if rand::random::<f64>() < 0.2 {
+        let _ = tx
+            .send(MarketEvent::Funding(FundingEvent {
                 token_address: "So11111111111111111111111111111111111111112".to_string(),
                 funding_rate_pct: (rand::random::<f64>() * 0.02 - 0.01) * 100.0,
-            })).await { error!("Failed to send FundingEvent: {}", e); }
-        }
-
-        tokio::time::sleep(Duration::from_secs(5)).await;
+                timestamp_ms: chrono::Utc::now().timestamp_millis(),
+            }))
+            .await
+            .map_err(|e| anyhow!("{}", e));
     }
 }
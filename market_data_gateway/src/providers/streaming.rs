@@ -0,0 +1,115 @@
+//! Generic resilient WebSocket wrapper for the real-feed consumers (Pyth's
+//! SSE stream rolls its own backoff loop since it isn't a WebSocket, and so
+//! does the Helius consumer; the Farcaster consumer uses this one).
+//!
+//! On connect, sends every message in `subscribe_messages`. On read error,
+//! closed socket, or no frame (data or control) arriving within
+//! `idle_timeout`, the connection is torn down and re-established with
+//! exponential backoff + jitter, re-sending the subscription requests.
+//! Non-text frames (ping/pong/close/raw) are consumed and dropped here so
+//! callers only ever see real data frames in `on_message`.
+use crate::DataValidationMetrics;
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{error, info, warn};
+
+pub const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+pub const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+pub struct StreamingProvider<F> {
+    pub name: &'static str,
+    pub url: String,
+    pub subscribe_messages: Vec<String>,
+    /// Force a reconnect if no frame has arrived within this window, rather
+    /// than waiting for the next consumer call to notice a dead socket.
+    pub idle_timeout: Duration,
+    pub on_message: F,
+}
+
+impl<F, Fut> StreamingProvider<F>
+where
+    F: Fn(String) -> Fut + Send + Sync,
+    Fut: Future<Output = ()> + Send,
+{
+    /// Run until `shutdown` is signalled: connect, subscribe, read frames
+    /// until the socket closes, errors, or goes idle, then reconnect with
+    /// exponential backoff. Returns promptly (instead of mid-backoff-sleep or
+    /// mid-read) once a shutdown is requested.
+    pub async fn run(&self, metrics: Option<&DataValidationMetrics>, mut shutdown: watch::Receiver<bool>) -> Result<()> {
+        let mut backoff = INITIAL_BACKOFF;
+
+        loop {
+            tokio::select! {
+                result = self.connect_and_stream(&mut shutdown) => {
+                    match result {
+                        Ok(()) => warn!(provider = self.name, "stream ended cleanly, reconnecting..."),
+                        Err(e) => error!(provider = self.name, "stream error: {}, reconnecting in {:?}", e, backoff),
+                    }
+                }
+                _ = shutdown.changed() => {
+                    info!(provider = self.name, "shutdown requested, stopping stream");
+                    return Ok(());
+                }
+            }
+
+            if *shutdown.borrow() {
+                info!(provider = self.name, "shutdown requested, stopping stream");
+                return Ok(());
+            }
+
+            if let Some(metrics) = metrics {
+                metrics.reconnect_count.inc();
+            }
+
+            let jitter = Duration::from_millis(rand::random::<u64>() % 250);
+            tokio::select! {
+                _ = tokio::time::sleep(backoff + jitter) => {}
+                _ = shutdown.changed() => {
+                    info!(provider = self.name, "shutdown requested during backoff, stopping stream");
+                    return Ok(());
+                }
+            }
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+
+    async fn connect_and_stream(&self, shutdown: &mut watch::Receiver<bool>) -> Result<()> {
+        let (ws_stream, _) = connect_async(&self.url)
+            .await
+            .with_context(|| format!("Failed to connect to {}", self.url))?;
+        info!(provider = self.name, url = %self.url, "connected");
+
+        let (mut write, mut read) = ws_stream.split();
+        for sub in &self.subscribe_messages {
+            write
+                .send(Message::Text(sub.clone()))
+                .await
+                .context("Failed to send subscription request")?;
+        }
+
+        loop {
+            let next = tokio::select! {
+                next = tokio::time::timeout(self.idle_timeout, read.next()) => {
+                    next.context("No message received within idle window, forcing reconnect")?
+                }
+                _ = shutdown.changed() => return Ok(()),
+            };
+
+            let Some(frame) = next else {
+                return Ok(()); // socket closed cleanly
+            };
+
+            match frame.context("WebSocket read error")? {
+                Message::Text(text) => (self.on_message)(text).await,
+                // Ping/Pong/Close/raw Frame carry no market data; tungstenite
+                // already answers pings automatically.
+                Message::Binary(_) | Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => {}
+                Message::Close(_) => return Ok(()),
+            }
+        }
+    }
+}
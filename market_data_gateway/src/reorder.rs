@@ -0,0 +1,105 @@
+//! Per-token sequence-ordering buffer sitting in front of the Redis publish
+//! step. Real feeds (price ticks, SOL/USD updates) can deliver updates out
+//! of order during reconnects/hiccups; without this, a late-but-stale update
+//! could overwrite a fresher price already seen by strategies.
+use shared_models::MarketEvent;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How long an event is held waiting for any earlier-sequenced sibling to
+/// show up before being released (or dropped as stale) anyway.
+pub const REORDER_WINDOW: Duration = Duration::from_millis(250);
+
+struct TokenState {
+    last_applied_seq: i64,
+    pending: Vec<(i64, MarketEvent)>,
+    held_since: Instant,
+}
+
+/// Result of offering an event to the buffer.
+pub enum Admit {
+    /// Apply these events now, already sorted into sequence order.
+    Ready(Vec<MarketEvent>),
+    /// Held in the reorder window; nothing to apply yet.
+    Held,
+    /// At or behind the last-applied sequence for this token; drop it.
+    Stale,
+}
+
+#[derive(Default)]
+pub struct ReorderBuffer {
+    tokens: HashMap<String, TokenState>,
+}
+
+impl ReorderBuffer {
+    /// Offer `event` (keyed by `MarketEvent::token`) with ordering key `seq`
+    /// (`MarketEvent::sequence`). Every event for a token is briefly held so
+    /// a late-arriving-but-older update can't win a race against one already
+    /// queued ahead of it; the window is short enough not to meaningfully
+    /// delay strategies.
+    pub fn admit(&mut self, event: MarketEvent, seq: i64) -> Admit {
+        let token = event.token().to_string();
+        let state = self.tokens.entry(token).or_insert_with(|| TokenState {
+            last_applied_seq: i64::MIN,
+            pending: Vec::new(),
+            held_since: Instant::now(),
+        });
+
+        if seq <= state.last_applied_seq {
+            return Admit::Stale;
+        }
+
+        if state.pending.is_empty() {
+            state.held_since = Instant::now();
+        }
+        state.pending.push((seq, event));
+
+        if state.held_since.elapsed() >= REORDER_WINDOW {
+            Admit::Ready(Self::drain(state))
+        } else {
+            Admit::Held
+        }
+    }
+
+    /// Release any token's pending events whose reorder window has elapsed,
+    /// even if nothing new has arrived to trigger the check in `admit`.
+    pub fn flush_expired(&mut self) -> Vec<MarketEvent> {
+        let mut ready = Vec::new();
+        for state in self.tokens.values_mut() {
+            if !state.pending.is_empty() && state.held_since.elapsed() >= REORDER_WINDOW {
+                ready.extend(Self::drain(state));
+            }
+        }
+        ready
+    }
+
+    /// Release every token's pending events regardless of how long they've
+    /// been held, so a graceful shutdown can publish everything buffered
+    /// instead of waiting out the reorder window or dropping it on exit.
+    pub fn flush_all(&mut self) -> Vec<MarketEvent> {
+        let mut ready = Vec::new();
+        for state in self.tokens.values_mut() {
+            if !state.pending.is_empty() {
+                ready.extend(Self::drain(state));
+            }
+        }
+        ready
+    }
+
+    /// Sort a token's pending events by sequence, drop any that a sibling in
+    /// the same batch already superseded, and advance `last_applied_seq`.
+    fn drain(state: &mut TokenState) -> Vec<MarketEvent> {
+        let mut pending = std::mem::take(&mut state.pending);
+        pending.sort_by_key(|(seq, _)| *seq);
+
+        let mut ready = Vec::with_capacity(pending.len());
+        for (seq, event) in pending {
+            if seq <= state.last_applied_seq {
+                continue;
+            }
+            state.last_applied_seq = seq;
+            ready.push(event);
+        }
+        ready
+    }
+}
@@ -9,6 +9,35 @@ pub struct Config {
     pub min_sharpe_for_promotion: f64,
     pub strategy_promotion_interval_secs: u64,
     pub rebalance_interval_secs: u64,
+    /// Per-bar cap, as a percent of the current `stable_price`, on how far
+    /// `SanityChecker`'s dampened price track may move toward the raw close
+    /// in a single bar. Bounds how much a one-bar flash spike or manipulated
+    /// wick can contribute to the stable-price backtest.
+    pub sanity_stable_price_growth_limit_pct: f64,
+    /// Maximum allowed gap, in percentage points of total return, between
+    /// `SanityChecker`'s raw-price and stable-price backtests before
+    /// `cross_validate` fails the strategy for relying on prices a dampened
+    /// track wouldn't have confirmed.
+    pub sanity_stable_return_divergence_limit_pct: f64,
+    /// `k` in `weight_factor = 1 - k*max_drawdown` for `StrategyState::init_weight`,
+    /// the health figure that discounts new/increased allocation sizing.
+    pub init_health_drawdown_k: f64,
+    /// `k` in the same formula for `StrategyState::maint_weight`, the
+    /// figure the executor sizes live positions against. Whether a strategy
+    /// keeps any allocation at all is gated by `maint_drawdown_limit` below.
+    pub maint_health_drawdown_k: f64,
+    /// Hard drawdown threshold, as a fraction of a strategy's own
+    /// high-water mark, beyond which `calculate_allocations` force-zeros its
+    /// allocation outright rather than merely discounting it via
+    /// `maint_weight()`. Checked directly against `max_drawdown` instead of
+    /// via `maint_weight()`'s sign, since that weight is clamped to `[0, 1]`
+    /// and can never flip the sign of a still-positive Sharpe ratio.
+    pub maint_drawdown_limit: f64,
+    /// Seed value for `StateManager`'s live-adjustable cap on how many
+    /// Paper/Live strategies `calculate_allocations` will fund at once.
+    /// Operators move the live value with `increase_active_count`/
+    /// `scale_active_count` governance commands rather than redeploying.
+    pub ideal_active_strategies: usize,
 }
 
 impl Config {
@@ -34,6 +63,30 @@ impl Config {
                 .unwrap_or_else(|_| "300".to_string())
                 .parse()
                 .context("REBALANCE_INTERVAL_SECS must be a valid number")?,
+            sanity_stable_price_growth_limit_pct: env::var("SANITY_STABLE_PRICE_GROWTH_LIMIT_PCT")
+                .unwrap_or_else(|_| "5.0".to_string())
+                .parse()
+                .context("SANITY_STABLE_PRICE_GROWTH_LIMIT_PCT must be a valid number")?,
+            sanity_stable_return_divergence_limit_pct: env::var("SANITY_STABLE_RETURN_DIVERGENCE_LIMIT_PCT")
+                .unwrap_or_else(|_| "20.0".to_string())
+                .parse()
+                .context("SANITY_STABLE_RETURN_DIVERGENCE_LIMIT_PCT must be a valid number")?,
+            init_health_drawdown_k: env::var("INIT_HEALTH_DRAWDOWN_K")
+                .unwrap_or_else(|_| "1.0".to_string())
+                .parse()
+                .context("INIT_HEALTH_DRAWDOWN_K must be a valid number")?,
+            maint_health_drawdown_k: env::var("MAINT_HEALTH_DRAWDOWN_K")
+                .unwrap_or_else(|_| "0.3".to_string())
+                .parse()
+                .context("MAINT_HEALTH_DRAWDOWN_K must be a valid number")?,
+            maint_drawdown_limit: env::var("MAINT_DRAWDOWN_LIMIT")
+                .unwrap_or_else(|_| "0.5".to_string())
+                .parse()
+                .context("MAINT_DRAWDOWN_LIMIT must be a valid number")?,
+            ideal_active_strategies: env::var("IDEAL_ACTIVE_STRATEGIES")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .context("IDEAL_ACTIVE_STRATEGIES must be a valid number")?,
         })
     }
 }
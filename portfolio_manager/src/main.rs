@@ -12,23 +12,36 @@ use tokio::sync::Mutex;
 use tokio::time::interval;
 use tracing::{debug, error, info, warn};
 use serde_json;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use config::CONFIG;
 use state_manager::{StrategyState, StateManager};
 
 // Add required dependencies for time and random generation
 
+const STRATEGY_SPECS_STREAM: &str = "strategy_specs";
+const GOVERNANCE_COMMANDS_STREAM: &str = "governance_commands";
+
+/// An operator-issued adjustment to `StateManager`'s live
+/// `ideal_active_strategies` cap, read off `GOVERNANCE_COMMANDS_STREAM`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum GovernanceCommand {
+    IncreaseActiveCount { n: i64 },
+    ScaleActiveCount { factor: f64 },
+}
+
 async fn process_new_strategy_submissions(
     conn: &mut redis::aio::MultiplexedConnection,
     backtest_client: &backtest_client::BacktestClient,
     _pending_backtests: Arc<Mutex<HashMap<String, PendingBacktest>>>,
+    state_manager: &mut StateManager,
     last_id: &mut String,
 ) -> Result<()> {
     // Read new strategy specs from Redis stream
     debug!("Reading strategy_specs stream starting from ID: {}", last_id);
     let stream_result: redis::RedisResult<Vec<redis::streams::StreamReadReply>> = conn.xread_options(
-        &["strategy_specs"],
+        &[STRATEGY_SPECS_STREAM],
         &[last_id.as_str()],
         &redis::streams::StreamReadOptions::default().count(10)
     ).await;
@@ -44,30 +57,44 @@ async fn process_new_strategy_submissions(
                         // Update last seen ID
                         *last_id = stream_id.id.clone();
                         debug!("Processing message ID: {}", stream_id.id);
-                        
+
                         if let Some(spec_json) = stream_id.map.get("spec") {
                             if let Ok(spec_str) = redis::from_redis_value::<String>(spec_json) {
                                 match serde_json::from_str::<StrategySpec>(&spec_str) {
                                 Ok(strategy_spec) => {
                                     info!("📋 Processing new strategy spec: {}", strategy_spec.id);
-                                    
+
+                                    state_manager
+                                        .discover_strategy(conn, strategy_spec.clone(), STRATEGY_SPECS_STREAM, stream_id.id.clone())
+                                        .await
+                                        .context("Failed to record StrategyDiscovered event")?;
+
                                     // If strategy has good fitness score, allocate capital immediately
                                     if strategy_spec.fitness > 0.6 {
                                         info!("🚀 High-fitness strategy detected: {} (fitness: {:.3})", strategy_spec.id, strategy_spec.fitness);
-                                        
+
                                         // Create allocation for high-performing strategy
+                                        let weight = (strategy_spec.fitness * 0.1).min(0.05); // Max 5% allocation
                                         let allocation = shared_models::StrategyAllocation {
                                             id: strategy_spec.id.clone(),
-                                            weight: (strategy_spec.fitness * 0.1).min(0.05), // Max 5% allocation
+                                            weight,
                                             sharpe_ratio: strategy_spec.fitness * 2.0, // Approximate Sharpe from fitness
-                                            mode: if strategy_spec.fitness > 0.8 { 
-                                                shared_models::TradeMode::Paper 
-                                            } else { 
-                                                shared_models::TradeMode::Simulating 
+                                            mode: if strategy_spec.fitness > 0.8 {
+                                                shared_models::TradeMode::Paper
+                                            } else {
+                                                shared_models::TradeMode::Simulating
                                             },
                                             params: strategy_spec.params.clone(),
+                                            // Brand new strategy, no drawdown history yet.
+                                            init_health: 1.0,
+                                            maint_health: 1.0,
                                         };
-                                        
+
+                                        state_manager
+                                            .update_allocation(conn, &strategy_spec.id, weight * state_manager.get_total_capital())
+                                            .await
+                                            .context("Failed to record AllocationChanged event")?;
+
                                         // Publish allocation to executor
                                         let allocations = vec![allocation];
                                         let allocations_json = serde_json::to_string(&allocations)?;
@@ -76,29 +103,38 @@ async fn process_new_strategy_submissions(
                                             "*",
                                             &[("allocations", allocations_json)]
                                         ).await?;
-                                        
+
                                         info!("💰 Allocated capital to strategy: {}", strategy_spec.id);
                                     }
-                                    
+
                                     // Submit strategy for backtesting
                                     match backtest_client.submit_backtest(&strategy_spec).await {
                                         Ok(result) => {
                                             // Update fitness based on backtest result
                                             let mut updated_spec = strategy_spec.clone();
                                             updated_spec.fitness = result.sharpe_ratio.max(0.1); // Ensure minimum fitness
-                                            
+
                                             // Send updated strategy to executor
+                                            let weight = 0.1; // Start with 10% weight
                                             let allocation = StrategyAllocation {
                                                 id: updated_spec.id.clone(),
-                                                weight: 0.1, // Start with 10% weight
+                                                weight,
                                                 sharpe_ratio: result.sharpe_ratio.max(0.1), // Ensure minimum sharpe
                                                 mode: TradeMode::Simulating,
                                                 params: updated_spec.params.clone(),
+                                                // Brand new strategy, no drawdown history yet.
+                                                init_health: 1.0,
+                                                maint_health: 1.0,
                                             };
-                                            
+
+                                            state_manager
+                                                .update_allocation(conn, &updated_spec.id, weight * state_manager.get_total_capital())
+                                                .await
+                                                .context("Failed to record AllocationChanged event")?;
+
                                             let allocation_json = serde_json::to_string(&allocation)
                                                 .context("Failed to serialize allocation")?;
-                                            
+
                                             let _: () = conn.xadd(
                                                 "allocations_channel",
                                                 "*",
@@ -107,7 +143,7 @@ async fn process_new_strategy_submissions(
                                                 error!("Failed to publish allocation: {}", e);
                                                 e
                                             })?;
-                                            
+
                                             info!("✅ Strategy {} evaluated with Sharpe {:.2}, allocated capital", updated_spec.id, result.sharpe_ratio);
                                         }
                                         Err(e) => {
@@ -137,13 +173,75 @@ async fn process_new_strategy_submissions(
     Ok(())
 }
 
+/// Reads operator-issued governance commands off `GOVERNANCE_COMMANDS_STREAM`
+/// and applies them to the live `ideal_active_strategies` cap, so an
+/// operator can concentrate or diversify capital without redeploying the
+/// portfolio manager.
+async fn process_governance_commands(
+    conn: &mut redis::aio::MultiplexedConnection,
+    state_manager: &mut StateManager,
+    last_id: &mut String,
+) -> Result<()> {
+    debug!("Reading governance_commands stream starting from ID: {}", last_id);
+    let stream_result: redis::RedisResult<Vec<redis::streams::StreamReadReply>> = conn.xread_options(
+        &[GOVERNANCE_COMMANDS_STREAM],
+        &[last_id.as_str()],
+        &redis::streams::StreamReadOptions::default().count(10)
+    ).await;
+
+    match stream_result {
+        Ok(replies) => {
+            for reply in replies {
+                for stream_key in reply.keys {
+                    for stream_id in stream_key.ids {
+                        *last_id = stream_id.id.clone();
+
+                        let Some(command_value) = stream_id.map.get("command") else {
+                            continue;
+                        };
+                        let Ok(command_str) = redis::from_redis_value::<String>(command_value) else {
+                            error!("Failed to convert governance command to string");
+                            continue;
+                        };
+                        match serde_json::from_str::<GovernanceCommand>(&command_str) {
+                            Ok(GovernanceCommand::IncreaseActiveCount { n }) => {
+                                state_manager
+                                    .increase_active_count(conn, n, GOVERNANCE_COMMANDS_STREAM, stream_id.id.clone())
+                                    .await
+                                    .context("Failed to record GovernanceCountChanged event")?;
+                                info!("🎛️ Governance: ideal_active_strategies adjusted by {} to {}", n, state_manager.get_ideal_active_strategies());
+                            }
+                            Ok(GovernanceCommand::ScaleActiveCount { factor }) => {
+                                state_manager
+                                    .scale_active_count(conn, factor, GOVERNANCE_COMMANDS_STREAM, stream_id.id.clone())
+                                    .await
+                                    .context("Failed to record GovernanceCountChanged event")?;
+                                info!("🎛️ Governance: ideal_active_strategies scaled by {} to {}", factor, state_manager.get_ideal_active_strategies());
+                            }
+                            Err(e) => {
+                                error!("Failed to parse governance command from Redis: {}", e);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            debug!("No new governance commands in stream or error reading (last_id: {}): {}", last_id, e);
+        }
+    }
+
+    Ok(())
+}
+
 // In-house sanity checker for cross-validating external backtest results
 mod sanity_checker {
+    use crate::config::CONFIG;
     use anyhow::Result;
     use serde::{Deserialize, Serialize};
     use std::collections::HashMap;
     use tracing::{info, warn};
-    
+
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct OHLCVData {
         pub timestamp: i64,
@@ -153,7 +251,7 @@ mod sanity_checker {
         pub close: f64,
         pub volume_usd: f64,
     }
-    
+
     #[derive(Debug, Clone)]
     pub struct SimpleBacktestResult {
         pub total_return: f64,
@@ -162,60 +260,104 @@ mod sanity_checker {
         pub trade_count: u32,
         pub win_rate: f64,
     }
-    
+
+    /// The momentum simulation's result using the raw oracle `close` price
+    /// throughout, alongside the same simulation run against a dampened
+    /// `stable_price` track that can't follow a one-bar flash spike or
+    /// manipulated wick. A strategy whose edge only exists in `raw` and
+    /// evaporates in `stable` is exactly what `cross_validate` should reject.
+    #[derive(Debug, Clone)]
+    pub struct ValidationResult {
+        pub raw: SimpleBacktestResult,
+        pub stable: SimpleBacktestResult,
+    }
+
     pub struct SanityChecker {
         // Store minimal historical data for validation
         price_data: HashMap<String, Vec<OHLCVData>>,
     }
-    
+
     impl SanityChecker {
         pub fn new() -> Self {
             Self {
                 price_data: HashMap::new(),
             }
         }
-        
-        // Simplified strategy simulation for sanity checking
-        pub fn validate_strategy(&self, _strategy_params: &serde_json::Value, token: &str) -> Result<SimpleBacktestResult> {
-            let data = self.price_data.get(token).ok_or_else(|| anyhow::anyhow!("No data for token {}", token))?;
-            
-            if data.len() < 10 {
-                return Err(anyhow::anyhow!("Insufficient data for validation"));
+
+        /// Builds a conservative "dampened" price track alongside the raw
+        /// `close` series: each bar, `stable_price` moves toward `close` by
+        /// at most `sanity_stable_price_growth_limit_pct` of its current
+        /// value, so a single manipulated wick can only ever nudge it instead
+        /// of teleporting it. Mirrors a stablecoin-style dampened-price peg.
+        fn compute_stable_prices(data: &[OHLCVData]) -> Vec<f64> {
+            let growth_limit = CONFIG.sanity_stable_price_growth_limit_pct / 100.0;
+            let mut stable_price = data[0].close;
+            let mut stable_prices = Vec::with_capacity(data.len());
+            stable_prices.push(stable_price);
+
+            for bar in &data[1..] {
+                let target = bar.close;
+                let max_move = growth_limit * stable_price;
+                stable_price += (target - stable_price).clamp(-max_move, max_move);
+                stable_prices.push(stable_price);
             }
-            
-            // Simplified momentum strategy simulation (this would be strategy-specific)
+
+            stable_prices
+        }
+
+        /// Simplified momentum strategy simulation (this would be
+        /// strategy-specific). Signal generation always reads the raw
+        /// `close` series, since the dampened track exists to value
+        /// entries/exits conservatively, not to hide real momentum. When
+        /// `stable_prices` is given, a position is marked at
+        /// `min(close, stable_price)` and entry cost is computed at
+        /// `max(close, stable_price)`, so a sudden favorable spike can't
+        /// register as realized gain until the stable track confirms it.
+        fn simulate_momentum(data: &[OHLCVData], stable_prices: Option<&[f64]>) -> SimpleBacktestResult {
             let mut capital = 1000.0;
             let mut position = 0.0;
             let mut trades = Vec::new();
             let mut peak_capital = capital;
             let mut max_drawdown = 0.0;
-            
+
+            let entry_price_at = |i: usize, close: f64| match stable_prices {
+                Some(stable) => close.max(stable[i]),
+                None => close,
+            };
+            let mark_price_at = |i: usize, close: f64| match stable_prices {
+                Some(stable) => close.min(stable[i]),
+                None => close,
+            };
+
             for i in 1..data.len() {
                 let prev_price = data[i-1].close;
                 let curr_price = data[i].close;
                 let price_change = (curr_price - prev_price) / prev_price;
-                
+
                 // Simple momentum signal
                 let signal = if price_change > 0.02 { 1.0 } else if price_change < -0.02 { -1.0 } else { 0.0 };
-                
+
                 // Simulate trade execution with realistic costs
                 if signal != 0.0 && position == 0.0 {
+                    let entry_price = entry_price_at(i, curr_price);
                     let trade_size = capital * 0.1; // 10% of capital per trade
                     let slippage_cost = trade_size * 0.003; // 0.3% slippage
-                    position = (trade_size - slippage_cost) / curr_price;
+                    position = (trade_size - slippage_cost) / entry_price;
                     capital -= trade_size;
-                    
-                    trades.push((i, signal, curr_price, trade_size));
+
+                    trades.push((i, signal, entry_price, trade_size));
                 } else if signal == 0.0 && position != 0.0 {
                     // Close position
-                    let trade_value = position * curr_price;
+                    let exit_price = mark_price_at(i, curr_price);
+                    let trade_value = position * exit_price;
                     let slippage_cost = trade_value * 0.003;
                     capital += trade_value - slippage_cost;
                     position = 0.0;
                 }
-                
+
                 // Track drawdown
-                let current_value = capital + (position * curr_price);
+                let mark_price = mark_price_at(i, curr_price);
+                let current_value = capital + (position * mark_price);
                 if current_value > peak_capital {
                     peak_capital = current_value;
                 } else {
@@ -225,15 +367,14 @@ mod sanity_checker {
                     }
                 }
             }
-            
+
             // Final position value
             if position != 0.0 {
-                let last_price = data.last()
-                    .map(|d| d.close)
-                    .unwrap_or(1.0);
+                let last_index = data.len() - 1;
+                let last_price = mark_price_at(last_index, data[last_index].close);
                 capital += position * last_price * 0.997; // Close with slippage
             }
-            
+
             let total_return = (capital - 1000.0) / 1000.0;
             let trade_count = trades.len() as u32;
             let wins = trades.iter().filter(|(idx, signal, entry_price, _)| {
@@ -244,25 +385,43 @@ mod sanity_checker {
                     false
                 }
             }).count();
-            
+
             let win_rate = if trade_count > 0 { wins as f64 / trade_count as f64 } else { 0.0 };
-            
+
             // Simplified Sharpe calculation (would need proper risk-free rate and volatility)
             let sharpe_ratio = if total_return > 0.0 && max_drawdown > 0.0 {
                 total_return / max_drawdown
             } else {
                 0.0
             };
-            
-            Ok(SimpleBacktestResult {
+
+            SimpleBacktestResult {
                 total_return,
                 sharpe_ratio,
                 max_drawdown,
                 trade_count,
                 win_rate,
-            })
+            }
         }
-        
+
+        // Simplified strategy simulation for sanity checking, run once
+        // against the raw oracle price and once against a dampened
+        // stable-price track so `cross_validate` can catch an edge that only
+        // exists because of a flash spike or manipulated wick.
+        pub fn validate_strategy(&self, _strategy_params: &serde_json::Value, token: &str) -> Result<ValidationResult> {
+            let data = self.price_data.get(token).ok_or_else(|| anyhow::anyhow!("No data for token {}", token))?;
+
+            if data.len() < 10 {
+                return Err(anyhow::anyhow!("Insufficient data for validation"));
+            }
+
+            let stable_prices = Self::compute_stable_prices(data);
+            let raw = Self::simulate_momentum(data, None);
+            let stable = Self::simulate_momentum(data, Some(&stable_prices));
+
+            Ok(ValidationResult { raw, stable })
+        }
+
         // Load historical data from CSV (budget-friendly data source)
         pub fn load_historical_data(&mut self, token: &str, csv_data: &str) -> Result<()> {
             let mut data = Vec::new();
@@ -289,30 +448,45 @@ mod sanity_checker {
             Ok(())
         }
         
-        // Cross-validate external backtest results with our internal results
-        pub fn cross_validate(&self, external_sharpe: f64, internal_result: &SimpleBacktestResult, strategy_id: &str) -> bool {
-            let sharpe_diff = (external_sharpe - internal_result.sharpe_ratio).abs();
+        // Cross-validate external backtest results with our internal results.
+        // `internal_result` carries both the raw-price and stable-price runs
+        // so a strategy whose edge only survives on raw, manipulable prices
+        // gets caught even when its raw Sharpe agrees with the external one.
+        pub fn cross_validate(&self, external_sharpe: f64, internal_result: &ValidationResult, strategy_id: &str) -> bool {
+            let raw = &internal_result.raw;
+            let stable = &internal_result.stable;
+
+            let sharpe_diff = (external_sharpe - raw.sharpe_ratio).abs();
             let max_acceptable_diff = 0.5; // Allow 0.5 Sharpe difference
-            
+
             if sharpe_diff > max_acceptable_diff {
                 warn!(
                     "❌ Strategy {} FAILED cross-validation: External Sharpe: {:.2}, Internal Sharpe: {:.2}, Diff: {:.2}",
-                    strategy_id, external_sharpe, internal_result.sharpe_ratio, sharpe_diff
+                    strategy_id, external_sharpe, raw.sharpe_ratio, sharpe_diff
                 );
                 return false;
             }
-            
-            if internal_result.total_return < -0.2 && external_sharpe > 1.0 {
+
+            if raw.total_return < -0.2 && external_sharpe > 1.0 {
                 warn!(
                     "❌ Strategy {} FAILED cross-validation: External claims positive Sharpe {:.2} but internal shows {:.2}% loss",
-                    strategy_id, external_sharpe, internal_result.total_return * 100.0
+                    strategy_id, external_sharpe, raw.total_return * 100.0
                 );
                 return false;
             }
-            
+
+            let return_divergence_pct = (raw.total_return - stable.total_return).abs() * 100.0;
+            if return_divergence_pct > CONFIG.sanity_stable_return_divergence_limit_pct {
+                warn!(
+                    "❌ Strategy {} FAILED cross-validation: raw-price return {:.2}% diverges {:.2}pp from stable-price return {:.2}%, edge likely depends on transient price spikes",
+                    strategy_id, raw.total_return * 100.0, return_divergence_pct, stable.total_return * 100.0
+                );
+                return false;
+            }
+
             info!(
-                "✅ Strategy {} PASSED cross-validation: External Sharpe: {:.2}, Internal Sharpe: {:.2}",
-                strategy_id, external_sharpe, internal_result.sharpe_ratio
+                "✅ Strategy {} PASSED cross-validation: External Sharpe: {:.2}, Internal Sharpe: {:.2} (stable-price: {:.2})",
+                strategy_id, external_sharpe, raw.sharpe_ratio, stable.sharpe_ratio
             );
             true
         }
@@ -357,7 +531,11 @@ async fn main() -> Result<()> {
     ).context("Failed to create backtest client")?);
     let pending_backtests = Arc::new(Mutex::new(HashMap::new()));
     let sanity_checker = Arc::new(Mutex::new(sanity_checker::SanityChecker::new()));
-    let _portfolio_state_manager = StateManager::new(CONFIG.initial_capital_usd);
+    let portfolio_state_manager = Arc::new(Mutex::new(
+        StateManager::new(&mut redis_conn, CONFIG.initial_capital_usd)
+            .await
+            .context("Failed to rebuild portfolio state from the event log")?,
+    ));
 
     // Spawn background tasks
     let _backtest_monitor_handle = tokio::spawn(monitor_backtest_jobs(
@@ -372,40 +550,92 @@ async fn main() -> Result<()> {
         pending_backtests.clone(),
     ));
 
-    // Main loop for processing new strategy submissions
-    let mut last_strategy_id = "0".to_string(); // Start from beginning
-    
+    // Keeps funded allocations from going stale: on startup this also
+    // catches anything that was already overdue while the manager was down.
+    let _rollover_monitor_handle = tokio::spawn(monitor_rollovers(
+        redis_client.clone(),
+        backtest_client.clone(),
+        sanity_checker.clone(),
+        portfolio_state_manager.clone(),
+    ));
+
+    // Main loop for processing new strategy submissions. The starting id is a
+    // projection over the event log, so a restart resumes exactly where the
+    // last run left off instead of re-processing the whole stream.
+    let mut last_strategy_id = {
+        let manager = portfolio_state_manager.lock().await;
+        manager.get_last_stream_id(STRATEGY_SPECS_STREAM).unwrap_or_else(|| "0".to_string())
+    };
+    let mut last_governance_id = {
+        let manager = portfolio_state_manager.lock().await;
+        manager.get_last_stream_id(GOVERNANCE_COMMANDS_STREAM).unwrap_or_else(|| "0".to_string())
+    };
+
     loop {
-        match process_new_strategy_submissions(
-            &mut redis_conn,
-            backtest_client.as_ref(),
-            pending_backtests.clone(),
-            &mut last_strategy_id,
-        )
-        .await
-        {
+        let submission_result = {
+            let mut manager = portfolio_state_manager.lock().await;
+            process_new_strategy_submissions(
+                &mut redis_conn,
+                backtest_client.as_ref(),
+                pending_backtests.clone(),
+                &mut manager,
+                &mut last_strategy_id,
+            )
+            .await
+        };
+        match submission_result {
             Ok(_) => info!("✅ Processed strategy submissions successfully"),
             Err(e) => error!("❌ Error processing new strategy submissions: {}", e),
         }
 
+        let governance_result = {
+            let mut manager = portfolio_state_manager.lock().await;
+            process_governance_commands(&mut redis_conn, &mut manager, &mut last_governance_id).await
+        };
+        if let Err(e) = governance_result {
+            error!("❌ Error processing governance commands: {}", e);
+        }
+
         tokio::time::sleep(Duration::from_secs(5)).await;
     }
 }
 
 /// Simulates performance updates for strategies.
 /// In a real system, this data would come from the position_manager.
-fn simulate_performance_updates(state_manager: &mut StateManager) {
-    for mut state in state_manager.get_all_strategy_states_mut() {
+async fn simulate_performance_updates(
+    conn: &mut redis::aio::MultiplexedConnection,
+    state_manager: &mut StateManager,
+) -> Result<()> {
+    let strategy_ids: Vec<String> = state_manager.strategies.keys().cloned().collect();
+    for strategy_id in strategy_ids {
         // Simulate some random performance
         let random_pnl = (rand::random::<f64>() - 0.45) * 100.0; // Skew towards positive
-        state.realized_pnl += random_pnl;
-        state.sharpe_ratio = state.realized_pnl / (state.run_time_secs as f64 + 1.0); // Simplified Sharpe
-        info!("Simulated performance for {}: PnL ${:.2}, Sharpe {:.2}", state.spec.id, state.realized_pnl, state.sharpe_ratio);
+        let is_win = random_pnl > 0.0;
+        state_manager.update_strategy_performance(conn, &strategy_id, random_pnl, is_win).await?;
+        if let Some(state) = state_manager.strategies.get(&strategy_id) {
+            info!("Simulated performance for {}: PnL ${:.2}, Sharpe {:.2}", strategy_id, state.realized_pnl, state.sharpe_ratio);
+        }
     }
+    Ok(())
 }
 
-/// Calculates new capital allocations based on strategy performance.
-fn calculate_allocations(state_manager: &StateManager) -> Vec<StrategyAllocation> {
+/// Calculates new capital allocations based on strategy performance, weighted
+/// by a two-tier "health" model borrowed from margin-health accounting
+/// instead of raw Sharpe alone, so a high-Sharpe-but-high-drawdown strategy
+/// no longer gets sized the same as a smooth one.
+///
+/// Only the top `state_manager.get_ideal_active_strategies()` strategies by
+/// risk-adjusted score are funded; weights are renormalized over that
+/// top-N subset alone, so allocated capital sums to the full budget
+/// regardless of how many strategies are merely active; a funded strategy
+/// force-zeroed by a maintenance-health breach keeps its score in that
+/// total, so its share is left unallocated. Strategies cut by
+/// the cap get an explicit zero-weight allocation published immediately,
+/// the same as a maintenance-health breach.
+async fn calculate_allocations(
+    conn: &mut redis::aio::MultiplexedConnection,
+    state_manager: &StateManager,
+) -> Result<Vec<StrategyAllocation>> {
     let mut allocations = Vec::new();
     let total_capital = state_manager.get_total_capital();
 
@@ -417,42 +647,112 @@ fn calculate_allocations(state_manager: &StateManager) -> Vec<StrategyAllocation
         .collect();
 
     if active_strategies.is_empty() {
-        return allocations;
+        return Ok(allocations);
     }
 
-    // Performance-weighted allocation (e.g., based on Sharpe ratio)
-    let total_performance_score: f64 = active_strategies.iter().map(|s| s.sharpe_ratio.max(0.0)).sum();
+    // Initialization health discounts a strategy's Sharpe-based score by its
+    // realized drawdown, so new/increased allocations favor smoother equity
+    // curves over merely higher ones.
+    let mut adjusted_scores: Vec<(&StrategyState, f64)> = active_strategies
+        .iter()
+        .map(|state| (*state, state.sharpe_ratio.max(0.0) * state.init_weight()))
+        .collect();
+    adjusted_scores.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    let ideal_active_strategies = state_manager.get_ideal_active_strategies();
+    let cut = ideal_active_strategies.min(adjusted_scores.len());
+    let dropped = adjusted_scores.split_off(cut);
+    let funded = adjusted_scores;
 
-    if total_performance_score <= 0.0 {
-        info!("No strategies with positive performance score. No allocations will be made.");
-        return allocations;
+    let total_adjusted_score: f64 = funded.iter().map(|(_, score)| score).sum();
+
+    if total_adjusted_score <= 0.0 {
+        info!("No strategies with positive risk-adjusted score. No allocations will be made.");
+        return Ok(allocations);
     }
 
-    for state in active_strategies {
-        let weight = state.sharpe_ratio.max(0.0) / total_performance_score;
-        let capital_allocation = total_capital * weight;
+    for (state, adjusted_score) in funded {
+        // Maintenance health is the stricter gate: once a strategy's own
+        // drawdown from its high-water mark breaches the hard limit, its
+        // allocation is force-zeroed rather than merely shrunk, and the
+        // de-allocation is published immediately instead of waiting for the
+        // next normal rebalance to pick it up. Checked directly against
+        // `max_drawdown` rather than the sign of `sharpe_ratio *
+        // maint_weight()` — that weight is clamped to `[0, 1]`, so the
+        // product can only go negative when `sharpe_ratio` already is,
+        // leaving drawdown with no way to trigger the gate on its own.
+        let maint_breached = state.max_drawdown > CONFIG.maint_drawdown_limit;
+        let weight = if maint_breached {
+            warn!(
+                "🚨 Strategy {} breached maintenance health (max_drawdown {:.2}% > limit {:.2}%), forcing allocation to zero",
+                state.spec.id, state.max_drawdown * 100.0, CONFIG.maint_drawdown_limit * 100.0
+            );
+            0.0
+        } else {
+            adjusted_score / total_adjusted_score
+        };
+
+        let allocation = StrategyAllocation {
+            id: state.spec.id.clone(),
+            weight,
+            sharpe_ratio: state.sharpe_ratio,
+            mode: state.mode,
+            params: state.spec.params.clone(),
+            init_health: state.init_weight(),
+            maint_health: state.maint_weight(),
+        };
+
+        if maint_breached {
+            let allocation_json = serde_json::to_string(&allocation).context("Failed to serialize de-allocation")?;
+            let _: () = conn
+                .xadd("allocations_channel", "*", &[("data", &allocation_json)])
+                .await
+                .context("Failed to publish de-allocation")?;
+        }
+
+        allocations.push(allocation);
+    }
 
+    for (state, _) in dropped {
+        warn!(
+            "📉 Strategy {} dropped below the top-{} ideal_active_strategies cap, de-allocating",
+            state.spec.id, ideal_active_strategies
+        );
         let allocation = StrategyAllocation {
             id: state.spec.id.clone(),
-            weight: weight,
+            weight: 0.0,
             sharpe_ratio: state.sharpe_ratio,
             mode: state.mode,
             params: state.spec.params.clone(),
+            init_health: state.init_weight(),
+            maint_health: state.maint_weight(),
         };
+        let allocation_json = serde_json::to_string(&allocation).context("Failed to serialize de-allocation")?;
+        let _: () = conn
+            .xadd("allocations_channel", "*", &[("data", &allocation_json)])
+            .await
+            .context("Failed to publish de-allocation")?;
         allocations.push(allocation);
     }
 
-    allocations
+    info!("Computed {} health-weighted allocations over ${:.2} total capital", allocations.len(), total_capital);
+    Ok(allocations)
 }
 
 /// Promotes strategies from Simulating to Paper trading based on performance thresholds.
-fn promote_strategies(state_manager: &mut StateManager) {
-    for mut state in state_manager.get_all_strategy_states_mut() {
-        if state.mode == TradeMode::Simulating && state.sharpe_ratio > CONFIG.min_sharpe_for_promotion {
-            info!("🏆 Promoting strategy {} to Paper Trading! Sharpe: {:.2}", state.spec.id, state.sharpe_ratio);
-            state.mode = TradeMode::Paper;
-        }
+async fn promote_strategies(conn: &mut redis::aio::MultiplexedConnection, state_manager: &mut StateManager) -> Result<()> {
+    let candidates: Vec<String> = state_manager
+        .get_all_strategy_states()
+        .into_iter()
+        .filter(|s| s.mode == TradeMode::Simulating && s.sharpe_ratio > CONFIG.min_sharpe_for_promotion)
+        .map(|s| s.spec.id)
+        .collect();
+
+    for strategy_id in candidates {
+        info!("🏆 Promoting strategy {} to Paper Trading!", strategy_id);
+        state_manager.promote_strategy(conn, &strategy_id, TradeMode::Paper).await?;
     }
+    Ok(())
 }
 
 async fn monitor_backtest_jobs(
@@ -470,6 +770,93 @@ async fn monitor_backtest_jobs(
     }
 }
 
+/// Keeps funded allocations from going stale. On every tick, resubmits each
+/// strategy whose `rollover_at` boundary has passed (including anything
+/// already overdue from before this process started) to a fresh backtest,
+/// cross-validates it against `sanity_checker`, and either rolls the
+/// allocation forward with a refreshed Sharpe or de-allocates it if it no
+/// longer clears `min_sharpe_for_promotion`.
+async fn monitor_rollovers(
+    redis_client: redis::Client,
+    backtest_client: Arc<backtest_client::BacktestClient>,
+    sanity_checker: Arc<Mutex<sanity_checker::SanityChecker>>,
+    state_manager: Arc<Mutex<StateManager>>,
+) -> Result<()> {
+    let mut conn = redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .context("Failed to connect to Redis for rollover monitor")?;
+    let mut interval = interval(Duration::from_secs(60));
+
+    loop {
+        interval.tick().await;
+
+        let due = {
+            let manager = state_manager.lock().await;
+            manager.strategies_due_for_rollover(chrono::Utc::now())
+        };
+
+        for state in due {
+            let spec = state.spec.clone();
+            info!("⏰ Strategy {} reached its rollover boundary, re-validating", spec.id);
+
+            let result = match backtest_client.submit_backtest(&spec).await {
+                Ok(result) => result,
+                Err(e) => {
+                    error!("Failed to resubmit backtest for rollover of {}: {}", spec.id, e);
+                    continue;
+                }
+            };
+
+            let passed_cross_validation = {
+                let checker = sanity_checker.lock().await;
+                match checker.validate_strategy(&spec.params, &spec.id) {
+                    Ok(validation) => checker.cross_validate(result.sharpe_ratio, &validation, &spec.id),
+                    Err(e) => {
+                        debug!("No sanity-check price history for {} yet, skipping cross-validation: {}", spec.id, e);
+                        true
+                    }
+                }
+            };
+
+            let mut manager = state_manager.lock().await;
+            if passed_cross_validation && result.sharpe_ratio > CONFIG.min_sharpe_for_promotion {
+                if let Err(e) = manager.roll_forward(&mut conn, &spec.id, result.sharpe_ratio).await {
+                    error!("Failed to roll forward strategy {}: {}", spec.id, e);
+                    continue;
+                }
+                info!("✅ Rolled over strategy {} with refreshed Sharpe {:.2}", spec.id, result.sharpe_ratio);
+            } else {
+                warn!(
+                    "🚨 Strategy {} failed rollover validation (Sharpe {:.2}), de-allocating",
+                    spec.id, result.sharpe_ratio
+                );
+                if let Err(e) = manager.update_allocation(&mut conn, &spec.id, 0.0).await {
+                    error!("Failed to de-allocate strategy {}: {}", spec.id, e);
+                    continue;
+                }
+
+                let de_allocation = StrategyAllocation {
+                    id: spec.id.clone(),
+                    weight: 0.0,
+                    sharpe_ratio: result.sharpe_ratio,
+                    mode: state.mode,
+                    params: spec.params.clone(),
+                    init_health: state.init_weight(),
+                    maint_health: state.maint_weight(),
+                };
+                match serde_json::to_string(&de_allocation) {
+                    Ok(json) => {
+                        let _: redis::RedisResult<String> =
+                            conn.xadd("allocations_channel", "*", &[("data", json)]).await;
+                    }
+                    Err(e) => error!("Failed to serialize de-allocation for {}: {}", spec.id, e),
+                }
+            }
+        }
+    }
+}
+
 async fn poll_backtest_results(
     _redis_client: redis::Client,
     _backtest_client: Arc<backtest_client::BacktestClient>,
@@ -1,9 +1,47 @@
+use crate::config::CONFIG;
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Utc, Weekday};
 use shared_models::{StrategySpec, TradeMode};
 use std::collections::HashMap;
-use anyhow::Result;
-use tracing::{info, debug};
+use anyhow::{Context, Result};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use tracing::{info, debug, warn};
 
-#[derive(Debug, Clone)]
+/// Append-only Redis stream every state transition is written to before it's
+/// applied in memory, so `StateManager::new` can rebuild current state by
+/// replaying the log rather than trusting whatever survived in process memory.
+pub const PORTFOLIO_EVENTS_STREAM: &str = "portfolio_events";
+
+/// Weekly boundary every funded allocation is rolled over on, so capital
+/// sized against a backtest never just sits there indefinitely once that
+/// backtest goes stale.
+const ROLLOVER_WEEKDAY: Weekday = Weekday::Sun;
+const ROLLOVER_HOUR_UTC: u32 = 15;
+
+/// Moment (UTC) of the next `ROLLOVER_WEEKDAY`/`ROLLOVER_HOUR_UTC`
+/// strictly after `now`. Mirrors `position_manager`'s
+/// `next_funding_rollover_after` / `executor`'s `perp_basis_arb::next_rollover_boundary_after`
+/// so every subsystem that rolls something over weekly agrees on the boundary.
+pub fn next_rollover_boundary_after(now: DateTime<Utc>) -> DateTime<Utc> {
+    let mut days_ahead =
+        (ROLLOVER_WEEKDAY.num_days_from_monday() as i64 - now.weekday().num_days_from_monday() as i64)
+            .rem_euclid(7);
+
+    let candidate_at = |days_ahead: i64| {
+        (now.date_naive() + ChronoDuration::days(days_ahead))
+            .and_hms_opt(ROLLOVER_HOUR_UTC, 0, 0)
+            .expect("ROLLOVER_HOUR_UTC must be 0-23")
+            .and_utc()
+    };
+
+    if candidate_at(days_ahead) <= now {
+        days_ahead += 7;
+    }
+
+    candidate_at(days_ahead)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StrategyState {
     pub spec: StrategySpec,
     pub mode: TradeMode,
@@ -13,15 +51,31 @@ pub struct StrategyState {
     pub trade_count: u32,
     pub win_count: u32,
     pub run_time_secs: u64,
+    #[serde(skip, default = "std::time::Instant::now")]
     pub last_updated: std::time::Instant,
     pub capital_allocated: f64,
+    /// High-water mark this strategy's `realized_pnl` has ever reached, used
+    /// only to derive `max_drawdown` as each update comes in. Like
+    /// `last_updated`, replay can't reconstruct this the way a live call
+    /// sequence can, so it isn't carried by `PortfolioEvent` itself.
+    #[serde(skip, default)]
+    peak_realized_pnl: f64,
+    /// Largest fractional retracement from `peak_realized_pnl` ever
+    /// observed, feeding `init_weight`/`maint_weight`'s health discount.
+    pub max_drawdown: f64,
+    /// Next weekly boundary this allocation is due for rollover, or `None`
+    /// while it's unfunded (`capital_allocated == 0.0`). Persisted (not
+    /// `skip`) so a restart can detect an overdue rollover immediately
+    /// instead of waiting for `StrategyDiscovered`/`AllocationChanged` to
+    /// set a fresh one.
+    pub rollover_at: Option<DateTime<Utc>>,
 }
 
 impl StrategyState {
     pub fn new(spec: StrategySpec) -> Self {
         // Use the fitness score from the spec as initial Sharpe ratio
         let initial_sharpe = spec.fitness;
-        
+
         Self {
             spec,
             mode: TradeMode::Simulating, // All strategies start in Simulating mode
@@ -33,28 +87,112 @@ impl StrategyState {
             run_time_secs: 0,
             last_updated: std::time::Instant::now(),
             capital_allocated: 0.0,
+            peak_realized_pnl: 0.0,
+            max_drawdown: 0.0,
+            rollover_at: None,
         }
     }
-    
+
     pub fn update_performance(&mut self, pnl: f64, is_win: bool) {
         self.realized_pnl += pnl;
         self.trade_count += 1;
         if is_win {
             self.win_count += 1;
         }
-        
+
         // Update runtime
         self.run_time_secs = self.last_updated.elapsed().as_secs();
-        
+
         // Recalculate Sharpe ratio (simplified version)
         if self.trade_count > 0 && self.run_time_secs > 0 {
             let avg_pnl_per_trade = self.realized_pnl / self.trade_count as f64;
             let time_factor = (self.run_time_secs as f64 / 3600.0).max(1.0); // Hours
             self.sharpe_ratio = avg_pnl_per_trade / time_factor;
         }
-        
+
+        // Track the worst retracement from the strategy's own high-water
+        // mark, so a strategy that gave back a lot of its gains is
+        // penalized even if it's still net-positive overall.
+        if self.realized_pnl > self.peak_realized_pnl {
+            self.peak_realized_pnl = self.realized_pnl;
+        }
+        if self.peak_realized_pnl > 0.0 {
+            let drawdown = ((self.peak_realized_pnl - self.realized_pnl) / self.peak_realized_pnl).max(0.0);
+            self.max_drawdown = self.max_drawdown.max(drawdown);
+        }
+
         self.last_updated = std::time::Instant::now();
     }
+
+    /// Allocation-sizing weight: 1.0 at zero drawdown, shrinking linearly as
+    /// `max_drawdown` grows and clamped to `[0, 1]` so a strategy can only
+    /// ever be sized down to nothing, never "extra" penalized.
+    pub fn init_weight(&self) -> f64 {
+        (1.0 - CONFIG.init_health_drawdown_k * self.max_drawdown).clamp(0.0, 1.0)
+    }
+
+    /// Companion to `init_weight` with a smaller `k`, so it degrades more
+    /// slowly. Published as `maint_health`, the figure the executor sizes
+    /// live positions against. Whether a strategy keeps any allocation at
+    /// all is gated on `max_drawdown > maint_drawdown_limit` in
+    /// `calculate_allocations`, not on this weight.
+    pub fn maint_weight(&self) -> f64 {
+        (1.0 - CONFIG.maint_health_drawdown_k * self.max_drawdown).clamp(0.0, 1.0)
+    }
+}
+
+/// Every transition `StateManager` can persist. Replaying these in order from
+/// the beginning of `PORTFOLIO_EVENTS_STREAM` reconstructs current state, so
+/// each variant carries the *resulting* values rather than a delta — replay
+/// can't reproduce wall-clock-dependent derivations (e.g. `update_performance`'s
+/// elapsed-time Sharpe recalculation) the same way a live call can.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PortfolioEvent {
+    /// A new strategy spec was picked up off `source_stream`. Carries the id
+    /// of the message it was derived from, so `get_last_stream_id` can be
+    /// read back as a projection over the log instead of its own field.
+    StrategyDiscovered {
+        spec: StrategySpec,
+        source_stream: String,
+        source_stream_id: String,
+    },
+    PerformanceUpdated {
+        strategy_id: String,
+        realized_pnl: f64,
+        sharpe_ratio: f64,
+        trade_count: u32,
+        win_count: u32,
+        max_drawdown: f64,
+    },
+    /// Carries `rollover_at` alongside the capital change since funding a
+    /// strategy is exactly when its rollover TTL should (re)start, and
+    /// zeroing it out is exactly when that TTL should be cleared.
+    AllocationChanged {
+        strategy_id: String,
+        capital_allocated: f64,
+        rollover_at: Option<DateTime<Utc>>,
+    },
+    StrategyPromoted {
+        strategy_id: String,
+        new_mode: TradeMode,
+    },
+    /// A funded allocation reached its rollover boundary and was
+    /// re-validated against a fresh backtest rather than left to trade on
+    /// stale results; its Sharpe and next boundary are refreshed in place.
+    RolloverCompleted {
+        strategy_id: String,
+        sharpe_ratio: f64,
+        rollover_at: DateTime<Utc>,
+    },
+    /// An operator moved the live cap on concurrently funded strategies via
+    /// a governance command. Carries `source_stream`/`source_stream_id` the
+    /// same way `StrategyDiscovered` does, so `get_last_stream_id` stays a
+    /// pure projection over the log for this stream too.
+    GovernanceCountChanged {
+        ideal_active_strategies: usize,
+        source_stream: String,
+        source_stream_id: String,
+    },
 }
 
 pub struct StateManager {
@@ -62,65 +200,291 @@ pub struct StateManager {
     total_capital: f64,
     initial_capital: f64,
     realized_pnl: f64,
+    /// Projection of the last-seen message id per upstream stream, derived
+    /// from replayed `StrategyDiscovered` and `GovernanceCountChanged` events.
+    last_stream_ids: HashMap<String, String>,
+    /// Live cap on how many Paper/Live strategies `calculate_allocations`
+    /// will fund at once, seeded from `CONFIG` but movable without a restart
+    /// via `increase_active_count`/`scale_active_count`.
+    ideal_active_strategies: usize,
 }
 
 impl StateManager {
-    pub fn new(initial_capital: f64) -> Self {
-        Self {
+    /// Rebuilds state by replaying `PORTFOLIO_EVENTS_STREAM` from the
+    /// beginning, so a restart picks up exactly where the log left off
+    /// instead of starting from a blank slate.
+    pub async fn new(conn: &mut redis::aio::MultiplexedConnection, initial_capital: f64) -> Result<Self> {
+        let mut manager = Self {
             strategies: HashMap::new(),
             total_capital: initial_capital,
             initial_capital,
             realized_pnl: 0.0,
+            last_stream_ids: HashMap::new(),
+            ideal_active_strategies: CONFIG.ideal_active_strategies,
+        };
+        manager.replay_event_log(conn).await?;
+        Ok(manager)
+    }
+
+    async fn replay_event_log(&mut self, conn: &mut redis::aio::MultiplexedConnection) -> Result<()> {
+        let reply: redis::streams::StreamRangeReply = conn
+            .xrange_all(PORTFOLIO_EVENTS_STREAM)
+            .await
+            .context("Failed to read portfolio_events stream for replay")?;
+
+        let replayed = reply.ids.len();
+        for entry in reply.ids {
+            let Some(event_value) = entry.map.get("event") else {
+                continue;
+            };
+            let Ok(event_json) = redis::from_redis_value::<String>(event_value) else {
+                warn!("Malformed portfolio event at {}, skipping", entry.id);
+                continue;
+            };
+            match serde_json::from_str::<PortfolioEvent>(&event_json) {
+                Ok(event) => self.apply_event(event),
+                Err(e) => warn!("Failed to deserialize portfolio event {}: {}", entry.id, e),
+            }
+        }
+        info!("Replayed {} events from {}", replayed, PORTFOLIO_EVENTS_STREAM);
+        Ok(())
+    }
+
+    /// Applies an already-persisted event to in-memory state. Used both by
+    /// replay and by `record_event` right after the event is written, so the
+    /// two paths can never disagree on what a given event means.
+    fn apply_event(&mut self, event: PortfolioEvent) {
+        match event {
+            PortfolioEvent::StrategyDiscovered { spec, source_stream, source_stream_id } => {
+                let strategy_id = spec.id.clone();
+                self.strategies.entry(strategy_id).or_insert_with(|| StrategyState::new(spec));
+                self.last_stream_ids.insert(source_stream, source_stream_id);
+            }
+            PortfolioEvent::PerformanceUpdated { strategy_id, realized_pnl, sharpe_ratio, trade_count, win_count, max_drawdown } => {
+                if let Some(state) = self.strategies.get_mut(&strategy_id) {
+                    state.realized_pnl = realized_pnl;
+                    state.sharpe_ratio = sharpe_ratio;
+                    state.trade_count = trade_count;
+                    state.win_count = win_count;
+                    state.max_drawdown = max_drawdown;
+                    state.last_updated = std::time::Instant::now();
+                }
+            }
+            PortfolioEvent::AllocationChanged { strategy_id, capital_allocated, rollover_at } => {
+                if let Some(state) = self.strategies.get_mut(&strategy_id) {
+                    state.capital_allocated = capital_allocated;
+                    state.rollover_at = rollover_at;
+                }
+            }
+            PortfolioEvent::StrategyPromoted { strategy_id, new_mode } => {
+                if let Some(state) = self.strategies.get_mut(&strategy_id) {
+                    state.mode = new_mode;
+                    info!("Promoted strategy {} to {:?} mode", strategy_id, new_mode);
+                }
+            }
+            PortfolioEvent::RolloverCompleted { strategy_id, sharpe_ratio, rollover_at } => {
+                if let Some(state) = self.strategies.get_mut(&strategy_id) {
+                    state.sharpe_ratio = sharpe_ratio;
+                    state.rollover_at = Some(rollover_at);
+                }
+            }
+            PortfolioEvent::GovernanceCountChanged { ideal_active_strategies, source_stream, source_stream_id } => {
+                self.ideal_active_strategies = ideal_active_strategies;
+                self.last_stream_ids.insert(source_stream, source_stream_id);
+            }
         }
     }
-    
-    pub fn add_strategy_spec(&mut self, spec: StrategySpec) {
+
+    async fn record_event(&mut self, conn: &mut redis::aio::MultiplexedConnection, event: PortfolioEvent) -> Result<()> {
+        let event_json = serde_json::to_string(&event).context("Failed to serialize portfolio event")?;
+        let _: String = conn
+            .xadd(PORTFOLIO_EVENTS_STREAM, "*", &[("event", event_json)])
+            .await
+            .context("Failed to append portfolio event")?;
+        self.apply_event(event);
+        Ok(())
+    }
+
+    /// Records that a strategy spec was read off `source_stream` at
+    /// `source_stream_id`, discovering it if this is the first time it's
+    /// been seen (a replay or redelivery just advances the stream bookmark).
+    pub async fn discover_strategy(
+        &mut self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        spec: StrategySpec,
+        source_stream: &str,
+        source_stream_id: String,
+    ) -> Result<()> {
+        if self.strategies.contains_key(&spec.id) {
+            self.last_stream_ids.insert(source_stream.to_string(), source_stream_id);
+            return Ok(());
+        }
         let strategy_id = spec.id.clone();
         let initial_fitness = spec.fitness;
-        
-        // Create initial strategy state
-        let state = StrategyState::new(spec);
-        self.strategies.insert(strategy_id.clone(), state);
-        
+        self.record_event(
+            conn,
+            PortfolioEvent::StrategyDiscovered { spec, source_stream: source_stream.to_string(), source_stream_id },
+        )
+        .await?;
         info!("Added new strategy: {} with initial Sharpe: {:.2}", strategy_id, initial_fitness);
+        Ok(())
     }
-    
+
+    pub async fn update_strategy_performance(
+        &mut self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        strategy_id: &str,
+        pnl: f64,
+        is_win: bool,
+    ) -> Result<()> {
+        let Some(state) = self.strategies.get(strategy_id) else {
+            return Ok(());
+        };
+        // Project the resulting values through the same logic a live update
+        // uses, so the event records a concrete snapshot rather than a delta
+        // that replay would have to re-derive from elapsed wall-clock time.
+        let mut projected = state.clone();
+        projected.update_performance(pnl, is_win);
+        self.record_event(
+            conn,
+            PortfolioEvent::PerformanceUpdated {
+                strategy_id: strategy_id.to_string(),
+                realized_pnl: projected.realized_pnl,
+                sharpe_ratio: projected.sharpe_ratio,
+                trade_count: projected.trade_count,
+                win_count: projected.win_count,
+                max_drawdown: projected.max_drawdown,
+            },
+        )
+        .await?;
+        debug!("Updated performance for strategy {}", strategy_id);
+        Ok(())
+    }
+
+    /// Sets `capital_allocated` and, in the same event, (re)schedules the
+    /// strategy's rollover: a positive allocation gets a fresh weekly
+    /// boundary, a zero one (de-allocation) clears it so a de-allocated
+    /// strategy doesn't keep showing up as overdue.
+    pub async fn update_allocation(
+        &mut self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        strategy_id: &str,
+        capital_allocated: f64,
+    ) -> Result<()> {
+        let rollover_at = (capital_allocated > 0.0).then(|| next_rollover_boundary_after(Utc::now()));
+        self.record_event(
+            conn,
+            PortfolioEvent::AllocationChanged { strategy_id: strategy_id.to_string(), capital_allocated, rollover_at },
+        )
+        .await
+    }
+
+    /// Re-validated a funded allocation at its rollover boundary: refreshes
+    /// its Sharpe from a new backtest and pushes the next boundary forward,
+    /// instead of leaving it to trade on a backtest that's gone stale.
+    pub async fn roll_forward(
+        &mut self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        strategy_id: &str,
+        sharpe_ratio: f64,
+    ) -> Result<()> {
+        let rollover_at = next_rollover_boundary_after(Utc::now());
+        self.record_event(
+            conn,
+            PortfolioEvent::RolloverCompleted { strategy_id: strategy_id.to_string(), sharpe_ratio, rollover_at },
+        )
+        .await
+    }
+
+    /// Funded strategies due for this rollover cycle, or overdue from one
+    /// the manager missed entirely while it wasn't running — a restart
+    /// detects these on its very first check instead of waiting for the
+    /// next scheduled boundary.
+    pub fn strategies_due_for_rollover(&self, now: DateTime<Utc>) -> Vec<StrategyState> {
+        self.strategies
+            .values()
+            .filter(|s| s.capital_allocated > 0.0 && s.rollover_at.is_some_and(|at| at <= now))
+            .cloned()
+            .collect()
+    }
+
+    /// Moves the live cap on concurrently funded strategies by `delta`
+    /// (negative to shrink), clamped so it never drops below zero.
+    pub async fn increase_active_count(
+        &mut self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        delta: i64,
+        source_stream: &str,
+        source_stream_id: String,
+    ) -> Result<()> {
+        let ideal_active_strategies = (self.ideal_active_strategies as i64 + delta).max(0) as usize;
+        self.record_event(
+            conn,
+            PortfolioEvent::GovernanceCountChanged {
+                ideal_active_strategies,
+                source_stream: source_stream.to_string(),
+                source_stream_id,
+            },
+        )
+        .await
+    }
+
+    /// Scales the live cap on concurrently funded strategies by `factor`,
+    /// rounding to the nearest whole strategy, for an operator concentrating
+    /// or diversifying capital in response to a regime change.
+    pub async fn scale_active_count(
+        &mut self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        factor: f64,
+        source_stream: &str,
+        source_stream_id: String,
+    ) -> Result<()> {
+        let ideal_active_strategies = ((self.ideal_active_strategies as f64 * factor).round().max(0.0)) as usize;
+        self.record_event(
+            conn,
+            PortfolioEvent::GovernanceCountChanged {
+                ideal_active_strategies,
+                source_stream: source_stream.to_string(),
+                source_stream_id,
+            },
+        )
+        .await
+    }
+
+    /// Live cap on how many Paper/Live strategies `calculate_allocations`
+    /// will fund at once.
+    pub fn get_ideal_active_strategies(&self) -> usize {
+        self.ideal_active_strategies
+    }
+
+    pub async fn promote_strategy(
+        &mut self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        strategy_id: &str,
+        new_mode: TradeMode,
+    ) -> Result<()> {
+        self.record_event(conn, PortfolioEvent::StrategyPromoted { strategy_id: strategy_id.to_string(), new_mode })
+            .await
+    }
+
+    /// Projection over the replayed log: the last message id seen on
+    /// `source_stream`, so stream consumption can resume from it directly.
+    pub fn get_last_stream_id(&self, source_stream: &str) -> Option<String> {
+        self.last_stream_ids.get(source_stream).cloned()
+    }
+
     pub fn get_all_specs(&self) -> Vec<StrategySpec> {
         self.strategies.values().map(|s| s.spec.clone()).collect()
     }
-    
+
     pub fn get_all_strategy_states(&self) -> Vec<StrategyState> {
         self.strategies.values().cloned().collect()
     }
-    
-    pub fn get_all_strategy_states_mut(&mut self) -> Vec<StrategyState> {
-        // For the simulation, we return a copy that can be modified
-        self.strategies.values().cloned().collect()
-    }
-    
-    pub fn update_strategy_state<F>(&mut self, strategy_id: &str, update_fn: F) -> Result<()>
-    where
-        F: FnOnce(&mut StrategyState),
-    {
-        if let Some(state) = self.strategies.get_mut(strategy_id) {
-            update_fn(state);
-            debug!("Updated state for strategy {}", strategy_id);
-        }
-        Ok(())
-    }
-    
-    pub fn promote_strategy(&mut self, strategy_id: &str, new_mode: TradeMode) -> Result<()> {
-        self.update_strategy_state(strategy_id, |state| {
-            state.mode = new_mode;
-            info!("Promoted strategy {} to {:?} mode", strategy_id, new_mode);
-        })?;
-        Ok(())
-    }
-    
+
     pub fn get_total_capital(&self) -> f64 {
         self.total_capital
     }
-    
+
     pub fn get_current_nav(&self) -> f64 {
         self.initial_capital + self.realized_pnl
     }
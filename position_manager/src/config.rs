@@ -8,6 +8,24 @@ pub struct Config {
     pub jupiter_api_url: String,
     pub paper_trading_mode: bool,
     pub trailing_stop_loss_percent: f64,
+    pub close_concurrency_limit: usize,
+    pub jupiter_quote_timeout_ms: u64,
+    /// How long a position may stay open before it's expired and either closed
+    /// or rolled over. Defaults to 7 days.
+    pub position_expiry_secs: i64,
+    /// Funding-bearing (short/perp) positions roll over on a fixed wall-clock
+    /// schedule rather than an elapsed-duration expiry, matching how perp
+    /// funding periods actually settle. Defaults to Sunday 15:00 UTC.
+    pub funding_rollover_weekday: chrono::Weekday,
+    pub funding_rollover_hour_utc: u32,
+    /// How long after the scheduled rollover moment a position is still
+    /// rolled forward — covers the monitor having been down across it.
+    pub funding_rollover_window_secs: i64,
+    /// How long before the scheduled rollover moment the roll is allowed to
+    /// fire, so the successor leg is open before the new funding period
+    /// actually starts instead of racing it right at the boundary.
+    pub funding_rollover_pre_expiry_margin_secs: i64,
+    pub metrics_bind_addr: String,
 }
 
 impl Config {
@@ -24,6 +42,35 @@ impl Config {
                 .unwrap_or_else(|_| "15.0".to_string())
                 .parse()
                 .unwrap(),
+            close_concurrency_limit: env::var("CLOSE_CONCURRENCY_LIMIT")
+                .unwrap_or_else(|_| "4".to_string())
+                .parse()
+                .unwrap(),
+            jupiter_quote_timeout_ms: env::var("JUPITER_QUOTE_TIMEOUT_MS")
+                .unwrap_or_else(|_| "2000".to_string())
+                .parse()
+                .unwrap(),
+            position_expiry_secs: env::var("POSITION_EXPIRY_SECS")
+                .unwrap_or_else(|_| (7 * 24 * 60 * 60).to_string())
+                .parse()
+                .unwrap(),
+            funding_rollover_weekday: env::var("FUNDING_ROLLOVER_WEEKDAY")
+                .unwrap_or_else(|_| "Sun".to_string())
+                .parse()
+                .expect("FUNDING_ROLLOVER_WEEKDAY must be a weekday name, e.g. \"Sun\""),
+            funding_rollover_hour_utc: env::var("FUNDING_ROLLOVER_HOUR_UTC")
+                .unwrap_or_else(|_| "15".to_string())
+                .parse()
+                .unwrap(),
+            funding_rollover_window_secs: env::var("FUNDING_ROLLOVER_WINDOW_SECS")
+                .unwrap_or_else(|_| (60 * 60).to_string())
+                .parse()
+                .unwrap(),
+            funding_rollover_pre_expiry_margin_secs: env::var("FUNDING_ROLLOVER_PRE_EXPIRY_MARGIN_SECS")
+                .unwrap_or_else(|_| (5 * 60).to_string())
+                .parse()
+                .unwrap(),
+            metrics_bind_addr: env::var("METRICS_BIND_ADDR").unwrap_or_else(|_| "0.0.0.0:9189".to_string()),
         }
     }
 }
@@ -14,6 +14,12 @@ pub struct TradeRecord {
     pub entry_price_usd: f64,
     pub highest_price_usd: Option<f64>,
     pub status: String,
+    pub expiry_at: Option<i64>,
+    pub rolled_from_trade_id: Option<i64>,
+    pub funding_rollover_at: Option<i64>,
+    /// Per-trade trailing-stop percent from the strategy's `OrderType::TrailingStop`,
+    /// overriding `CONFIG.trailing_stop_loss_percent` when set.
+    pub trail_percent_override: Option<f64>,
 }
 
 pub struct Database {
@@ -28,18 +34,13 @@ impl Database {
         }
         let conn = Connection::open(path)?;
         info!("Position database opened at {}", db_path);
-        Self::init_db(&conn)?;
+        shared_models::migrations::run(&conn).context("Failed to run schema migrations")?;
         Ok(Self { conn })
     }
 
-    fn init_db(conn: &Connection) -> Result<()> {
-        // Use same schema as executor database
-        Ok(())
-    }
-
     pub fn get_open_trades(&self) -> Result<Vec<TradeRecord>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, strategy_id, token_address, side, amount_usd, entry_price_usd, highest_price_usd, status 
+            "SELECT id, strategy_id, token_address, side, amount_usd, entry_price_usd, highest_price_usd, status, expiry_at, rolled_from_trade_id, funding_rollover_at, trail_percent_override
              FROM trades WHERE status = 'OPEN'",
         )?;
 
@@ -53,6 +54,10 @@ impl Database {
                 entry_price_usd: row.get(5)?,
                 highest_price_usd: row.get(6)?,
                 status: row.get(7)?,
+                expiry_at: row.get(8)?,
+                rolled_from_trade_id: row.get(9)?,
+                funding_rollover_at: row.get(10)?,
+                trail_percent_override: row.get(11)?,
             })
         })?;
 
@@ -68,4 +73,106 @@ impl Database {
         )?;
         Ok(())
     }
+
+    pub fn update_trade_pnl(&self, trade_id: i64, status: &str, close_price_usd: f64, pnl_usd: f64) -> Result<()> {
+        let now: DateTime<Utc> = Utc::now();
+        self.conn.execute(
+            "UPDATE trades SET status = ?1, close_time = ?2, close_price_usd = ?3, pnl_usd = ?4 WHERE id = ?5",
+            params![status, now.timestamp(), close_price_usd, pnl_usd, trade_id],
+        ).context("Failed to update trade PnL")?;
+        Ok(())
+    }
+
+    /// Lazily assign an expiry horizon to a trade that doesn't have one yet.
+    pub fn set_expiry(&self, trade_id: i64, expiry_at: i64) -> Result<()> {
+        self.conn.execute(
+            "UPDATE trades SET expiry_at = ?1 WHERE id = ?2",
+            params![expiry_at, trade_id],
+        )?;
+        Ok(())
+    }
+
+    /// Assign or reschedule a funding-bearing position's next rollover boundary.
+    pub fn set_funding_rollover_at(&self, trade_id: i64, funding_rollover_at: i64) -> Result<()> {
+        self.conn.execute(
+            "UPDATE trades SET funding_rollover_at = ?1 WHERE id = ?2",
+            params![funding_rollover_at, trade_id],
+        )?;
+        Ok(())
+    }
+
+    /// Close an expiring trade and open its successor in one call, linking the two
+    /// via `rolled_from_trade_id` so the rollover shows up as a pair in trade history.
+    pub fn rollover_trade(
+        &self,
+        predecessor: &TradeRecord,
+        close_price_usd: f64,
+        pnl_usd: f64,
+        new_entry_price_usd: f64,
+        new_expiry_at: i64,
+    ) -> Result<i64> {
+        let now: DateTime<Utc> = Utc::now();
+        self.conn.execute(
+            "UPDATE trades SET status = 'CLOSED_ROLLED', close_time = ?1, close_price_usd = ?2, pnl_usd = ?3 WHERE id = ?4",
+            params![now.timestamp(), close_price_usd, pnl_usd, predecessor.id],
+        ).context("Failed to close predecessor trade for rollover")?;
+
+        self.conn.execute(
+            "INSERT INTO trades (strategy_id, token_address, symbol, amount_usd, status, entry_time, entry_price_usd, confidence, side, highest_price_usd, expiry_at, rolled_from_trade_id, trail_percent_override)
+             VALUES (?1, ?2, ?2, ?3, 'OPEN', ?4, ?5, 1.0, ?6, ?5, ?7, ?8, ?9)",
+            params![
+                predecessor.strategy_id,
+                predecessor.token_address,
+                predecessor.amount_usd,
+                now.timestamp(),
+                new_entry_price_usd,
+                predecessor.side,
+                new_expiry_at,
+                predecessor.id,
+                predecessor.trail_percent_override,
+            ],
+        ).context("Failed to insert rollover successor trade")?;
+
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Close a funding-bearing position at its scheduled rollover boundary and
+    /// open its successor at the same price, carrying the predecessor's own
+    /// expiry forward and settling `funding_settled_usd` into the predecessor's
+    /// recorded PnL alongside any price movement since its entry.
+    pub fn rollover_funding_position(
+        &self,
+        predecessor: &TradeRecord,
+        price_pnl_usd: f64,
+        funding_settled_usd: f64,
+        current_price_usd: f64,
+        new_funding_rollover_at: i64,
+    ) -> Result<i64> {
+        let now: DateTime<Utc> = Utc::now();
+        let total_pnl_usd = price_pnl_usd + funding_settled_usd;
+
+        self.conn.execute(
+            "UPDATE trades SET status = 'CLOSED_FUNDING_ROLLED', close_time = ?1, close_price_usd = ?2, pnl_usd = ?3, funding_settled_usd = ?4 WHERE id = ?5",
+            params![now.timestamp(), current_price_usd, total_pnl_usd, funding_settled_usd, predecessor.id],
+        ).context("Failed to close predecessor trade for funding rollover")?;
+
+        self.conn.execute(
+            "INSERT INTO trades (strategy_id, token_address, symbol, amount_usd, status, entry_time, entry_price_usd, confidence, side, highest_price_usd, expiry_at, rolled_from_trade_id, funding_rollover_at, trail_percent_override)
+             VALUES (?1, ?2, ?2, ?3, 'OPEN', ?4, ?5, 1.0, ?6, ?5, ?7, ?8, ?9, ?10)",
+            params![
+                predecessor.strategy_id,
+                predecessor.token_address,
+                predecessor.amount_usd,
+                now.timestamp(),
+                current_price_usd,
+                predecessor.side,
+                predecessor.expiry_at,
+                predecessor.id,
+                new_funding_rollover_at,
+                predecessor.trail_percent_override,
+            ],
+        ).context("Failed to insert funding-rollover successor trade")?;
+
+        Ok(self.conn.last_insert_rowid())
+    }
 }
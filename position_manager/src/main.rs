@@ -1,10 +1,12 @@
 mod config;
 mod database;
 mod jupiter;
+mod metrics;
 mod position_monitor;
 mod signer_client;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use metrics::PositionManagerMetrics;
 use std::sync::Arc;
 use tracing::info;
 
@@ -16,7 +18,13 @@ async fn main() -> Result<()> {
 
     let db = Arc::new(database::Database::new(&config::CONFIG.database_path)?);
 
-    position_monitor::run_monitor(db).await?;
+    let metrics = Arc::new(
+        PositionManagerMetrics::new().context("Failed to initialize position manager metrics")?,
+    );
+    metrics.spawn_percentile_publisher();
+    metrics.spawn_server(&config::CONFIG.metrics_bind_addr);
+
+    position_monitor::run_monitor(db, metrics).await?;
 
     Ok(())
 }
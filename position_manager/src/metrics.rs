@@ -0,0 +1,104 @@
+//! Prometheus metrics for position closing, mirroring the executor's
+//! `ExecutorMetrics` and the market data gateway's `DataValidationMetrics`.
+use anyhow::{Context, Result};
+use axum::{extract::State, http::StatusCode, response::Response, routing::get, Router};
+use prometheus::{Counter, Encoder, Gauge, Registry, TextEncoder};
+use shared_models::LatencyHistogram;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+#[derive(Clone)]
+pub struct PositionManagerMetrics {
+    registry: Registry,
+    pub jupiter_quote_latency: Arc<LatencyHistogram>,
+    jupiter_quote_p50_ms: Gauge,
+    jupiter_quote_p90_ms: Gauge,
+    jupiter_quote_p99_ms: Gauge,
+    pub funding_rollovers_total: Counter,
+    /// Rollovers that ran well past their scheduled boundary, e.g. because the
+    /// monitor was down across it and only caught up afterward.
+    pub funding_rollovers_missed_total: Counter,
+}
+
+impl PositionManagerMetrics {
+    pub fn new() -> Result<Self> {
+        let registry = Registry::new();
+
+        let jupiter_quote_p50_ms = Gauge::new("position_manager_jupiter_quote_latency_p50_ms", "p50 Jupiter quote round-trip latency")
+            .context("Failed to create jupiter_quote_p50_ms gauge")?;
+        let jupiter_quote_p90_ms = Gauge::new("position_manager_jupiter_quote_latency_p90_ms", "p90 Jupiter quote round-trip latency")
+            .context("Failed to create jupiter_quote_p90_ms gauge")?;
+        let jupiter_quote_p99_ms = Gauge::new("position_manager_jupiter_quote_latency_p99_ms", "p99 Jupiter quote round-trip latency")
+            .context("Failed to create jupiter_quote_p99_ms gauge")?;
+        let funding_rollovers_total = Counter::new("position_manager_funding_rollovers_total", "Funding-bearing positions rolled over at their scheduled boundary")
+            .context("Failed to create funding_rollovers_total counter")?;
+        let funding_rollovers_missed_total = Counter::new("position_manager_funding_rollovers_missed_total", "Funding rollovers caught well after their scheduled boundary (e.g. after downtime)")
+            .context("Failed to create funding_rollovers_missed_total counter")?;
+
+        registry.register(Box::new(jupiter_quote_p50_ms.clone())).context("Failed to register jupiter_quote_p50_ms")?;
+        registry.register(Box::new(jupiter_quote_p90_ms.clone())).context("Failed to register jupiter_quote_p90_ms")?;
+        registry.register(Box::new(jupiter_quote_p99_ms.clone())).context("Failed to register jupiter_quote_p99_ms")?;
+        registry.register(Box::new(funding_rollovers_total.clone())).context("Failed to register funding_rollovers_total")?;
+        registry.register(Box::new(funding_rollovers_missed_total.clone())).context("Failed to register funding_rollovers_missed_total")?;
+
+        Ok(Self {
+            registry,
+            jupiter_quote_latency: Arc::new(LatencyHistogram::new()),
+            jupiter_quote_p50_ms,
+            jupiter_quote_p90_ms,
+            jupiter_quote_p99_ms,
+            funding_rollovers_total,
+            funding_rollovers_missed_total,
+        })
+    }
+
+    /// Periodically flush the HDR histogram's percentiles into scrapeable gauges.
+    pub fn spawn_percentile_publisher(&self) {
+        let metrics = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(10));
+            loop {
+                interval.tick().await;
+                let (p50, p90, p99) = metrics.jupiter_quote_latency.percentiles();
+                metrics.jupiter_quote_p50_ms.set(p50);
+                metrics.jupiter_quote_p90_ms.set(p90);
+                metrics.jupiter_quote_p99_ms.set(p99);
+            }
+        });
+    }
+
+    pub fn spawn_server(&self, bind_addr: &str) {
+        let metrics = self.clone();
+        let bind_addr = bind_addr.to_string();
+        tokio::spawn(async move {
+            let app = Router::new().route("/metrics", get(metrics_handler)).with_state(metrics);
+            match tokio::net::TcpListener::bind(&bind_addr).await {
+                Ok(listener) => {
+                    info!("📊 Position manager metrics server listening on {}", bind_addr);
+                    if let Err(e) = axum::serve(listener, app).await {
+                        error!("Position manager metrics server failed: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to bind position manager metrics server to {}: {}", bind_addr, e),
+            }
+        });
+    }
+}
+
+async fn metrics_handler(State(metrics): State<PositionManagerMetrics>) -> Result<Response<String>, StatusCode> {
+    let encoder = TextEncoder::new();
+    let metric_families = metrics.registry.gather();
+    let mut buffer = Vec::new();
+
+    encoder
+        .encode(&metric_families, &mut buffer)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let body = String::from_utf8(buffer).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Response::builder()
+        .header("content-type", "text/plain; version=0.0.4")
+        .body(body)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
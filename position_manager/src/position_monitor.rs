@@ -1,19 +1,29 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use crate::jupiter::JupiterClient;
 use crate::database::{Database, TradeRecord};
-use shared_models::Side;
-use tracing::{error, info};
+use crate::metrics::PositionManagerMetrics;
+use shared_models::{PositionLifecycleAction, PositionLifecycleEvent, Side, StrategyAllocation, TickGate};
+use tracing::{debug, error, info, warn};
 use redis::AsyncCommands;
 use redis::streams::StreamReadOptions;
 use std::str::FromStr;
 use tokio::sync::Mutex;
 use std::sync::Arc;
 use std::collections::HashMap;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use crate::config::CONFIG;
 use solana_sdk::pubkey::Pubkey;
+use futures_util::stream::{self, StreamExt};
+use chrono::Utc;
 
-pub async fn run_monitor(db: Arc<Database>) -> Result<()> {
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+pub async fn run_monitor(db: Arc<Database>, metrics: Arc<PositionManagerMetrics>) -> Result<()> {
     info!("📈 Starting Position Manager v24...");
     
     // Initialize Jupiter client
@@ -22,6 +32,7 @@ pub async fn run_monitor(db: Arc<Database>) -> Result<()> {
     // Initialize price tracking
     let current_prices = Arc::new(Mutex::new(HashMap::new()));
     let sol_usd_price = Arc::new(Mutex::new(50.0)); // Default SOL price
+    let current_funding_rates: Arc<Mutex<HashMap<String, f64>>> = Arc::new(Mutex::new(HashMap::new()));
     
     // Initialize Redis connection for market data
     let redis_client = redis::Client::open(CONFIG.redis_url.clone())?;
@@ -29,13 +40,24 @@ pub async fn run_monitor(db: Arc<Database>) -> Result<()> {
     
     let mut market_stream_ids = HashMap::new();
 
+    // Guards against stale/duplicate ticks from reconnects or multiple producers
+    // regressing a price the trailing-stop logic below already saw move forward.
+    let mut price_gate = TickGate::new();
+    let mut sol_price_gate = TickGate::new();
+
+    // Active allocations keyed by strategy id, used to decide whether an expiring
+    // position should roll over or simply close, and to read each strategy's
+    // own max-hold policy out of `StrategyAllocation.params`.
+    let mut active_strategies: HashMap<String, StrategyAllocation> = HashMap::new();
+    let mut allocations_stream_id = "0".to_string();
+
     loop {
         // Listen for market events from Redis streams with timeout to avoid blocking forever
         let result = tokio::time::timeout(
             Duration::from_millis(1000),
             redis_conn.xread_options::<_, _, redis::streams::StreamReadReply>(
-                &["events:price", "events:sol_price"], 
-                &["0", "0"],
+                &["events:price", "events:sol_price", "events:funding", "allocations_channel"],
+                &["0", "0", "0", allocations_stream_id.as_str()],
                 &StreamReadOptions::default().block(100).count(10)
             )
         ).await;
@@ -50,7 +72,11 @@ pub async fn run_monitor(db: Arc<Database>) -> Result<()> {
                             if let Some(data_bytes) = stream_msg.map.get("data") {
                                 if let Ok(data_str) = redis::from_redis_value::<String>(data_bytes) {
                                     if let Ok(event) = serde_json::from_str::<shared_models::SolPriceEvent>(&data_str) {
-                                        *sol_usd_price.lock().await = event.price_usd;
+                                        if sol_price_gate.accept("SOL", event.publish_time * 1000) {
+                                            *sol_usd_price.lock().await = event.price_usd;
+                                        } else {
+                                            debug!(timestamp = event.publish_time, "Dropping stale/duplicate SolPriceEvent");
+                                        }
                                     }
                                 }
                             }
@@ -58,12 +84,38 @@ pub async fn run_monitor(db: Arc<Database>) -> Result<()> {
                             if let Some(data_bytes) = stream_msg.map.get("data") {
                                 if let Ok(data_str) = redis::from_redis_value::<String>(data_bytes) {
                                     if let Ok(event) = serde_json::from_str::<shared_models::PriceTick>(&data_str) {
-                                        current_prices.lock().await.insert(event.token_address.clone(), event.price_usd);
+                                        if price_gate.accept(&event.token_address, event.timestamp_ms) {
+                                            current_prices.lock().await.insert(event.token_address.clone(), event.price_usd);
+                                        } else {
+                                            debug!(
+                                                token = %event.token_address,
+                                                timestamp_ms = event.timestamp_ms,
+                                                "Dropping stale/duplicate PriceTick"
+                                            );
+                                        }
+                                    }
+                                }
+                            }
+                        } else if stream_name == "events:funding" {
+                            if let Some(data_bytes) = stream_msg.map.get("data") {
+                                if let Ok(data_str) = redis::from_redis_value::<String>(data_bytes) {
+                                    if let Ok(event) = serde_json::from_str::<shared_models::FundingEvent>(&data_str) {
+                                        current_funding_rates.lock().await.insert(event.token_address.clone(), event.funding_rate_pct);
+                                    }
+                                }
+                            }
+                        } else if stream_name == "allocations_channel" {
+                            allocations_stream_id = stream_msg.id.clone();
+                            if let Some(data) = stream_msg.map.get("allocations") {
+                                if let Ok(allocations_str) = redis::from_redis_value::<String>(data) {
+                                    if let Ok(allocations) = serde_json::from_str::<Vec<StrategyAllocation>>(&allocations_str) {
+                                        active_strategies = allocations.into_iter().map(|a| (a.id.clone(), a)).collect();
+                                        debug!("📋 Tracking {} active strategy allocations for rollover decisions", active_strategies.len());
                                     }
                                 }
                             }
                         }
-                        
+
                         market_stream_ids.insert(stream_name.clone(), stream_msg.id.clone());
                     }
                 }
@@ -77,13 +129,22 @@ pub async fn run_monitor(db: Arc<Database>) -> Result<()> {
         // Check open positions
         if !CONFIG.paper_trading_mode {
             if let Err(e) = check_open_positions(
-                db.clone(), 
-                jupiter_client.clone(), 
-                current_prices.clone(), 
-                sol_usd_price.clone()
+                db.clone(),
+                jupiter_client.clone(),
+                current_prices.clone(),
+                sol_usd_price.clone(),
+                metrics.clone(),
             ).await {
                 error!("Error checking open positions: {}", e);
             }
+
+            if let Err(e) = check_position_expiries(db.clone(), &redis_client, current_prices.clone(), &active_strategies).await {
+                error!("Error checking position expiries: {}", e);
+            }
+
+            if let Err(e) = check_funding_rollovers(db.clone(), &redis_client, current_prices.clone(), current_funding_rates.clone(), metrics.clone()).await {
+                error!("Error checking funding rollovers: {}", e);
+            }
         }
 
         tokio::time::sleep(Duration::from_secs(5)).await;
@@ -95,44 +156,326 @@ async fn check_open_positions(
     jupiter: Arc<JupiterClient>,
     current_prices: Arc<Mutex<HashMap<String, f64>>>,
     sol_price: Arc<Mutex<f64>>,
+    metrics: Arc<PositionManagerMetrics>,
 ) -> Result<()> {
     let open_trades = db.get_open_trades()?;
-    let prices = current_prices.lock().await;
-    
+
+    // First pass: scan for triggered trailing stops without holding anything across an await.
+    let mut candidates = Vec::new();
+    {
+        let prices = current_prices.lock().await;
+        for trade in open_trades {
+            if let Some(&current_price) = prices.get(&trade.token_address) {
+                if current_price > trade.highest_price_usd.unwrap_or(trade.entry_price_usd) {
+                    db.update_highest_price(trade.id, current_price)?;
+                }
+
+                let highest = trade.highest_price_usd.unwrap_or(trade.entry_price_usd);
+                let trail_percent = trade.trail_percent_override.unwrap_or(CONFIG.trailing_stop_loss_percent);
+                let trailing_stop_price = highest * (1.0 - trail_percent / 100.0);
+
+                if current_price <= trailing_stop_price {
+                    info!(
+                        "Trailing stop triggered for trade {} at price {:.4} (stop: {:.4})",
+                        trade.id, current_price, trailing_stop_price
+                    );
+                    candidates.push((trade, current_price));
+                }
+            }
+        }
+    }
+
+    // Second pass: drive the closes concurrently, bounded so one hung quote can't
+    // stall every other time-sensitive exit this cycle.
+    stream::iter(candidates)
+        .for_each_concurrent(CONFIG.close_concurrency_limit, |(trade, close_price_usd)| {
+            let db = db.clone();
+            let jupiter = jupiter.clone();
+            let sol_price = sol_price.clone();
+            let metrics = metrics.clone();
+            async move {
+                let trade_id = trade.id;
+                if let Err(e) = execute_close_trade(db, jupiter, sol_price, trade, close_price_usd, metrics).await {
+                    warn!("Failed to close trade {}: {}, will retry next cycle", trade_id, e);
+                }
+            }
+        })
+        .await;
+
+    Ok(())
+}
+
+/// A strategy's own `params.max_hold_secs` overrides the global
+/// `CONFIG.position_expiry_secs` default, so e.g. a fast-rotation strategy
+/// like `AirdropRotation` can hold shorter than a slower one.
+fn max_hold_secs_for(allocation: Option<&StrategyAllocation>) -> i64 {
+    allocation
+        .and_then(|a| a.params.get("max_hold_secs"))
+        .and_then(|v| v.as_i64())
+        .unwrap_or(CONFIG.position_expiry_secs)
+}
+
+/// Time-box every open position: once it reaches its expiry, roll it into a fresh
+/// position at the current price if its strategy still has an active allocation,
+/// otherwise close it outright. Trades without an expiry yet (e.g. opened before
+/// this feature existed) get one lazily assigned from the strategy's max-hold policy.
+async fn check_position_expiries(
+    db: Arc<Database>,
+    redis_client: &redis::Client,
+    current_prices: Arc<Mutex<HashMap<String, f64>>>,
+    active_strategies: &HashMap<String, StrategyAllocation>,
+) -> Result<()> {
+    let now = now_unix();
+    let open_trades = db.get_open_trades()?;
+
     for trade in open_trades {
-        if let Some(&current_price) = prices.get(&trade.token_address) {
-            // Update highest price for trailing stop
-            if current_price > trade.highest_price_usd.unwrap_or(trade.entry_price_usd) {
-                db.update_highest_price(trade.id, current_price)?;
+        let allocation = active_strategies.get(&trade.strategy_id);
+        let expiry_at = match trade.expiry_at {
+            Some(t) => t,
+            None => {
+                let expiry_at = now + max_hold_secs_for(allocation);
+                db.set_expiry(trade.id, expiry_at)?;
+                expiry_at
+            }
+        };
+
+        if now < expiry_at {
+            continue;
+        }
+
+        let Some(&current_price) = current_prices.lock().await.get(&trade.token_address) else {
+            warn!("Trade {} expired but no current price available, will retry next cycle", trade.id);
+            continue;
+        };
+
+        let pnl_usd = if trade.side == Side::Long.to_string() {
+            (current_price - trade.entry_price_usd) * (trade.amount_usd / trade.entry_price_usd)
+        } else {
+            (trade.entry_price_usd - current_price) * (trade.amount_usd / trade.entry_price_usd)
+        };
+
+        if allocation.is_some() {
+            let new_expiry_at = now + max_hold_secs_for(allocation);
+            match db.rollover_trade(&trade, current_price, pnl_usd, current_price, new_expiry_at) {
+                Ok(new_id) => {
+                    info!(
+                        "Rolled over expired trade {} into trade {} for strategy {} at {:.4}",
+                        trade.id, new_id, trade.strategy_id, current_price
+                    );
+                    publish_lifecycle_event(
+                        redis_client,
+                        PositionLifecycleEvent {
+                            trade_id: trade.id,
+                            strategy_id: trade.strategy_id.clone(),
+                            token_address: trade.token_address.clone(),
+                            action: PositionLifecycleAction::RolledOver,
+                            new_trade_id: Some(new_id),
+                            pnl_usd,
+                            timestamp: now,
+                        },
+                    )
+                    .await;
+                }
+                Err(e) => error!("Failed to roll over trade {}: {}", trade.id, e),
             }
-            
-            // Check stop loss conditions
-            let highest = trade.highest_price_usd.unwrap_or(trade.entry_price_usd);
-            let trailing_stop_price = highest * (1.0 - CONFIG.trailing_stop_loss_percent / 100.0);
-            
-            if current_price <= trailing_stop_price {
+        } else {
+            let status = if pnl_usd > 0.0 { "CLOSED_EXPIRED_PROFIT" } else { "CLOSED_EXPIRED_LOSS" };
+            db.update_trade_pnl(trade.id, status, current_price, pnl_usd)?;
+            info!(
+                "Closed expired trade {} for strategy {} (no active allocation) at {:.4}, PnL: ${:.2}",
+                trade.id, trade.strategy_id, current_price, pnl_usd
+            );
+            publish_lifecycle_event(
+                redis_client,
+                PositionLifecycleEvent {
+                    trade_id: trade.id,
+                    strategy_id: trade.strategy_id.clone(),
+                    token_address: trade.token_address.clone(),
+                    action: PositionLifecycleAction::Expired,
+                    new_trade_id: None,
+                    pnl_usd,
+                    timestamp: now,
+                },
+            )
+            .await;
+        }
+    }
+
+    Ok(())
+}
+
+/// How long after its scheduled boundary a rollover is considered "missed"
+/// (caught up late, e.g. because the monitor was down) rather than caught
+/// during its normal ~5s polling cadence.
+const MISSED_FUNDING_ROLLOVER_THRESHOLD_SECS: i64 = 30;
+
+/// Unix timestamp (seconds, UTC) of the next occurrence of
+/// `CONFIG.funding_rollover_weekday` / `CONFIG.funding_rollover_hour_utc`
+/// strictly after `now`.
+fn next_funding_rollover_after(now: i64) -> i64 {
+    use chrono::{Datelike, TimeZone};
+
+    let now_dt = Utc.timestamp_opt(now, 0).single().unwrap_or_else(Utc::now);
+    let mut days_ahead = (CONFIG.funding_rollover_weekday.num_days_from_monday() as i64
+        - now_dt.weekday().num_days_from_monday() as i64)
+        .rem_euclid(7);
+
+    let candidate_at = |days_ahead: i64| {
+        (now_dt.date_naive() + chrono::Duration::days(days_ahead))
+            .and_hms_opt(CONFIG.funding_rollover_hour_utc, 0, 0)
+            .expect("funding_rollover_hour_utc must be 0-23")
+            .and_utc()
+    };
+
+    if candidate_at(days_ahead) <= now_dt {
+        days_ahead += 7;
+    }
+
+    candidate_at(days_ahead).timestamp()
+}
+
+/// Funding-bearing (short/perp) positions roll over on a fixed wall-clock
+/// schedule rather than an elapsed-duration expiry, mirroring how real perp
+/// funding periods settle. The roll is allowed to fire up to
+/// `CONFIG.funding_rollover_pre_expiry_margin_secs` before the scheduled
+/// boundary (so the successor leg is already open once the new funding period
+/// starts) through `CONFIG.funding_rollover_window_secs` after it (covering the
+/// monitor having been down across the boundary). Before that window the
+/// position is left alone; past it, the boundary is not rolled and no funding
+/// is settled for it: the position is only rescheduled to the next boundary.
+/// Checking this on every tick — not just right at the scheduled moment —
+/// means a boundary the process was down across is still noticed on the next
+/// startup (rolled late if still inside the window, rescheduled otherwise)
+/// instead of drifting past its schedule forever.
+async fn check_funding_rollovers(
+    db: Arc<Database>,
+    redis_client: &redis::Client,
+    current_prices: Arc<Mutex<HashMap<String, f64>>>,
+    current_funding_rates: Arc<Mutex<HashMap<String, f64>>>,
+    metrics: Arc<PositionManagerMetrics>,
+) -> Result<()> {
+    let now = now_unix();
+    let open_trades = db.get_open_trades()?;
+
+    for trade in open_trades {
+        if trade.side != Side::Short.to_string() {
+            continue;
+        }
+
+        let rollover_at = match trade.funding_rollover_at {
+            Some(t) => t,
+            None => {
+                db.set_funding_rollover_at(trade.id, next_funding_rollover_after(now))?;
+                continue;
+            }
+        };
+
+        if now < rollover_at - CONFIG.funding_rollover_pre_expiry_margin_secs {
+            continue;
+        }
+
+        if now > rollover_at + CONFIG.funding_rollover_window_secs {
+            warn!(
+                "Trade {} missed its funding rollover window entirely, rescheduling",
+                trade.id
+            );
+            db.set_funding_rollover_at(trade.id, next_funding_rollover_after(now))?;
+            continue;
+        }
+
+        let Some(&current_price) = current_prices.lock().await.get(&trade.token_address) else {
+            warn!("Funding rollover due for trade {} but no current price available, will retry next cycle", trade.id);
+            continue;
+        };
+        let funding_rate_pct = current_funding_rates
+            .lock()
+            .await
+            .get(&trade.token_address)
+            .copied()
+            .unwrap_or(0.0);
+
+        let price_pnl_usd =
+            (trade.entry_price_usd - current_price) * (trade.amount_usd / trade.entry_price_usd);
+        let funding_settled_usd = trade.amount_usd * (funding_rate_pct / 100.0);
+
+        if now - rollover_at > MISSED_FUNDING_ROLLOVER_THRESHOLD_SECS {
+            metrics.funding_rollovers_missed_total.inc();
+            warn!(
+                "Funding rollover for trade {} ran {}s late (likely caught up after downtime)",
+                trade.id,
+                now - rollover_at
+            );
+        }
+
+        let new_rollover_at = next_funding_rollover_after(now);
+        match db.rollover_funding_position(&trade, price_pnl_usd, funding_settled_usd, current_price, new_rollover_at) {
+            Ok(new_id) => {
+                metrics.funding_rollovers_total.inc();
                 info!(
-                    "Trailing stop triggered for trade {} at price {:.4} (stop: {:.4})",
-                    trade.id, current_price, trailing_stop_price
+                    "Funding-rolled trade {} into trade {} for strategy {} at {:.4}, funding settled ${:.2}",
+                    trade.id, new_id, trade.strategy_id, current_price, funding_settled_usd
                 );
-                execute_close_trade(db.clone(), jupiter.clone(), sol_price.clone(), trade, current_price).await?;
+                publish_lifecycle_event(
+                    redis_client,
+                    PositionLifecycleEvent {
+                        trade_id: trade.id,
+                        strategy_id: trade.strategy_id.clone(),
+                        token_address: trade.token_address.clone(),
+                        action: PositionLifecycleAction::FundingRolledOver,
+                        new_trade_id: Some(new_id),
+                        pnl_usd: price_pnl_usd + funding_settled_usd,
+                        timestamp: now,
+                    },
+                )
+                .await;
             }
+            Err(e) => error!("Failed to funding-roll trade {}: {}", trade.id, e),
         }
     }
-    
+
     Ok(())
 }
 
+const POSITION_LIFECYCLE_STREAM: &str = "events:position_lifecycle";
+
+async fn publish_lifecycle_event(redis_client: &redis::Client, event: PositionLifecycleEvent) {
+    let mut conn = match redis_client.get_multiplexed_async_connection().await {
+        Ok(c) => c,
+        Err(e) => {
+            error!("Failed to get redis connection to publish position lifecycle event: {}", e);
+            return;
+        }
+    };
+    let payload = match serde_json::to_string(&event) {
+        Ok(p) => p,
+        Err(e) => {
+            error!("Failed to serialize position lifecycle event: {}", e);
+            return;
+        }
+    };
+    let result: redis::RedisResult<()> = conn.xadd(POSITION_LIFECYCLE_STREAM, "*", &[("data", payload)]).await;
+    if let Err(e) = result {
+        error!("Failed to publish position lifecycle event to {}: {}", POSITION_LIFECYCLE_STREAM, e);
+    }
+}
+
 async fn execute_close_trade(
     db: Arc<Database>,
     jupiter: Arc<JupiterClient>,
     sol_price: Arc<Mutex<f64>>,
     trade: TradeRecord,
     close_price_usd: f64,
+    metrics: Arc<PositionManagerMetrics>,
 ) -> Result<()> {
     info!("Executing close trade for trade_id: {}", trade.id);
-    
-    let user_pk = Pubkey::from_str(&crate::signer_client::get_pubkey(&CONFIG.signer_url).await?)?;
+
+    let quote_timeout = Duration::from_millis(CONFIG.jupiter_quote_timeout_ms);
+
+    let user_pk = Pubkey::from_str(
+        &tokio::time::timeout(quote_timeout, crate::signer_client::get_pubkey(&CONFIG.signer_url))
+            .await
+            .map_err(|_| anyhow!("Timed out fetching signer pubkey for trade {}", trade.id))??,
+    )?;
     let _current_sol_price = *sol_price.lock().await;
 
     let pnl_usd = if trade.side == Side::Long.to_string() {
@@ -142,14 +485,29 @@ async fn execute_close_trade(
     };
 
     if trade.side == Side::Long.to_string() {
-        let swap_tx_b64 = jupiter.get_swap_transaction(
-            &user_pk, 
-            &trade.token_address, 
-            "So11111111111111111111111111111111111111112", // SOL mint
-            trade.amount_usd, 
-            30
-        ).await?;
-        let _signed_tx_b64 = crate::signer_client::sign_transaction(&CONFIG.signer_url, &swap_tx_b64).await?;
+        let quote_started_at = std::time::Instant::now();
+        let swap_tx_b64 = tokio::time::timeout(
+            quote_timeout,
+            jupiter.get_swap_transaction(
+                &user_pk,
+                &trade.token_address,
+                "So11111111111111111111111111111111111111112", // SOL mint
+                trade.amount_usd,
+                30,
+            ),
+        )
+        .await
+        .map_err(|_| anyhow!("Timed out fetching Jupiter quote for trade {}", trade.id))??;
+        metrics
+            .jupiter_quote_latency
+            .record_ms(quote_started_at.elapsed().as_millis() as u64);
+
+        let _signed_tx_b64 = tokio::time::timeout(
+            quote_timeout,
+            crate::signer_client::sign_transaction(&CONFIG.signer_url, &swap_tx_b64),
+        )
+        .await
+        .map_err(|_| anyhow!("Timed out signing close transaction for trade {}", trade.id))??;
         info!("Position closed via Jupiter swap");
     } else {
         info!("Closing SHORT position via Drift (simulated)");
@@ -163,8 +521,8 @@ async fn execute_close_trade(
     let redis_client = redis::Client::open(CONFIG.redis_url.clone())?;
     let mut conn = redis_client.get_multiplexed_async_connection().await?;
     let _: () = conn.xadd(
-        "metrics:portfolio:realized_pnl_stream", 
-        "*", 
+        "metrics:portfolio:realized_pnl_stream",
+        "*",
         &[("pnl", pnl_usd.to_string().as_bytes())]
     ).await?;
 
@@ -21,6 +21,7 @@ pub async fn sign_transaction(signer_url: &str, tx_b64: &str) -> Result<String>
     let url = format!("{}/sign", signer_url);
     let request = shared_models::SignRequest {
         transaction_b64: tx_b64.to_string(),
+        partial: false,
     };
 
     let response: shared_models::SignResponse = client
@@ -3,6 +3,12 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Consecutive terminal execution failures (e.g. `ConfirmationTracker`
+/// exhausting its rebroadcast retries) before the breaker trips to Critical
+/// on its own, independent of realized drawdown — repeated failures to land
+/// a transaction are themselves a sign something is wrong with execution.
+const EXECUTION_FAILURE_TRIP_THRESHOLD: u64 = 3;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CircuitState {
     Closed,      // Normal operation
@@ -10,21 +16,63 @@ pub enum CircuitState {
     Open,        // Emergency stop
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum RiskLevel {
     Normal,      // < 5% drawdown
-    Warning,     // 5-10% drawdown  
+    Warning,     // 5-10% drawdown
     Critical,    // 10-15% drawdown
     Emergency,   // > 15% drawdown
 }
 
+/// Trip/reset thresholds, dwell time, and probe count the state machine runs
+/// on, so the 5/10/15 ladder is a tunable default rather than hardcoded.
+/// Trip thresholds gate escalation (crossed going up, applied immediately);
+/// reset thresholds gate de-escalation (crossed going down, only applied
+/// after `min_dwell` has elapsed at the current level, and — for leaving
+/// `Open` specifically — only after `halfopen_probe_count` consecutive
+/// `update_drawdown` calls all land below the reset threshold).
+#[derive(Debug, Clone, Copy)]
+pub struct CircuitBreakerConfig {
+    pub warning_trip_pct: f64,
+    pub warning_reset_pct: f64,
+    pub critical_trip_pct: f64,
+    pub critical_reset_pct: f64,
+    pub emergency_trip_pct: f64,
+    pub emergency_reset_pct: f64,
+    /// Minimum time at a level before any downgrade out of it is considered.
+    pub min_dwell_secs: u64,
+    /// Consecutive improved `update_drawdown` calls required while `HalfOpen`
+    /// before the breaker fully closes back to the next level down.
+    pub halfopen_probe_count: u32,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            warning_trip_pct: 5.0,
+            warning_reset_pct: 3.5,
+            critical_trip_pct: 10.0,
+            critical_reset_pct: 7.0,
+            emergency_trip_pct: 15.0,
+            emergency_reset_pct: 12.0,
+            min_dwell_secs: 60,
+            halfopen_probe_count: 3,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
     state: Arc<AtomicU64>, // Stores CircuitState as u64
     risk_level: Arc<AtomicU64>, // Stores RiskLevel as u64
     last_state_change: Arc<AtomicU64>, // Unix timestamp
     trading_halted: Arc<AtomicBool>,
     position_size_multiplier: Arc<AtomicU64>, // Scaled by 1000 (e.g., 500 = 0.5x)
+    consecutive_execution_failures: Arc<AtomicU64>,
+    /// Consecutive `update_drawdown` calls, while `HalfOpen`, that landed
+    /// below the reset threshold for the level being recovered from.
+    halfopen_probe_streak: Arc<AtomicU64>,
 }
 
 impl Default for CircuitBreaker {
@@ -35,47 +83,124 @@ impl Default for CircuitBreaker {
 
 impl CircuitBreaker {
     pub fn new() -> Self {
+        Self::with_config(CircuitBreakerConfig::default())
+    }
+
+    pub fn with_config(config: CircuitBreakerConfig) -> Self {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
 
         Self {
+            config,
             state: Arc::new(AtomicU64::new(CircuitState::Closed as u64)),
             risk_level: Arc::new(AtomicU64::new(RiskLevel::Normal as u64)),
             last_state_change: Arc::new(AtomicU64::new(now)),
             trading_halted: Arc::new(AtomicBool::new(false)),
             position_size_multiplier: Arc::new(AtomicU64::new(1000)), // 1.0x
+            consecutive_execution_failures: Arc::new(AtomicU64::new(0)),
+            halfopen_probe_streak: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Feeds a fresh drawdown reading through the hysteresis/dwell-time state
+    /// machine. Escalation (crossing a trip threshold further out than the
+    /// current level) always applies immediately — there's no reason to
+    /// delay tightening risk controls. De-escalation is gated by
+    /// `min_dwell_secs`, the lower reset threshold, and — when leaving
+    /// `Open` — a `HalfOpen` probation of `halfopen_probe_count` consecutive
+    /// improved readings, so a drawdown oscillating around a threshold can't
+    /// flap the position-size multiplier back and forth.
     pub fn update_drawdown(&self, current_drawdown_pct: f64) -> RiskLevel {
-        let new_risk_level = match current_drawdown_pct {
-            x if x < 5.0 => RiskLevel::Normal,
-            x if x < 10.0 => RiskLevel::Warning,
-            x if x < 15.0 => RiskLevel::Critical,
-            _ => RiskLevel::Emergency,
-        };
+        let old_level = self.get_risk_level();
+        let trip_level = self.trip_level(current_drawdown_pct);
 
-        let old_risk_level = self.get_risk_level();
-        
-        if new_risk_level != old_risk_level {
-            self.risk_level.store(new_risk_level as u64, Ordering::SeqCst);
-            self.handle_risk_level_change(old_risk_level, new_risk_level, current_drawdown_pct);
+        if trip_level > old_level {
+            self.halfopen_probe_streak.store(0, Ordering::SeqCst);
+            self.set_risk_level(trip_level);
+            return trip_level;
         }
 
-        new_risk_level
+        match self.get_state() {
+            CircuitState::Open => {
+                if self.time_since_last_change() >= self.config.min_dwell_secs
+                    && current_drawdown_pct < self.reset_threshold(old_level)
+                {
+                    self.enter_half_open();
+                }
+                old_level
+            }
+            CircuitState::HalfOpen => {
+                if current_drawdown_pct < self.reset_threshold(old_level) {
+                    let streak = self.halfopen_probe_streak.fetch_add(1, Ordering::SeqCst) + 1;
+                    if streak >= self.config.halfopen_probe_count as u64 {
+                        let new_level = next_lower(old_level);
+                        self.halfopen_probe_streak.store(0, Ordering::SeqCst);
+                        self.set_risk_level(new_level);
+                        new_level
+                    } else {
+                        old_level
+                    }
+                } else {
+                    // Regressed mid-probation; snap back to Open and restart the cooldown.
+                    self.halfopen_probe_streak.store(0, Ordering::SeqCst);
+                    self.state.store(CircuitState::Open as u64, Ordering::SeqCst);
+                    self.touch_last_state_change();
+                    old_level
+                }
+            }
+            CircuitState::Closed => {
+                if old_level != RiskLevel::Normal
+                    && current_drawdown_pct < self.reset_threshold(old_level)
+                    && self.time_since_last_change() >= self.config.min_dwell_secs
+                {
+                    let new_level = next_lower(old_level);
+                    self.set_risk_level(new_level);
+                    new_level
+                } else {
+                    old_level
+                }
+            }
+        }
     }
 
-    fn handle_risk_level_change(&self, _old: RiskLevel, new: RiskLevel, _drawdown: f64) {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
-        self.last_state_change.store(now, Ordering::SeqCst);
+    fn trip_level(&self, drawdown_pct: f64) -> RiskLevel {
+        if drawdown_pct >= self.config.emergency_trip_pct {
+            RiskLevel::Emergency
+        } else if drawdown_pct >= self.config.critical_trip_pct {
+            RiskLevel::Critical
+        } else if drawdown_pct >= self.config.warning_trip_pct {
+            RiskLevel::Warning
+        } else {
+            RiskLevel::Normal
+        }
+    }
+
+    /// The drawdown a de-escalation away from `level` must fall below.
+    /// `Normal` has no threshold since there's nothing lower to fall to.
+    fn reset_threshold(&self, level: RiskLevel) -> f64 {
+        match level {
+            RiskLevel::Emergency => self.config.emergency_reset_pct,
+            RiskLevel::Critical => self.config.critical_reset_pct,
+            RiskLevel::Warning => self.config.warning_reset_pct,
+            RiskLevel::Normal => f64::NEG_INFINITY,
+        }
+    }
+
+    fn enter_half_open(&self) {
+        self.state.store(CircuitState::HalfOpen as u64, Ordering::SeqCst);
+        self.halfopen_probe_streak.store(0, Ordering::SeqCst);
+        self.touch_last_state_change();
+    }
 
-        match new {
+    /// Applies `level`, updating `risk_level`, the derived trading controls,
+    /// and the breaker's `CircuitState`, and resets the dwell clock.
+    fn set_risk_level(&self, level: RiskLevel) {
+        self.risk_level.store(level as u64, Ordering::SeqCst);
+        self.touch_last_state_change();
+
+        match level {
             RiskLevel::Normal => {
                 // Full trading resumed
                 self.position_size_multiplier.store(1000, Ordering::SeqCst); // 1.0x
@@ -86,7 +211,7 @@ impl CircuitBreaker {
                 // Reduce position sizes by 50%
                 self.position_size_multiplier.store(500, Ordering::SeqCst); // 0.5x
                 self.trading_halted.store(false, Ordering::SeqCst);
-                self.state.store(CircuitState::HalfOpen as u64, Ordering::SeqCst);
+                self.state.store(CircuitState::Closed as u64, Ordering::SeqCst);
             }
             RiskLevel::Critical => {
                 // Close only mode
@@ -103,6 +228,40 @@ impl CircuitBreaker {
         }
     }
 
+    fn touch_last_state_change(&self) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        self.last_state_change.store(now, Ordering::SeqCst);
+    }
+
+    /// Called when a live trade's transaction terminally fails to land (e.g.
+    /// `ConfirmationTracker` exhausted its rebroadcast retries). After
+    /// `EXECUTION_FAILURE_TRIP_THRESHOLD` in a row, trips the breaker to
+    /// Critical even if drawdown hasn't moved, since an execution pipeline
+    /// that can't land transactions is unsafe to keep trading on regardless
+    /// of PnL. Does not escalate further if already Critical/Emergency.
+    pub fn record_execution_failure(&self) {
+        let failures = self.consecutive_execution_failures.fetch_add(1, Ordering::SeqCst) + 1;
+        if failures < EXECUTION_FAILURE_TRIP_THRESHOLD {
+            return;
+        }
+
+        let old_risk_level = self.get_risk_level();
+        if matches!(old_risk_level, RiskLevel::Normal | RiskLevel::Warning) {
+            self.halfopen_probe_streak.store(0, Ordering::SeqCst);
+            self.set_risk_level(RiskLevel::Critical);
+        }
+    }
+
+    /// Resets the consecutive-failure counter on a trade that lands
+    /// successfully, so an isolated blip doesn't linger toward the trip
+    /// threshold indefinitely.
+    pub fn record_execution_success(&self) {
+        self.consecutive_execution_failures.store(0, Ordering::SeqCst);
+    }
+
     pub fn get_state(&self) -> CircuitState {
         match self.state.load(Ordering::SeqCst) {
             0 => CircuitState::Closed,
@@ -146,12 +305,9 @@ impl CircuitBreaker {
         self.risk_level.store(RiskLevel::Normal as u64, Ordering::SeqCst);
         self.trading_halted.store(false, Ordering::SeqCst);
         self.position_size_multiplier.store(1000, Ordering::SeqCst);
-        
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        self.last_state_change.store(now, Ordering::SeqCst);
+        self.consecutive_execution_failures.store(0, Ordering::SeqCst);
+        self.halfopen_probe_streak.store(0, Ordering::SeqCst);
+        self.touch_last_state_change();
     }
 
     /// Get time since last state change in seconds
@@ -165,6 +321,15 @@ impl CircuitBreaker {
     }
 }
 
+fn next_lower(level: RiskLevel) -> RiskLevel {
+    match level {
+        RiskLevel::Emergency => RiskLevel::Critical,
+        RiskLevel::Critical => RiskLevel::Warning,
+        RiskLevel::Warning => RiskLevel::Normal,
+        RiskLevel::Normal => RiskLevel::Normal,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,39 +337,83 @@ mod tests {
     #[test]
     fn test_circuit_breaker_states() {
         let cb = CircuitBreaker::new();
-        
+
         // Initial state
         assert_eq!(cb.get_risk_level(), RiskLevel::Normal);
         assert_eq!(cb.get_state(), CircuitState::Closed);
         assert!(cb.is_trading_allowed());
         assert_eq!(cb.get_position_size_multiplier(), 1.0);
 
-        // Warning level
+        // Crossing a trip threshold escalates immediately, no hysteresis on the way up.
         cb.update_drawdown(7.5);
         assert_eq!(cb.get_risk_level(), RiskLevel::Warning);
-        assert_eq!(cb.get_state(), CircuitState::HalfOpen);
+        assert_eq!(cb.get_state(), CircuitState::Closed);
         assert!(cb.is_trading_allowed());
         assert_eq!(cb.get_position_size_multiplier(), 0.5);
 
-        // Critical level
         cb.update_drawdown(12.0);
         assert_eq!(cb.get_risk_level(), RiskLevel::Critical);
         assert_eq!(cb.get_state(), CircuitState::Open);
         assert!(cb.is_trading_allowed()); // Can still close
         assert_eq!(cb.get_position_size_multiplier(), 0.0);
 
-        // Emergency level
         cb.update_drawdown(20.0);
         assert_eq!(cb.get_risk_level(), RiskLevel::Emergency);
         assert_eq!(cb.get_state(), CircuitState::Open);
         assert!(!cb.is_trading_allowed()); // Complete halt
         assert_eq!(cb.get_position_size_multiplier(), 0.0);
 
-        // Recovery
+        // An improved reading doesn't de-escalate on its own: dwell time
+        // hasn't elapsed and Open only leaves via a HalfOpen probation.
         cb.update_drawdown(2.0);
-        assert_eq!(cb.get_risk_level(), RiskLevel::Normal);
-        assert_eq!(cb.get_state(), CircuitState::Closed);
-        assert!(cb.is_trading_allowed());
-        assert_eq!(cb.get_position_size_multiplier(), 1.0);
+        assert_eq!(cb.get_risk_level(), RiskLevel::Emergency);
+        assert_eq!(cb.get_state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn test_halfopen_probe_recovers_one_level_at_a_time() {
+        let cb = CircuitBreaker::with_config(CircuitBreakerConfig {
+            min_dwell_secs: 0, // no real-time dependency in tests
+            halfopen_probe_count: 2,
+            ..CircuitBreakerConfig::default()
+        });
+
+        cb.update_drawdown(20.0);
+        assert_eq!(cb.get_risk_level(), RiskLevel::Emergency);
+        assert_eq!(cb.get_state(), CircuitState::Open);
+
+        // First improved reading (dwell already elapsed) moves Open -> HalfOpen probation.
+        cb.update_drawdown(2.0);
+        assert_eq!(cb.get_risk_level(), RiskLevel::Emergency);
+        assert_eq!(cb.get_state(), CircuitState::HalfOpen);
+
+        // One more improved reading is still short of halfopen_probe_count.
+        cb.update_drawdown(2.0);
+        assert_eq!(cb.get_risk_level(), RiskLevel::Emergency);
+        assert_eq!(cb.get_state(), CircuitState::HalfOpen);
+
+        // Second consecutive improved reading hits the probe count: fully
+        // closes one level down (Emergency -> Critical), not all the way to Normal.
+        cb.update_drawdown(2.0);
+        assert_eq!(cb.get_risk_level(), RiskLevel::Critical);
+        assert_eq!(cb.get_state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn test_halfopen_probe_snaps_back_on_regression() {
+        let cb = CircuitBreaker::with_config(CircuitBreakerConfig {
+            min_dwell_secs: 0,
+            halfopen_probe_count: 2,
+            ..CircuitBreakerConfig::default()
+        });
+
+        cb.update_drawdown(20.0);
+        cb.update_drawdown(2.0); // Open -> HalfOpen
+        assert_eq!(cb.get_state(), CircuitState::HalfOpen);
+
+        // A reading back above the reset threshold mid-probation snaps back to Open.
+        cb.update_drawdown(18.0);
+        assert_eq!(cb.get_risk_level(), RiskLevel::Emergency);
+        assert_eq!(cb.get_state(), CircuitState::Open);
     }
 }
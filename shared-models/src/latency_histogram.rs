@@ -0,0 +1,54 @@
+use hdrhistogram::Histogram;
+use std::sync::Mutex;
+
+/// Thread-safe HDR histogram for recording millisecond latencies and
+/// periodically snapshotting percentiles for Prometheus gauges. Values are
+/// clamped to `[1, 60_000]` ms; anything outside that range is still recorded
+/// at the nearest bound so one outlier can't poison the histogram.
+pub struct LatencyHistogram {
+    inner: Mutex<Histogram<u64>>,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self {
+            inner: Mutex::new(
+                Histogram::new_with_bounds(1, 60_000, 3).expect("valid HDR histogram bounds"),
+            ),
+        }
+    }
+
+    pub fn record_ms(&self, millis: u64) {
+        let clamped = millis.clamp(1, 60_000);
+        if let Ok(mut h) = self.inner.lock() {
+            let _ = h.record(clamped);
+        }
+    }
+
+    /// Returns (p50, p90, p99) in milliseconds.
+    pub fn percentiles(&self) -> (f64, f64, f64) {
+        let h = match self.inner.lock() {
+            Ok(h) => h,
+            Err(_) => return (0.0, 0.0, 0.0),
+        };
+        (
+            h.value_at_quantile(0.50) as f64,
+            h.value_at_quantile(0.90) as f64,
+            h.value_at_quantile(0.99) as f64,
+        )
+    }
+
+    /// Largest recorded latency in milliseconds.
+    pub fn max_ms(&self) -> f64 {
+        match self.inner.lock() {
+            Ok(h) => h.max() as f64,
+            Err(_) => 0.0,
+        }
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
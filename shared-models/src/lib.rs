@@ -1,5 +1,13 @@
 use serde::{Deserialize, Serialize};
 
+pub mod tick_gate;
+pub use tick_gate::TickGate;
+
+pub mod latency_histogram;
+pub use latency_histogram::LatencyHistogram;
+
+pub mod migrations;
+
 // Event Types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EventType {
@@ -12,6 +20,9 @@ pub enum EventType {
     SolPrice,
     TwitterRaw,
     FarcasterRaw,
+    Candle,
+    Fill,
+    MarkPrice,
 }
 
 impl EventType {
@@ -26,6 +37,9 @@ impl EventType {
             EventType::SolPrice => "sol_price",
             EventType::TwitterRaw => "twitter_raw",
             EventType::FarcasterRaw => "farcaster_raw",
+            EventType::Candle => "candle",
+            EventType::Fill => "fill",
+            EventType::MarkPrice => "mark_price",
         }
     }
 }
@@ -42,6 +56,19 @@ pub enum MarketEvent {
     SolPrice(SolPriceEvent),
     TwitterRaw(TwitterRawEvent),
     FarcasterRaw(FarcasterRawEvent),
+    /// A just-completed OHLCV bucket, republished from `Candle` so
+    /// momentum/breakout strategies can subscribe to it the same way they
+    /// subscribe to any other market event instead of reading `events:candles`
+    /// directly.
+    Candle(Candle),
+    /// A trade settlement record, emitted by the executor once a fill is
+    /// known (real or simulated) so PnL/dashboard consumers can subscribe to
+    /// it the same way they subscribe to any other market event.
+    Fill(FillEvent),
+    /// A perp mark price, distinct from `Price` (spot): basis-trading
+    /// strategies need both to compute `(mark - spot) / spot` rather than
+    /// treating the funding rate itself as the basis.
+    MarkPrice(MarkPriceEvent),
 }
 
 impl MarketEvent {
@@ -56,9 +83,24 @@ impl MarketEvent {
             MarketEvent::SolPrice(_) => EventType::SolPrice,
             MarketEvent::TwitterRaw(_) => EventType::TwitterRaw,
             MarketEvent::FarcasterRaw(_) => EventType::FarcasterRaw,
+            MarketEvent::Candle(_) => EventType::Candle,
+            MarketEvent::Fill(_) => EventType::Fill,
+            MarketEvent::MarkPrice(_) => EventType::MarkPrice,
+        }
+    }
+
+    /// Monotonic per-token ordering key for event types whose value can
+    /// "rewind" (a price update applied out of order must not clobber a
+    /// fresher one already applied). `None` for event types with no natural
+    /// sequence notion, which bypass reorder-buffering entirely.
+    pub fn sequence(&self) -> Option<i64> {
+        match self {
+            MarketEvent::Price(e) => Some(e.timestamp_ms),
+            MarketEvent::SolPrice(e) => Some(e.publish_time),
+            _ => None,
         }
     }
-    
+
     pub fn token(&self) -> &str {
         match self {
             MarketEvent::Price(e) => &e.token_address,
@@ -70,7 +112,140 @@ impl MarketEvent {
             MarketEvent::SolPrice(_) => "SOL",
             MarketEvent::TwitterRaw(_) => "",
             MarketEvent::FarcasterRaw(_) => "",
+            MarketEvent::Candle(e) => &e.token_address,
+            MarketEvent::Fill(e) => &e.token_address,
+            MarketEvent::MarkPrice(e) => &e.token_address,
+        }
+    }
+}
+
+/// One completed OHLCV bucket for a token at a given aggregation interval.
+/// Published on `events:candles` once its bucket rolls over, and again as
+/// `MarketEvent::Candle` on `events:price` so strategies can subscribe to it
+/// like any other market event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Candle {
+    pub token_address: String,
+    /// One of "1m", "5m", "15m", "1h".
+    pub interval: String,
+    pub bucket_start_ts: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume_usd: f64,
+}
+
+/// Published on `events:position_lifecycle` whenever the position manager's
+/// expiry or funding-rollover sweep rolls a position forward, or the expiry
+/// sweep force-closes it, so dashboards
+/// and alerting don't have to scrape trade status columns to notice.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionLifecycleEvent {
+    pub trade_id: i64,
+    pub strategy_id: String,
+    pub token_address: String,
+    pub action: PositionLifecycleAction,
+    /// Set when `action` is `RolledOver` or `FundingRolledOver`: the id of
+    /// the successor trade.
+    #[serde(default)]
+    pub new_trade_id: Option<i64>,
+    pub pnl_usd: f64,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionLifecycleAction {
+    RolledOver,
+    Expired,
+    /// A funding-bearing (short/perp) position was closed and reopened at its
+    /// scheduled funding rollover boundary rather than its elapsed-duration expiry.
+    FundingRolledOver,
+}
+
+/// One completed OHLC bucket of portfolio NAV, built from `nav_snapshots`
+/// rows the same way `Candle` is built from price ticks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NavCandle {
+    /// One of "1m", "5m", "1h".
+    pub interval: String,
+    pub bucket_start_ts: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+/// A precise on-chain token amount: `raw` base units (e.g. lamports, or a
+/// SPL mint's smallest unit) plus the mint's `decimals`. Quote/swap amounts
+/// and anything persisted to the trades table should carry this instead of
+/// an `f64` USD/human value, so conversions between human-readable amounts
+/// and on-chain base units are exact instead of accumulating rounding error.
+///
+/// Serializes as a single JSON string of `raw` (decimal, or `0x`-prefixed
+/// hex) so it drops straight into a field like Jupiter's `outAmount`, which
+/// the API already represents as a plain base-unit decimal string.
+/// `decimals` isn't part of the wire format — the API doesn't send it, so it
+/// isn't known at the point of deserializing one of these strings — and is
+/// set to `DEFAULT_DECIMALS` on deserialize; callers that know the real
+/// per-mint decimals should overwrite the field once parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenAmount {
+    pub raw: u128,
+    pub decimals: u8,
+}
+
+impl TokenAmount {
+    /// Decimals assumed when the real per-mint decimals aren't known (no
+    /// on-chain mint lookup exists in this codebase yet). Matches the
+    /// decimals of SOL and most SPL meme-coin mints closely enough to be a
+    /// reasonable placeholder; call sites that know the real value should
+    /// always pass it explicitly instead of relying on this.
+    pub const DEFAULT_DECIMALS: u8 = 9;
+
+    pub fn new(raw: u128, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    /// Build from a human-readable amount (e.g. "12.5" tokens), rounding to
+    /// the nearest base unit. Only safe to use at display/input boundaries;
+    /// prefer carrying the raw base-unit amount everywhere else.
+    pub fn from_human(amount: f64, decimals: u8) -> Self {
+        let raw = (amount * 10f64.powi(decimals as i32)).round();
+        Self { raw: raw.max(0.0) as u128, decimals }
+    }
+
+    /// Convert to a human-readable `f64`. Only for display/logging/metrics —
+    /// PnL and sizing math should stay in base units until this boundary.
+    pub fn to_f64(&self) -> f64 {
+        self.raw as f64 / 10f64.powi(self.decimals as i32)
+    }
+}
+
+impl Serialize for TokenAmount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.raw.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for TokenAmount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        let parsed = if let Some(hex) = raw.strip_prefix("0x") {
+            u128::from_str_radix(hex, 16)
+        } else {
+            raw.parse::<u128>()
         }
+        .map_err(serde::de::Error::custom)?;
+
+        Ok(TokenAmount { raw: parsed, decimals: TokenAmount::DEFAULT_DECIMALS })
     }
 }
 
@@ -80,6 +255,11 @@ pub struct PriceTick {
     pub token_address: String,
     pub price_usd: f64,
     pub volume_usd_1m: f64,
+    /// Unix timestamp (millis) this tick was observed at the source. Zero for
+    /// producers that don't have a real clock to report, which always loses
+    /// ordering ties against a timestamped tick for the same token.
+    #[serde(default)]
+    pub timestamp_ms: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,6 +290,21 @@ pub struct BridgeEvent {
 pub struct FundingEvent {
     pub token_address: String,
     pub funding_rate_pct: f64,
+    /// Unix timestamp (millis) this rate was published at the source, so a
+    /// staleness guard can tell a frozen feed apart from a genuinely
+    /// near-zero funding rate. Zero for producers that don't have a real
+    /// clock to report.
+    #[serde(default)]
+    pub timestamp_ms: i64,
+}
+
+/// A perp's current mark price, kept separate from `PriceTick`'s spot price
+/// so `basis_pct = (mark_price_usd - spot) / spot * 100` can be computed
+/// instead of conflating the two.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkPriceEvent {
+    pub token_address: String,
+    pub mark_price_usd: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -122,6 +317,10 @@ pub struct OnChainEvent {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SolPriceEvent {
     pub price_usd: f64,
+    /// Unix timestamp (seconds) the price was published at the source, e.g. Pyth/Hermes.
+    /// Zero for producers that don't have a real publish time to report.
+    #[serde(default)]
+    pub publish_time: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -156,20 +355,204 @@ impl std::fmt::Display for Side {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TradeMode {
     Simulating,
     Paper,
     Live,
 }
 
+/// Canonical settlement record for a trade, emitted by every `TradeMode`
+/// (simulating/paper/live) through one code path instead of each mode
+/// writing its own ad-hoc JSON, so downstream PnL and dashboards read
+/// consistent units regardless of which mode produced the fill.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillEvent {
+    pub trade_id: i64,
+    pub strategy_id: String,
+    pub token_address: String,
+    pub side: Side,
+    /// Which mode the order that produced this fill was running under, so a
+    /// consumer watching a mixed stream (e.g. a fan-out server) can tell a
+    /// real fill apart from a paper one without joining back to the trade.
+    pub mode: TradeMode,
+    pub price_usd: f64,
+    /// Which `LatestRate` source (e.g. "jupiter", "sol_price_oracle",
+    /// "external_reference") actually filled `price_usd`, so disagreements
+    /// between oracles are auditable after the fact.
+    pub price_source: String,
+    /// Normalized UI/USD value of the fill, derived from `filled_size_token`
+    /// and `price_usd` (not the order's originally requested size), so it
+    /// reflects what the quote actually returned rather than what was asked for.
+    pub filled_size_usd: f64,
+    /// Exact on-chain base-unit amount the fill represents.
+    pub filled_size_token: TokenAmount,
+    pub fee_usd: f64,
+    pub slippage_pct: f64,
+    pub status: FillStatus,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FillStatus {
+    New,
+    /// The fill was later found to have failed on-chain (e.g. the signed
+    /// transaction never landed); consumers should retract any position or
+    /// PnL they derived from the matching `New` fill rather than keep a
+    /// phantom position open.
+    Revoked,
+}
+
+/// A position-sizing USD amount, stored as integer micro-USD (1 `Usd` ==
+/// 1_000_000 micros) so order sizing and volume thresholds compare
+/// deterministically instead of accumulating `f64` rounding error across
+/// platforms. Only feed-ingest and (de)serialization boundaries convert
+/// through `f64`; arithmetic elsewhere stays in micros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Usd {
+    micros: i128,
+}
+
+impl Usd {
+    pub const ZERO: Usd = Usd { micros: 0 };
+    const MICROS_PER_UNIT: f64 = 1_000_000.0;
+
+    /// Build from a human-readable USD amount. Only safe at ingest/config
+    /// boundaries; prefer carrying `Usd` everywhere past that point.
+    pub fn from_f64(amount: f64) -> Self {
+        Self { micros: (amount * Self::MICROS_PER_UNIT).round() as i128 }
+    }
+
+    /// Convert to a human-readable `f64`. Only for display/logging/metrics
+    /// and for handing off to APIs (e.g. Jupiter) that speak `f64`.
+    pub fn to_f64(&self) -> f64 {
+        self.micros as f64 / Self::MICROS_PER_UNIT
+    }
+
+    pub fn checked_add(self, other: Usd) -> Option<Usd> {
+        self.micros.checked_add(other.micros).map(|micros| Usd { micros })
+    }
+
+    pub fn checked_sub(self, other: Usd) -> Option<Usd> {
+        self.micros.checked_sub(other.micros).map(|micros| Usd { micros })
+    }
+
+    /// Scale by a dimensionless factor (e.g. a circuit-breaker size cut).
+    /// Goes through `f64` since the factor itself carries no currency unit.
+    pub fn checked_scale(self, factor: f64) -> Option<Usd> {
+        let scaled = self.micros as f64 * factor;
+        scaled.is_finite().then(|| Usd { micros: scaled.round() as i128 })
+    }
+
+    pub fn min(self, other: Usd) -> Usd {
+        if self <= other { self } else { other }
+    }
+}
+
+impl Default for Usd {
+    fn default() -> Self {
+        Usd::ZERO
+    }
+}
+
+impl std::fmt::Display for Usd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.2}", self.to_f64())
+    }
+}
+
+impl Serialize for Usd {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_f64(self.to_f64())
+    }
+}
+
+impl<'de> Deserialize<'de> for Usd {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Usd::from_f64(f64::deserialize(deserializer)?))
+    }
+}
+
+/// A per-token USD price, stored as integer nano-USD (1e-9). Kept distinct
+/// from `Usd`: meme-coin prices routinely carry more significant digits
+/// after the decimal point than `Usd`'s micro-USD precision would preserve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Price {
+    nanos: i128,
+}
+
+impl Price {
+    const NANOS_PER_UNIT: f64 = 1_000_000_000.0;
+
+    pub fn from_f64(amount: f64) -> Self {
+        Self { nanos: (amount * Self::NANOS_PER_UNIT).round() as i128 }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.nanos as f64 / Self::NANOS_PER_UNIT
+    }
+}
+
+impl std::fmt::Display for Price {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.9}", self.to_f64())
+    }
+}
+
+impl Serialize for Price {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_f64(self.to_f64())
+    }
+}
+
+impl<'de> Deserialize<'de> for Price {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Price::from_f64(f64::deserialize(deserializer)?))
+    }
+}
+
 // Strategy Types
+/// How an `OrderDetails` should be worked, instead of every strategy signal
+/// implying an immediate market fill. `Limit`/`TrailingStop` are gated or
+/// managed by the execution layer rather than sent naked to Jupiter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum OrderType {
+    Market,
+    /// Only execute once the quoted price is at or better than
+    /// `trigger_price_usd` for the order's `Side`.
+    Limit { trigger_price_usd: Price },
+    /// Enter at market, then have the execution layer manage the exit with a
+    /// stop that trails the position by `trail_percent`.
+    TrailingStop { trail_percent: f64 },
+}
+
+impl Default for OrderType {
+    fn default() -> Self {
+        OrderType::Market
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderDetails {
     pub token_address: String,
-    pub suggested_size_usd: f64,
+    pub suggested_size_usd: Usd,
     pub confidence: f64,
     pub side: Side,
+    #[serde(default)]
+    pub order_type: OrderType,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -185,6 +568,21 @@ pub struct StrategyAllocation {
     pub sharpe_ratio: f64,
     pub mode: TradeMode,
     pub params: serde_json::Value,
+    /// `StrategyState::init_weight()` at the time this allocation was
+    /// computed: how much `max_drawdown` discounted the Sharpe-based score
+    /// used to size this allocation. 1.0 for callers that don't model health.
+    #[serde(default = "default_health")]
+    pub init_health: f64,
+    /// `StrategyState::maint_weight()` at the time this allocation was
+    /// computed: the figure the executor sizes *positions* against. The
+    /// portfolio manager's default `k` for it is smaller than for
+    /// `init_health`, so it falls more slowly as drawdown grows.
+    #[serde(default = "default_health")]
+    pub maint_health: f64,
+}
+
+fn default_health() -> f64 {
+    1.0
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -204,6 +602,13 @@ fn default_fitness() -> f64 {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignRequest {
     pub transaction_b64: String,
+    /// Set when `transaction_b64` already carries other participants'
+    /// signatures (e.g. an escrow or program-authority transaction passed
+    /// through multiple signer services in sequence) so the signer fills in
+    /// only its own slot instead of assuming a freshly-built, all-empty
+    /// signature array.
+    #[serde(default)]
+    pub partial: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -211,6 +616,48 @@ pub struct SignResponse {
     pub signed_transaction_b64: String,
 }
 
+/// Response to FROST-Ed25519 round one (`POST /sign/commit`): this signer's
+/// fresh per-session nonce commitment, published so a coordinator can
+/// collect one from each participant before round two. Points are
+/// base64-encoded compressed Edwards points (32 bytes each).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrostCommitResponse {
+    pub session_id: String,
+    pub participant_id: u16,
+    pub hiding_point_b64: String,
+    pub binding_point_b64: String,
+}
+
+/// One participant's published round-one commitment, as relayed back by the
+/// coordinator in `FrostSignRequest` so every signer can compute the same
+/// binding factors and group commitment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrostCommitment {
+    pub participant_id: u16,
+    pub hiding_point_b64: String,
+    pub binding_point_b64: String,
+}
+
+/// Request body for FROST-Ed25519 round two (`POST /sign/respond`): the
+/// message to sign and the full set of round-one commitments, so this
+/// signer can derive its response `z_i` without a second round-trip to
+/// fetch what the other participants published.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrostSignRequest {
+    pub session_id: String,
+    pub message_b64: String,
+    pub commitments: Vec<FrostCommitment>,
+}
+
+/// This signer's round-two response share. The coordinator sums every
+/// participant's `z_b64` (plus the group commitment `R` derivable from the
+/// same commitment set) into the final standard Ed25519 signature.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrostSignResponse {
+    pub participant_id: u16,
+    pub z_b64: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BacktestResult {
     pub spec_id: String,
@@ -0,0 +1,136 @@
+//! Idempotent, ordered schema migrations for the shared `trades` SQLite
+//! database. The executor and the position manager both open the same file
+//! (both read `DATABASE_PATH`) with their own `Database` wrapper, so this
+//! list has to live in one place and be run by both binaries on every
+//! `Database::new` — two independently-numbered migration lists against the
+//! same file would silently race on which service's columns/tables actually
+//! get created, since `schema_migrations` is keyed only on the bare integer
+//! version with no content check.
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+
+pub struct Migration {
+    pub version: i64,
+    pub description: &'static str,
+    pub up_sql: &'static str,
+}
+
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "create trades table (core columns)",
+        up_sql: "CREATE TABLE IF NOT EXISTS trades (
+            id INTEGER PRIMARY KEY,
+            strategy_id TEXT NOT NULL,
+            token_address TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            amount_usd REAL NOT NULL,
+            status TEXT NOT NULL,
+            signature TEXT,
+            entry_time INTEGER NOT NULL,
+            entry_price_usd REAL NOT NULL,
+            confidence REAL NOT NULL,
+            side TEXT NOT NULL
+        )",
+    },
+    Migration {
+        version: 2,
+        description: "add trade close fields",
+        up_sql: "ALTER TABLE trades ADD COLUMN close_time INTEGER;
+                  ALTER TABLE trades ADD COLUMN close_price_usd REAL;
+                  ALTER TABLE trades ADD COLUMN pnl_usd REAL;",
+    },
+    Migration {
+        version: 3,
+        description: "add highest_price_usd for trailing-stop tracking",
+        up_sql: "ALTER TABLE trades ADD COLUMN highest_price_usd REAL;",
+    },
+    Migration {
+        version: 4,
+        description: "add expiry/rollover tracking columns",
+        up_sql: "ALTER TABLE trades ADD COLUMN expiry_at INTEGER;
+                  ALTER TABLE trades ADD COLUMN rolled_from_trade_id INTEGER;",
+    },
+    Migration {
+        version: 5,
+        description: "create candles table",
+        up_sql: "CREATE TABLE IF NOT EXISTS candles (
+            id INTEGER PRIMARY KEY,
+            token_address TEXT NOT NULL,
+            interval TEXT NOT NULL,
+            bucket_start_ts INTEGER NOT NULL,
+            open REAL NOT NULL,
+            high REAL NOT NULL,
+            low REAL NOT NULL,
+            close REAL NOT NULL,
+            volume_usd REAL NOT NULL,
+            UNIQUE(token_address, interval, bucket_start_ts)
+        )",
+    },
+    Migration {
+        version: 6,
+        description: "create nav_snapshots table for true NAV/drawdown history",
+        up_sql: "CREATE TABLE IF NOT EXISTS nav_snapshots (
+            id INTEGER PRIMARY KEY,
+            timestamp INTEGER NOT NULL,
+            nav_usd REAL NOT NULL,
+            realized_pnl REAL NOT NULL,
+            unrealized_pnl REAL NOT NULL
+        )",
+    },
+    Migration {
+        version: 7,
+        description: "add quote out-amount columns for exact on-chain sizing",
+        up_sql: "ALTER TABLE trades ADD COLUMN quote_out_amount_raw TEXT;
+                  ALTER TABLE trades ADD COLUMN quote_out_amount_decimals INTEGER;",
+    },
+    Migration {
+        version: 8,
+        description: "add funding rollover schedule and settled-funding tracking",
+        up_sql: "ALTER TABLE trades ADD COLUMN funding_rollover_at INTEGER;
+                  ALTER TABLE trades ADD COLUMN funding_settled_usd REAL;",
+    },
+    Migration {
+        version: 9,
+        description: "add per-trade trailing-stop override for OrderType::TrailingStop entries",
+        up_sql: "ALTER TABLE trades ADD COLUMN trail_percent_override REAL;",
+    },
+];
+
+/// Apply every migration in `MIGRATIONS` that hasn't already run against
+/// `conn`, in order. Safe to call on every `Database::new`, from either the
+/// executor or the position manager: already-applied versions are skipped
+/// via the `schema_migrations` table, so whichever service opens the file
+/// first runs every migration and the other is a no-op.
+pub fn run(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            applied_at INTEGER NOT NULL
+        )",
+    )
+    .context("Failed to create schema_migrations table")?;
+
+    for migration in MIGRATIONS {
+        let already_applied: bool = conn
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM schema_migrations WHERE version = ?1)",
+                [migration.version],
+                |row| row.get(0),
+            )
+            .context("Failed to check schema_migrations")?;
+        if already_applied {
+            continue;
+        }
+
+        conn.execute_batch(migration.up_sql)
+            .with_context(|| format!("Migration {} ({}) failed", migration.version, migration.description))?;
+        conn.execute(
+            "INSERT INTO schema_migrations (version, applied_at) VALUES (?1, strftime('%s', 'now'))",
+            [migration.version],
+        )
+        .with_context(|| format!("Failed to record migration {} as applied", migration.version))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+/// Per-token reorder/dedup gate for price ticks arriving from multiple producers
+/// (or after a reconnect replaying a stream). Keeps the last-accepted timestamp
+/// per `token_address` and rejects any tick that is not strictly newer, so a
+/// stale retransmit can never overwrite a price that already moved forward.
+#[derive(Debug, Default)]
+pub struct TickGate {
+    last_accepted_ms: HashMap<String, i64>,
+}
+
+impl TickGate {
+    pub fn new() -> Self {
+        Self {
+            last_accepted_ms: HashMap::new(),
+        }
+    }
+
+    /// Returns `true` if `timestamp_ms` is newer than the last accepted timestamp
+    /// for `token_address` and records it as the new high-water mark. Ticks with
+    /// `timestamp_ms <= 0` (producers without a real clock) are always accepted
+    /// and never update the high-water mark, preserving today's behavior for them.
+    pub fn accept(&mut self, token_address: &str, timestamp_ms: i64) -> bool {
+        if timestamp_ms <= 0 {
+            return true;
+        }
+
+        match self.last_accepted_ms.get(token_address) {
+            Some(&last) if timestamp_ms <= last => false,
+            _ => {
+                self.last_accepted_ms
+                    .insert(token_address.to_string(), timestamp_ms);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_stale_and_duplicate_ticks() {
+        let mut gate = TickGate::new();
+        assert!(gate.accept("SOL", 100));
+        assert!(gate.accept("SOL", 200));
+        assert!(!gate.accept("SOL", 200));
+        assert!(!gate.accept("SOL", 150));
+        assert!(gate.accept("SOL", 201));
+    }
+
+    #[test]
+    fn tracks_tokens_independently() {
+        let mut gate = TickGate::new();
+        assert!(gate.accept("SOL", 100));
+        assert!(gate.accept("BONK", 50));
+        assert!(!gate.accept("SOL", 50));
+        assert!(gate.accept("BONK", 51));
+    }
+
+    #[test]
+    fn always_accepts_unstamped_ticks() {
+        let mut gate = TickGate::new();
+        assert!(gate.accept("SOL", 0));
+        assert!(gate.accept("SOL", 0));
+    }
+}
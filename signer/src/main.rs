@@ -1,18 +1,73 @@
-use anyhow::{anyhow, Result};
+mod frost;
+
+use anyhow::{anyhow, Context, Result};
 use axum::{extract::State, http::StatusCode, routing::{get, post}, Json, Router};
-use shared_models::{SignRequest, SignResponse};
+use curve25519_dalek::{edwards::CompressedEdwardsY, scalar::Scalar};
+use rand::RngCore;
+use shared_models::{FrostCommitResponse, FrostSignRequest, FrostSignResponse, SignRequest, SignResponse};
 use solana_sdk::{
     signature::{read_keypair_file, Keypair, Signer},
     transaction::VersionedTransaction,
     message::VersionedMessage,
 };
-use std::{env, net::SocketAddr, sync::Arc};
+use std::{collections::HashMap, env, net::SocketAddr, sync::Arc};
+use tokio::sync::Mutex;
 use tracing::{error, info, instrument, level_filters::LevelFilter};
 use tracing_subscriber::EnvFilter;
 use base64::{Engine as _, engine::general_purpose};
 
 struct AppState {
     keypair: Keypair,
+    /// This instance's FROST-Ed25519 share, loaded from `FROST_KEY_SHARE_PATH`
+    /// if set. `None` means this signer only serves the single-key `/sign`
+    /// route — threshold mode is opt-in per instance.
+    frost_share: Option<frost::KeyShare>,
+    /// Round-one nonces awaiting a matching round-two `/sign/respond` call,
+    /// keyed by the session id `/sign/commit` generated. A session is
+    /// consumed (and its nonces dropped) on first use to guard against reuse.
+    frost_sessions: Mutex<HashMap<String, frost::NonceCommitment>>,
+}
+
+/// On-disk format of `FROST_KEY_SHARE_PATH`, the output of an offline
+/// `frost::generate_shares` DKG run distributed to each participant.
+#[derive(serde::Deserialize)]
+struct FrostKeyShareFile {
+    participant_id: u16,
+    secret_share_b64: String,
+    group_public_key_b64: String,
+}
+
+fn load_frost_share() -> Result<Option<frost::KeyShare>> {
+    let Ok(path) = env::var("FROST_KEY_SHARE_PATH") else {
+        return Ok(None);
+    };
+    let contents = std::fs::read_to_string(&path).with_context(|| format!("Failed to read FROST key share at {}", path))?;
+    let file: FrostKeyShareFile = serde_json::from_str(&contents).context("Malformed FROST key share file")?;
+    Ok(Some(frost::KeyShare {
+        participant_id: file.participant_id,
+        secret_share: decode_scalar(&file.secret_share_b64)?,
+        group_public_key: decode_point(&file.group_public_key_b64)?,
+    }))
+}
+
+fn decode_scalar(b64: &str) -> Result<Scalar> {
+    let bytes = general_purpose::STANDARD.decode(b64).context("Malformed base64 scalar")?;
+    let bytes: [u8; 32] = bytes.try_into().map_err(|_| anyhow!("Scalar must be 32 bytes"))?;
+    Option::<Scalar>::from(Scalar::from_canonical_bytes(bytes)).ok_or_else(|| anyhow!("Non-canonical scalar encoding"))
+}
+
+fn decode_point(b64: &str) -> Result<curve25519_dalek::edwards::EdwardsPoint> {
+    let bytes = general_purpose::STANDARD.decode(b64).context("Malformed base64 point")?;
+    let bytes: [u8; 32] = bytes.try_into().map_err(|_| anyhow!("Point must be 32 bytes"))?;
+    CompressedEdwardsY(bytes).decompress().ok_or_else(|| anyhow!("Invalid compressed Edwards point"))
+}
+
+fn encode_point(point: &curve25519_dalek::edwards::EdwardsPoint) -> String {
+    general_purpose::STANDARD.encode(point.compress().as_bytes())
+}
+
+fn encode_scalar(scalar: &Scalar) -> String {
+    general_purpose::STANDARD.encode(scalar.as_bytes())
 }
 
 #[tokio::main]
@@ -32,11 +87,22 @@ async fn main() -> Result<()> {
     let pubkey = keypair.pubkey();
     info!(%pubkey, "Wallet loaded successfully.");
 
-    let state = Arc::new(AppState { keypair });
+    let frost_share = load_frost_share()?;
+    if let Some(share) = &frost_share {
+        info!(participant_id = share.participant_id, "FROST threshold-signing share loaded.");
+    }
+
+    let state = Arc::new(AppState {
+        keypair,
+        frost_share,
+        frost_sessions: Mutex::new(HashMap::new()),
+    });
 
     let app = Router::new()
         .route("/pubkey", get(get_pubkey))
         .route("/sign", post(sign_transaction))
+        .route("/sign/commit", post(frost_commit))
+        .route("/sign/respond", post(frost_respond))
         .with_state(state);
 
     let addr = SocketAddr::from(([0, 0, 0, 0], 8989));
@@ -52,6 +118,10 @@ async fn get_pubkey(State(state): State<Arc<AppState>>) -> Json<serde_json::Valu
     Json(serde_json::json!({ "pubkey": state.keypair.pubkey().to_string() }))
 }
 
+/// Signs whatever message bytes the transaction compiles to, durable-nonce
+/// or recent-blockhash alike — a durable-nonce transaction's `AdvanceNonceAccount`
+/// instruction and nonce-as-blockhash are just more instruction/field bytes
+/// from this handler's point of view, so no special-casing is needed here.
 #[instrument(skip(state, request), name="sign_transaction_handler")]
 async fn sign_transaction(
     State(state): State<Arc<AppState>>,
@@ -73,14 +143,47 @@ async fn sign_transaction(
         }
     };
 
+    let num_required_signatures = match &tx.message {
+        VersionedMessage::Legacy(legacy) => legacy.header.num_required_signatures,
+        VersionedMessage::V0(v0) => v0.header.num_required_signatures,
+    } as usize;
+
+    let our_pubkey = state.keypair.pubkey();
+    let signer_index = match tx
+        .message
+        .static_account_keys()
+        .iter()
+        .take(num_required_signatures)
+        .position(|key| *key == our_pubkey)
+    {
+        Some(index) => index,
+        None => {
+            error!(%our_pubkey, "This key is not a required signer of the transaction");
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
+
+    if tx.signatures.len() != num_required_signatures {
+        if !request.partial {
+            error!(
+                expected = num_required_signatures,
+                actual = tx.signatures.len(),
+                "Signature array length does not match num_required_signatures"
+            );
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        tx.signatures.resize(num_required_signatures, Default::default());
+    }
+
     // Get the message to sign
     let message = match &tx.message {
         VersionedMessage::Legacy(legacy) => legacy.serialize(),
         VersionedMessage::V0(v0) => v0.serialize(),
     };
 
-    // Sign the transaction with partial signatures  
-    tx.signatures[0] = state.keypair.sign_message(&message);
+    // Fill in only our own slot so any other co-signers' signatures already
+    // present on a partially-signed transaction are left untouched.
+    tx.signatures[signer_index] = state.keypair.sign_message(&message);
 
     let signed_tx_bytes = match bincode::serialize(&tx) {
         Ok(bytes) => bytes,
@@ -95,3 +198,90 @@ async fn sign_transaction(
         signed_transaction_b64: general_purpose::STANDARD.encode(&signed_tx_bytes),
     }))
 }
+
+/// FROST round one: generates a fresh nonce commitment, stashes the nonces
+/// server-side under a new session id, and publishes the commitment points.
+#[instrument(skip(state), name="frost_commit_handler")]
+async fn frost_commit(State(state): State<Arc<AppState>>) -> Result<Json<FrostCommitResponse>, StatusCode> {
+    let Some(share) = &state.frost_share else {
+        error!("FROST commit requested but this signer has no key share configured");
+        return Err(StatusCode::NOT_IMPLEMENTED);
+    };
+
+    let nonce = frost::commit();
+    let response = FrostCommitResponse {
+        session_id: new_session_id(),
+        participant_id: share.participant_id,
+        hiding_point_b64: encode_point(&nonce.hiding_point),
+        binding_point_b64: encode_point(&nonce.binding_point),
+    };
+
+    state.frost_sessions.lock().await.insert(response.session_id.clone(), nonce);
+    info!(session_id = %response.session_id, "FROST round one commitment published.");
+    Ok(Json(response))
+}
+
+/// FROST round two: consumes the session's nonces (a session can only ever
+/// be responded to once) and returns this participant's response share `z_i`
+/// for the coordinator to sum with every other participant's.
+#[instrument(skip(state, request), name="frost_respond_handler")]
+async fn frost_respond(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<FrostSignRequest>,
+) -> Result<Json<FrostSignResponse>, StatusCode> {
+    let Some(share) = &state.frost_share else {
+        error!("FROST respond requested but this signer has no key share configured");
+        return Err(StatusCode::NOT_IMPLEMENTED);
+    };
+
+    let Some(nonce) = state.frost_sessions.lock().await.remove(&request.session_id) else {
+        error!(session_id = %request.session_id, "Unknown or already-consumed FROST session");
+        return Err(StatusCode::BAD_REQUEST);
+    };
+
+    let message = match general_purpose::STANDARD.decode(&request.message_b64) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            error!(error = %e, "Failed to decode base64 FROST message");
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
+
+    let commitments: Vec<frost::PublishedCommitment> = match request
+        .commitments
+        .iter()
+        .map(|c| {
+            Ok(frost::PublishedCommitment {
+                participant_id: c.participant_id,
+                hiding_point: decode_point(&c.hiding_point_b64)?,
+                binding_point: decode_point(&c.binding_point_b64)?,
+            })
+        })
+        .collect::<Result<_>>()
+    {
+        Ok(commitments) => commitments,
+        Err(e) => {
+            error!(error = %e, "Malformed FROST commitment in request");
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
+
+    let z = match frost::sign(share, &nonce, &commitments, &message) {
+        Ok(z) => z,
+        Err(e) => {
+            error!(error = %e, "FROST round two signing failed");
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
+
+    info!(session_id = %request.session_id, "FROST round two response computed.");
+    Ok(Json(FrostSignResponse { participant_id: share.participant_id, z_b64: encode_scalar(&z) }))
+}
+
+/// A fresh, unguessable id for a FROST signing session, so round two can be
+/// matched back to the exact nonces round one generated.
+fn new_session_id() -> String {
+    let mut bytes = [0u8; 16];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}